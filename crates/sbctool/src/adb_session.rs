@@ -0,0 +1,377 @@
+use adb_client::{search_adb_devices, ADBDeviceExt, ADBServer, ADBTcpDevice, ADBUSBDevice, DeviceState, RebootType};
+use crate::error::SbcError;
+use crate::interactive;
+use anyhow::Result;
+use std::io::{Read, Write};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, SocketAddrV4, TcpStream, ToSocketAddrs};
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// Appended to every command so the remote exit status survives `shell_command`
+const EXIT_MARKER: &str = "__SBCTOOL_EXIT:";
+
+/// Default adbd TCP port when `-s <ip>` is given without a port
+const DEFAULT_ADB_TCP_PORT: u16 = 5555;
+
+/// Port of the adb server when `--adb-server` gives only a host
+const DEFAULT_ADB_SERVER_PORT: u16 = 5037;
+
+/// adb server from `--adb-server`; localhost:5037 when unset
+static SERVER_ADDRESS: OnceLock<SocketAddrV4> = OnceLock::new();
+
+/// How long to wait before retrying a device the server reports as offline
+const OFFLINE_RETRY_DELAY: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// How a target is reached; shared by the TUI, `info` and `connect` so they agree
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AdbRoute {
+    /// "auto": a directly attached USB device, else the first ready device on the adb server
+    Auto,
+    /// `ip`, `ip:port`, `[ipv6]`, `[ipv6]:port` or `hostname:port`: adbd over TCP, no server needed
+    Tcp(SocketAddr),
+    /// Anything else is a serial known to the adb server
+    Server,
+}
+
+impl AdbRoute {
+    pub fn for_target(target: &str) -> Self {
+        if target == "auto" {
+            AdbRoute::Auto
+        } else if let Some(address) = parse_tcp_target(target) {
+            AdbRoute::Tcp(address)
+        } else {
+            AdbRoute::Server
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            AdbRoute::Auto => "USB, else adb server",
+            AdbRoute::Tcp(_) => "direct TCP",
+            AdbRoute::Server => "adb server",
+        }
+    }
+}
+
+pub struct ADBSession {
+    target: String,
+}
+
+impl ADBSession {
+    pub fn new(target: &str) -> Self {
+        Self {
+            target: target.to_string(),
+        }
+    }
+
+    pub async fn execute_command(&self, command: &str) -> Result<String> {
+        let mut device = match self.open_device() {
+            Ok(device) => device,
+            Err(_) if self.is_offline() => {
+                // Devices that just (re)connected report "offline" for a moment
+                tokio::time::sleep(OFFLINE_RETRY_DELAY).await;
+                self.open_device()?
+            }
+            Err(e) => return Err(e),
+        };
+
+        let wrapped = format!("{}; echo {}$?", command, EXIT_MARKER);
+        let mut output = Vec::new();
+        device.shell_command(&[wrapped.as_str()], &mut output)?;
+
+        let output = String::from_utf8_lossy(&output);
+        Self::split_exit_status(&output)
+    }
+
+    /// Reboots through adbd's reboot service instead of a shell command
+    pub fn reboot(&self) -> Result<()> {
+        self.open_device()?.reboot(RebootType::System)?;
+        Ok(())
+    }
+
+    /// Interactive `adb shell` on the device, with the local terminal in raw mode until it exits
+    pub fn interactive_shell(&self) -> Result<()> {
+        let mut device = self.open_device()?;
+        let _raw_mode = interactive::RawMode::enable()?;
+        device.shell(&mut std::io::stdin(), Box::new(std::io::stdout()))?;
+        Ok(())
+    }
+
+    /// Copies a file off the device through adbd's sync service, checking the byte count against `stat`
+    pub fn pull<F>(&self, remote_path: &str, local_path: &Path, progress: F) -> Result<u64>
+    where
+        F: FnMut(u64, u64),
+    {
+        let mut device = self.open_device()?;
+        let size = device.stat(remote_path)?.file_size as u64;
+
+        let file = std::fs::File::create(local_path)?;
+        let mut writer = ProgressWriter { inner: file, transferred: 0, total: size, progress };
+        device.pull(&remote_path, &mut writer)?;
+        writer.flush()?;
+
+        if writer.transferred != size {
+            return Err(anyhow::anyhow!("Transfer incomplete: received {} of {} bytes", writer.transferred, size));
+        }
+        Ok(writer.transferred)
+    }
+
+    /// Copies a file onto the device; a `remote_path` ending in '/' keeps the local file name
+    pub fn push<F>(&self, local_path: &Path, remote_path: &str, progress: F) -> Result<u64>
+    where
+        F: FnMut(u64, u64),
+    {
+        let remote_path = match (remote_path.ends_with('/'), local_path.file_name()) {
+            (true, Some(name)) => format!("{}{}", remote_path, name.to_string_lossy()),
+            _ => remote_path.to_string(),
+        };
+        let file = std::fs::File::open(local_path)?;
+        let size = file.metadata()?.len();
+
+        let mut device = self.open_device()?;
+        let mut reader = ProgressReader { inner: file, transferred: 0, total: size, progress };
+        device.push(&mut reader, &remote_path)?;
+
+        // The sync protocol doesn't echo a count, so ask the device what it stored
+        let stored = device.stat(&remote_path)?.file_size as u64;
+        if stored != size {
+            return Err(anyhow::anyhow!("Transfer incomplete: device has {} of {} bytes", stored, size));
+        }
+        Ok(reader.transferred)
+    }
+
+    fn split_exit_status(output: &str) -> Result<String> {
+        let Some(pos) = output.rfind(EXIT_MARKER) else {
+            return Ok(output.trim().to_string());
+        };
+
+        let stdout = output[..pos].trim().to_string();
+        let exit_status: i32 = output[pos + EXIT_MARKER.len()..].trim().parse().unwrap_or(-1);
+
+        if exit_status == 0 {
+            Ok(stdout)
+        } else {
+            Err(SbcError::CommandFailed { exit_status, output: stdout }.into())
+        }
+    }
+
+    fn open_device(&self) -> Result<Box<dyn ADBDeviceExt>> {
+        match AdbRoute::for_target(&self.target) {
+            AdbRoute::Auto => {
+                // Prefer a directly attached USB device, fall back to the adb server
+                if let Ok(Some((vendor_id, product_id))) = search_adb_devices() {
+                    if let Ok(device) = ADBUSBDevice::new(vendor_id, product_id) {
+                        return Ok(Box::new(device));
+                    }
+                }
+            }
+            AdbRoute::Tcp(address) => return Ok(Box::new(ADBTcpDevice::new(address)?)),
+            AdbRoute::Server => {}
+        }
+
+        let mut server = server();
+        let serial = self.pick_server_serial(&mut server)?;
+        Ok(Box::new(server.get_device_by_name(&serial)?))
+    }
+
+    /// Picks the serial to talk to through the adb server, explaining devices that
+    /// are listed but can't run commands (unauthorized, offline, ...)
+    fn pick_server_serial(&self, server: &mut ADBServer) -> Result<String> {
+        let devices = server
+            .devices_long()
+            .map_err(|e| anyhow::anyhow!("ADB server at {} is not reachable: {}", server_address(), e))?;
+
+        if self.target == "auto" {
+            if let Some(device) = devices.iter().find(|device| matches!(device.state, DeviceState::Device)) {
+                return Ok(device.identifier.clone());
+            }
+            return match devices.first() {
+                Some(device) => Err(Self::state_error(&device.identifier, &device.state)),
+                None => Err(SbcError::NoTarget("No ADB devices found".to_string()).into()),
+            };
+        }
+
+        match devices.iter().find(|device| device.identifier == self.target) {
+            Some(device) if matches!(device.state, DeviceState::Device) => Ok(device.identifier.clone()),
+            Some(device) => Err(Self::state_error(&device.identifier, &device.state)),
+            // Not listed; let the server report what it knows about the serial
+            None => Ok(self.target.clone()),
+        }
+    }
+
+    fn state_error(serial: &str, state: &DeviceState) -> anyhow::Error {
+        match state {
+            DeviceState::Unauthorized => anyhow::anyhow!("device {} is unauthorized — check the allow dialog on the device", serial),
+            DeviceState::Offline => anyhow::anyhow!("device {} is offline — reconnect it or run 'adb reconnect offline'", serial),
+            other => anyhow::anyhow!("device {} is not ready (state: {:?})", serial, other),
+        }
+    }
+
+    fn is_offline(&self) -> bool {
+        let Ok(devices) = server().devices_long() else {
+            return false;
+        };
+        devices.iter().any(|device| {
+            matches!(device.state, DeviceState::Offline) && (self.target == "auto" || device.identifier == self.target)
+        })
+    }
+
+    /// One line per device: a directly attached USB device, then everything the adb server lists
+    pub fn describe_devices() -> Vec<String> {
+        let mut lines = Vec::new();
+        if let Ok(Some((vendor_id, product_id))) = search_adb_devices() {
+            lines.push(format!("USB {:04x}:{:04x} (direct, no server needed)", vendor_id, product_id));
+        }
+        if let Ok(devices) = server().devices_long() {
+            for device in devices {
+                lines.push(format!("{:<24} {:?}", device.identifier, device.state));
+            }
+        }
+        lines
+    }
+
+    /// Serials currently listed by the adb server (empty when no server is running)
+    pub fn server_serials() -> Vec<String> {
+        server()
+            .devices_long()
+            .map(|devices| devices.into_iter().map(|device| device.identifier).collect())
+            .unwrap_or_default()
+    }
+}
+
+/// Counts bytes written through it for `pull` progress
+struct ProgressWriter<W, F> {
+    inner: W,
+    transferred: u64,
+    total: u64,
+    progress: F,
+}
+
+impl<W: Write, F: FnMut(u64, u64)> Write for ProgressWriter<W, F> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let n = self.inner.write(buf)?;
+        self.transferred += n as u64;
+        (self.progress)(self.transferred, self.total);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Counts bytes read through it for `push` progress
+struct ProgressReader<R, F> {
+    inner: R,
+    transferred: u64,
+    total: u64,
+    progress: F,
+}
+
+impl<R: Read, F: FnMut(u64, u64)> Read for ProgressReader<R, F> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.transferred += n as u64;
+        (self.progress)(self.transferred, self.total);
+        Ok(n)
+    }
+}
+
+/// Points every server connection at `host[:port]` instead of localhost:5037, failing early
+/// when nothing listens there
+pub fn set_server_address(address: &str) -> Result<()> {
+    let with_port = if address.contains(':') {
+        address.to_string()
+    } else {
+        format!("{}:{}", address, DEFAULT_ADB_SERVER_PORT)
+    };
+    // ADBServer only speaks IPv4
+    let resolved = with_port
+        .to_socket_addrs()
+        .map_err(|e| anyhow::anyhow!("Invalid --adb-server '{}': {}", address, e))?
+        .find_map(|addr| match addr {
+            SocketAddr::V4(addr) => Some(addr),
+            SocketAddr::V6(_) => None,
+        })
+        .ok_or_else(|| anyhow::anyhow!("--adb-server '{}' has no IPv4 address", address))?;
+
+    TcpStream::connect_timeout(&SocketAddr::V4(resolved), std::time::Duration::from_secs(3))
+        .map_err(|e| anyhow::anyhow!("ADB server at {} is not reachable: {}", resolved, e))?;
+    let _ = SERVER_ADDRESS.set(resolved);
+    Ok(())
+}
+
+/// The `--adb-server` address, if one was given
+pub fn server_override() -> Option<SocketAddrV4> {
+    SERVER_ADDRESS.get().copied()
+}
+
+fn server_address() -> SocketAddrV4 {
+    SERVER_ADDRESS
+        .get()
+        .copied()
+        .unwrap_or(SocketAddrV4::new(Ipv4Addr::LOCALHOST, DEFAULT_ADB_SERVER_PORT))
+}
+
+fn server() -> ADBServer {
+    ADBServer::new(server_address())
+}
+
+/// Accepts `ip`, `ip:port`, `[ipv6]`, `[ipv6]:port` and `hostname:port`.
+/// IPv6 addresses are never glued to a port with `format!`, which would not parse.
+fn parse_tcp_target(target: &str) -> Option<SocketAddr> {
+    // ip:port and [ipv6]:port
+    if let Ok(address) = target.parse::<SocketAddr>() {
+        return Some(address);
+    }
+
+    // Bare ip or [ipv6] without a port
+    let bare = target
+        .strip_prefix('[')
+        .and_then(|rest| rest.strip_suffix(']'))
+        .unwrap_or(target);
+    if let Ok(ip) = bare.parse::<IpAddr>() {
+        return Some(SocketAddr::new(ip, DEFAULT_ADB_TCP_PORT));
+    }
+
+    // hostname:port; a bare name stays an adb server serial
+    let (host, port) = target.rsplit_once(':')?;
+    let port: u16 = port.parse().ok()?;
+    (host, port).to_socket_addrs().ok()?.next()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tcp_target_ipv4() {
+        assert_eq!(parse_tcp_target("192.168.1.215:5555"), Some("192.168.1.215:5555".parse().unwrap()));
+        assert_eq!(parse_tcp_target("192.168.1.215:5037"), Some("192.168.1.215:5037".parse().unwrap()));
+    }
+
+    #[test]
+    fn tcp_target_bare_ip_gets_default_port() {
+        assert_eq!(parse_tcp_target("192.168.1.215"), Some("192.168.1.215:5555".parse().unwrap()));
+        assert_eq!(parse_tcp_target("fe80::1"), Some("[fe80::1]:5555".parse().unwrap()));
+    }
+
+    #[test]
+    fn tcp_target_bracketed_ipv6() {
+        assert_eq!(parse_tcp_target("[fd00::20]"), Some("[fd00::20]:5555".parse().unwrap()));
+        assert_eq!(parse_tcp_target("[fd00::20]:5556"), Some("[fd00::20]:5556".parse().unwrap()));
+    }
+
+    #[test]
+    fn tcp_target_hostname() {
+        let address = parse_tcp_target("localhost:5556").expect("localhost resolves");
+        assert!(address.ip().is_loopback());
+        assert_eq!(address.port(), 5556);
+    }
+
+    #[test]
+    fn usb_serial_is_not_tcp() {
+        assert_eq!(parse_tcp_target("0123456789ABCDEF"), None);
+        assert_eq!(parse_tcp_target("emulator-5554"), None);
+    }
+}
@@ -0,0 +1,44 @@
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Whether non-TUI output may use ANSI colors; the TUI styles itself through ratatui
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Colors are off with `--no-color`, a non-empty NO_COLOR (https://no-color.org)
+/// or when stdout is piped or redirected
+pub fn init(no_color: bool) {
+    let no_color_env = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+    ENABLED.store(!no_color && !no_color_env && std::io::stdout().is_terminal(), Ordering::Relaxed);
+}
+
+fn paint(code: &str, text: &str) -> String {
+    if ENABLED.load(Ordering::Relaxed) {
+        format!("\x1b[{}m{}\x1b[0m", code, text)
+    } else {
+        text.to_string()
+    }
+}
+
+pub fn bold(text: &str) -> String {
+    paint("1", text)
+}
+
+pub fn cyan(text: &str) -> String {
+    paint("36", text)
+}
+
+pub fn red(text: &str) -> String {
+    paint("31", text)
+}
+
+/// Same thresholds as the TUI: red from 80°C, yellow from 65°C
+pub fn temperature(temp: f64) -> String {
+    let text = format!("{:.1}°C", temp);
+    if temp >= 80.0 {
+        paint("31", &text)
+    } else if temp >= 65.0 {
+        paint("33", &text)
+    } else {
+        paint("32", &text)
+    }
+}
@@ -0,0 +1,73 @@
+use anyhow::Result;
+use crate::color;
+use crate::snapshot;
+use crate::tui::SystemInfo;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Longest value shown per column; longer ones (kernel cmdline, module lists) are cut
+const MAX_COLUMN_WIDTH: usize = 40;
+
+/// Prints the fields of two collections of the same board side by side, mismatches in red.
+/// `only_mismatches` drops the rows that agree; `all` keeps the fields that change on every
+/// collection (CPU frequencies, top processes), which never agree between two runs.
+pub fn print(left: (&str, &SystemInfo), right: (&str, &SystemInfo), only_mismatches: bool, all: bool) -> Result<()> {
+    let mut left_fields = BTreeMap::new();
+    let mut right_fields = BTreeMap::new();
+    snapshot::flatten(&serde_json::to_value(left.1)?, "", &mut left_fields);
+    snapshot::flatten(&serde_json::to_value(right.1)?, "", &mut right_fields);
+
+    let volatile = |key: &str| snapshot::VOLATILE_FIELDS.iter().any(|field| key.starts_with(field));
+    let keys: BTreeSet<&String> = left_fields
+        .keys()
+        .chain(right_fields.keys())
+        .filter(|key| all || !volatile(key.as_str()))
+        .collect();
+
+    let missing = "-".to_string();
+    let rows: Vec<(&String, &String, &String)> = keys
+        .into_iter()
+        .map(|key| (key, left_fields.get(key).unwrap_or(&missing), right_fields.get(key).unwrap_or(&missing)))
+        .collect();
+    let mismatches = rows.iter().filter(|(_, left, right)| left != right).count();
+
+    let key_width = rows.iter().map(|(key, _, _)| key.chars().count()).max().unwrap_or(0);
+    let value_width = rows
+        .iter()
+        .map(|(_, left, _)| left.chars().count().min(MAX_COLUMN_WIDTH))
+        .chain([left.0.chars().count()])
+        .max()
+        .unwrap_or(0);
+
+    println!(
+        "{}  {}  {}",
+        pad("", key_width),
+        color::bold(&pad(left.0, value_width)),
+        color::bold(right.0)
+    );
+    for (key, left_value, right_value) in &rows {
+        let matching = left_value == right_value;
+        if only_mismatches && matching {
+            continue;
+        }
+        let (left_cell, right_cell) = (pad(&cut(left_value), value_width), cut(right_value));
+        if matching {
+            println!("{}  {}  {}", color::cyan(&pad(key, key_width)), left_cell, right_cell);
+        } else {
+            println!("{}  {}  {}", color::cyan(&pad(key, key_width)), color::red(&left_cell), color::red(&right_cell));
+        }
+    }
+    println!("{} of {} fields differ", mismatches, rows.len());
+    Ok(())
+}
+
+fn cut(value: &str) -> String {
+    if value.chars().count() <= MAX_COLUMN_WIDTH {
+        value.to_string()
+    } else {
+        format!("{}…", value.chars().take(MAX_COLUMN_WIDTH - 1).collect::<String>())
+    }
+}
+
+fn pad(text: &str, width: usize) -> String {
+    format!("{:<width$}", text, width = width)
+}
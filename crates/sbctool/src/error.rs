@@ -0,0 +1,46 @@
+use thiserror::Error;
+
+/// Failure kinds callers may want to tell apart. Functions keep returning `anyhow::Result`;
+/// recover the kind with `err.downcast_ref::<SbcError>()`.
+#[derive(Debug, Error)]
+pub enum SbcError {
+    #[error("SSH authentication failed for {user}; tried {attempts}")]
+    AuthFailed { user: String, attempts: String },
+    #[error("Connection to {host}:{port} timed out after {seconds}s")]
+    ConnectTimeout { host: String, port: u16, seconds: u64 },
+    #[error("Failed to connect to {host}:{port}: {reason}")]
+    Unreachable { host: String, port: u16, reason: String },
+    /// `output` is stderr for SSH and the combined output for ADB, which has no separate stderr
+    #[error("Command failed with exit status {exit_status}{}", detail(.output))]
+    CommandFailed { exit_status: i32, output: String },
+    /// The server wouldn't start the command on a working session, e.g. one too long for it
+    #[error("The server refused to run the command: {0}")]
+    ExecRefused(String),
+    #[error("Unknown connection type: {0}")]
+    UnknownConnectionType(String),
+    /// Nothing to connect to: no ADB device, an unknown ssh alias or a host name that doesn't resolve
+    #[error("{0}")]
+    NoTarget(String),
+}
+
+impl SbcError {
+    /// Process exit status for this failure; 1 is left for everything else and 2 is clap's usage error
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            SbcError::NoTarget(_) => 3,
+            SbcError::AuthFailed { .. } => 4,
+            SbcError::ConnectTimeout { .. } | SbcError::Unreachable { .. } => 5,
+            SbcError::CommandFailed { .. } | SbcError::ExecRefused(_) => 6,
+            SbcError::UnknownConnectionType(_) => 1,
+        }
+    }
+}
+
+fn detail(output: &str) -> String {
+    let output = output.trim();
+    if output.is_empty() {
+        String::new()
+    } else {
+        format!(": {}", output)
+    }
+}
@@ -0,0 +1,104 @@
+use anyhow::Result;
+use crate::paths;
+use regex::Regex;
+use serde::Deserialize;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+/// User-defined probes, e.g.
+///
+/// ```toml
+/// [[collector]]
+/// label = "Board serial"
+/// command = "cat /proc/device-tree/serial-number"
+/// regex = "([0-9a-f]+)"
+/// ```
+const CONFIG_PATH: &str = "~/.config/sbctool/collectors.toml";
+
+#[derive(Deserialize)]
+struct CollectorsFile {
+    #[serde(default)]
+    collector: Vec<CollectorConfig>,
+}
+
+#[derive(Deserialize)]
+struct CollectorConfig {
+    label: String,
+    command: String,
+    regex: Option<String>,
+}
+
+/// One extra probe run alongside the built-in commands
+pub struct ExtraCollector {
+    pub label: String,
+    pub command: String,
+    regex: Option<Regex>,
+}
+
+impl ExtraCollector {
+    /// First capture group (or the whole match) of the regex, else the first output line
+    pub fn extract(&self, output: &str) -> Option<String> {
+        let value = match &self.regex {
+            Some(regex) => {
+                let captures = regex.captures(output)?;
+                captures.get(1).or_else(|| captures.get(0))?.as_str().trim().to_string()
+            }
+            None => output.lines().next()?.trim().to_string(),
+        };
+        (!value.is_empty()).then_some(value)
+    }
+}
+
+static COLLECTORS: OnceLock<Vec<ExtraCollector>> = OnceLock::new();
+
+/// File from a profile's `collectors`, read instead of `CONFIG_PATH`
+static CONFIG_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+
+/// Must be called before the first `configured()`
+pub fn set_config_path(path: PathBuf) {
+    let _ = CONFIG_OVERRIDE.set(path);
+}
+
+fn config_path() -> PathBuf {
+    CONFIG_OVERRIDE.get().cloned().unwrap_or_else(|| paths::expand(CONFIG_PATH))
+}
+
+/// Collectors from the config file, loaded on first use.
+/// Call once before the TUI starts so config problems are printed to a normal terminal.
+pub fn configured() -> &'static [ExtraCollector] {
+    COLLECTORS.get_or_init(|| match load() {
+        Ok(collectors) => collectors,
+        Err(e) => {
+            eprintln!("Ignoring {}: {}", config_path().display(), e);
+            Vec::new()
+        }
+    })
+}
+
+fn load() -> Result<Vec<ExtraCollector>> {
+    let path = config_path();
+    let content = match std::fs::read_to_string(&path) {
+        Ok(content) => content,
+        // Only the default file is optional; one named by a profile should exist
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound && CONFIG_OVERRIDE.get().is_none() => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+
+    let file: CollectorsFile = toml::from_str(&content)?;
+    let mut collectors = Vec::new();
+    for config in file.collector {
+        let regex = match config.regex.as_deref().map(Regex::new).transpose() {
+            Ok(regex) => regex,
+            Err(e) => {
+                eprintln!("Skipping collector '{}': invalid regex: {}", config.label, e);
+                continue;
+            }
+        };
+        collectors.push(ExtraCollector {
+            label: config.label,
+            command: config.command,
+            regex,
+        });
+    }
+    Ok(collectors)
+}
@@ -0,0 +1,51 @@
+use anyhow::Result;
+use std::io::Read;
+use std::sync::mpsc::{self, Receiver};
+
+/// Local terminal in raw mode while a remote shell owns it, so keys like Ctrl-C reach the board.
+/// Dropping it restores the terminal, also when the shell fails.
+pub struct RawMode;
+
+impl RawMode {
+    pub fn enable() -> Result<Self> {
+        crossterm::terminal::enable_raw_mode()?;
+        Ok(RawMode)
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        let _ = crossterm::terminal::disable_raw_mode();
+    }
+}
+
+/// (columns, rows) of the local terminal, 80x24 when it can't be queried
+pub fn size() -> (u16, u16) {
+    crossterm::terminal::size().unwrap_or((80, 24))
+}
+
+/// $TERM for the remote PTY, so the board's programs use the same escape sequences
+pub fn term_name() -> String {
+    std::env::var("TERM").unwrap_or_else(|_| "xterm-256color".to_string())
+}
+
+/// Reads stdin on its own thread, since it can't be polled portably; the receiver is
+/// disconnected once stdin reaches EOF
+pub fn spawn_stdin_reader() -> Receiver<Vec<u8>> {
+    let (sender, receiver) = mpsc::channel();
+    std::thread::spawn(move || {
+        let mut stdin = std::io::stdin();
+        let mut buffer = [0; 4096];
+        loop {
+            match stdin.read(&mut buffer) {
+                Ok(0) | Err(_) => break,
+                Ok(n) => {
+                    if sender.send(buffer[..n].to_vec()).is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+    });
+    receiver
+}
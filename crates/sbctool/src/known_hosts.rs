@@ -0,0 +1,121 @@
+use anyhow::Result;
+use crate::paths;
+use base64::Engine;
+use ssh2::{HashType, HostKeyType, Session};
+use std::io::Write;
+
+/// sbctool's own trust store, one "[host]:port key-type SHA256:fingerprint" line per server,
+/// independent of OpenSSH's known_hosts format and hashing options
+const KNOWN_HOSTS_PATH: &str = "~/.config/sbctool/known_hosts";
+
+/// Outcome of [`verify`]
+pub enum HostKeyCheck {
+    /// The key matches the stored one or was recorded on first contact
+    Trusted,
+    /// The store couldn't be read or written, so the key wasn't checked
+    Unchecked(String),
+    /// The key differs from the stored one
+    Changed(String),
+}
+
+impl HostKeyCheck {
+    pub fn warning(self) -> Option<String> {
+        match self {
+            HostKeyCheck::Trusted => None,
+            HostKeyCheck::Unchecked(warning) | HostKeyCheck::Changed(warning) => Some(warning),
+        }
+    }
+}
+
+/// Compares the server's host key with the stored one, recording it on first contact.
+/// A changed key is kept in the store, so it is reported as changed until the entry is
+/// removed with `--reset-hostkey`. The store is advisory, so a file that can't be read or
+/// written (read-only home, container) leaves the key unchecked rather than failing.
+pub fn verify(host: &str, port: u16, session: &Session) -> Result<HostKeyCheck> {
+    let (_, key_type) = session
+        .host_key()
+        .ok_or_else(|| anyhow::anyhow!("Server did not send a host key"))?;
+    let hash = session
+        .host_key_hash(HashType::Sha256)
+        .ok_or_else(|| anyhow::anyhow!("Could not hash the server's host key"))?;
+    let key_type = key_type_name(key_type);
+    let fingerprint = format!("SHA256:{}", base64::engine::general_purpose::STANDARD_NO_PAD.encode(hash));
+    let entry_host = host_entry(host, port);
+
+    let entries = match load() {
+        Ok(entries) => entries,
+        Err(e) => return Ok(HostKeyCheck::Unchecked(format!("Could not read {}, host key of {} not checked: {}", KNOWN_HOSTS_PATH, entry_host, e))),
+    };
+    match entries.iter().find(|(stored_host, _, _)| *stored_host == entry_host) {
+        Some((_, stored_type, stored_fingerprint)) if *stored_type == key_type && *stored_fingerprint == fingerprint => Ok(HostKeyCheck::Trusted),
+        Some((_, stored_type, stored_fingerprint)) => Ok(HostKeyCheck::Changed(format!(
+            "Host key for {} changed: stored {} {}, server sent {} {}. If the board was reflashed, run 'sbctool --reset-hostkey {}'",
+            entry_host, stored_type, stored_fingerprint, key_type, fingerprint, host
+        ))),
+        None => match record(&entry_host, key_type, &fingerprint) {
+            Ok(()) => Ok(HostKeyCheck::Trusted),
+            Err(e) => Ok(HostKeyCheck::Unchecked(format!("Could not record the host key of {} in {}: {}", entry_host, KNOWN_HOSTS_PATH, e))),
+        },
+    }
+}
+
+fn record(entry_host: &str, key_type: &str, fingerprint: &str) -> Result<()> {
+    let path = paths::expand(KNOWN_HOSTS_PATH);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut file = std::fs::OpenOptions::new().create(true).append(true).open(&path)?;
+    writeln!(file, "{} {} {}", entry_host, key_type, fingerprint)?;
+    Ok(())
+}
+
+/// Removes the stored key(s) for host:port, returning how many were removed
+pub fn forget(host: &str, port: u16) -> Result<usize> {
+    let entry_host = host_entry(host, port);
+    let entries = load()?;
+    let kept: Vec<&(String, String, String)> = entries.iter().filter(|(stored_host, _, _)| *stored_host != entry_host).collect();
+    let removed = entries.len() - kept.len();
+
+    if removed > 0 {
+        let content: String = kept
+            .iter()
+            .map(|(stored_host, key_type, fingerprint)| format!("{} {} {}\n", stored_host, key_type, fingerprint))
+            .collect();
+        std::fs::write(paths::expand(KNOWN_HOSTS_PATH), content)?;
+    }
+    Ok(removed)
+}
+
+fn load() -> Result<Vec<(String, String, String)>> {
+    let path = paths::expand(KNOWN_HOSTS_PATH);
+    let content = match std::fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+
+    Ok(content
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            Some((fields.next()?.to_string(), fields.next()?.to_string(), fields.next()?.to_string()))
+        })
+        .collect())
+}
+
+/// Always bracketed so IPv6 addresses and ports stay unambiguous
+fn host_entry(host: &str, port: u16) -> String {
+    format!("[{}]:{}", host, port)
+}
+
+fn key_type_name(key_type: HostKeyType) -> &'static str {
+    match key_type {
+        HostKeyType::Rsa => "ssh-rsa",
+        HostKeyType::Dss => "ssh-dss",
+        HostKeyType::Ecdsa256 => "ecdsa-sha2-nistp256",
+        HostKeyType::Ecdsa384 => "ecdsa-sha2-nistp384",
+        HostKeyType::Ecdsa521 => "ecdsa-sha2-nistp521",
+        HostKeyType::Ed255219 => "ssh-ed25519",
+        HostKeyType::Unknown => "unknown",
+    }
+}
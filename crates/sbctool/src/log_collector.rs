@@ -1,14 +1,129 @@
 use anyhow::Result;
 use crate::tui::LogEntry;
 use crate::ssh_session::SSHSession;
+use crate::ssh_resolver;
+use crate::system_info::SystemInfoCollector;
+use crate::verbose;
+use crate::adb_session::ADBSession;
+use crate::raw_command;
+use crate::error::SbcError;
 use tokio::time::{sleep, Duration};
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Maps a syslog priority (0-7) to the log levels shown in the TUI
+pub fn level_from_priority(priority: u8) -> &'static str {
+    match priority {
+        0..=3 => "ERROR",
+        4 => "WARN",
+        5 | 6 => "INFO",
+        _ => "DEBUG",
+    }
+}
+
+/// Android's log level letters (logcat's V/D/I/W/E/F/A) as the levels shown in the TUI
+fn level_from_logcat(letter: &str) -> Option<&'static str> {
+    match letter {
+        "F" | "A" | "E" => Some("ERROR"),
+        "W" => Some("WARN"),
+        "I" => Some("INFO"),
+        "D" | "V" => Some("DEBUG"),
+        _ => None,
+    }
+}
+
+/// An ISO 8601 timestamp ("2025-09-05T18:49:25+0000", "2025-09-05T18:49:25,123456+00:00") as the
+/// local `%H:%M:%S` used by the other entries; without a parseable offset the clock part is kept as is
+fn clock_time(iso: &str) -> String {
+    chrono::DateTime::parse_from_rfc3339(&iso.replacen(',', ".", 1))
+        .or_else(|_| chrono::DateTime::parse_from_str(iso, "%Y-%m-%dT%H:%M:%S%z"))
+        .map(|t| t.with_timezone(&chrono::Local).format("%H:%M:%S").to_string())
+        .unwrap_or_else(|_| {
+            let time = iso.split_once('T').map_or(iso, |(_, time)| time);
+            time.chars().take(8).collect()
+        })
+}
+
+/// Parses one line of `journalctl -o json` output, using the real PRIORITY field for the level
+pub fn parse_journald_json_line(line: &str) -> Option<LogEntry> {
+    let value: serde_json::Value = serde_json::from_str(line).ok()?;
+
+    let message = match value.get("MESSAGE")? {
+        serde_json::Value::String(message) => message.clone(),
+        // Messages that aren't valid UTF-8 are exported as byte arrays
+        serde_json::Value::Array(bytes) => {
+            let bytes: Vec<u8> = bytes.iter().filter_map(|b| b.as_u64().map(|b| b as u8)).collect();
+            String::from_utf8_lossy(&bytes).to_string()
+        }
+        _ => return None,
+    };
+
+    // journald defaults to LOG_INFO when PRIORITY is missing
+    let level = value
+        .get("PRIORITY")
+        .and_then(|p| p.as_str())
+        .and_then(|p| p.parse::<u8>().ok())
+        .map(level_from_priority)
+        .unwrap_or("INFO");
+
+    let timestamp = value
+        .get("__REALTIME_TIMESTAMP")
+        .and_then(|t| t.as_str())
+        .and_then(|t| t.parse::<i64>().ok())
+        .and_then(chrono::DateTime::from_timestamp_micros)
+        .map(|t| t.with_timezone(&chrono::Local).format("%H:%M:%S").to_string())
+        .unwrap_or_default();
+
+    let message = match value.get("SYSLOG_IDENTIFIER").and_then(|i| i.as_str()) {
+        Some(identifier) => format!("{}: {}", identifier, message),
+        None => message,
+    };
+
+    Some(LogEntry {
+        timestamp,
+        level: level.to_string(),
+        message,
+    })
+}
+
+/// Linux log source picked with `--log-source`; auto-detected when not given
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogSource {
+    Journald,
+    Syslog,
+    Dmesg,
+}
+
+/// Kernel ring buffer with the raw `<N>` priority prefix, ISO timestamps when supported
+const DMESG_COMMAND: &str = "(dmesg -r --time-format iso 2>/dev/null || dmesg -r)";
+
+/// Text log files tried in order by the syslog source
+const SYSLOG_PATHS: [&str; 3] = ["/var/log/syslog", "/var/log/messages", "/var/log/kern.log"];
+
+/// Entries fetched on the first poll unless `--log-lines` says otherwise
+pub const DEFAULT_LOG_LINES: usize = 20;
+
+/// Entries fetched on later polls, which only need what arrived since the previous one
+const POLL_LINES: usize = 20;
+
+/// How often a wait between polls checks for shutdown
+const SHUTDOWN_CHECK_INTERVAL: Duration = Duration::from_millis(100);
 
 pub struct LogCollector {
     connection_type: String,
     target: String,
     is_android: bool,
     ssh_session: Option<Arc<SSHSession>>,
+    log_source: Option<LogSource>,
+    /// History fetched on the first poll
+    log_lines: usize,
+    /// `--logcat-args`, split on whitespace, e.g. ["-b", "kernel", "*:E"]
+    logcat_args: Vec<String>,
+    /// `--raw-logs`: every line as-is, without the per-source parsers
+    raw_logs: bool,
+    /// Set when the TUI quits; polling stops instead of starting another remote command
+    shutdown: Arc<AtomicBool>,
 }
 
 impl LogCollector {
@@ -18,6 +133,11 @@ impl LogCollector {
             target: target.to_string(),
             is_android,
             ssh_session: None,
+            log_source: None,
+            log_lines: DEFAULT_LOG_LINES,
+            logcat_args: Vec::new(),
+            raw_logs: false,
+            shutdown: Arc::new(AtomicBool::new(false)),
         }
     }
     
@@ -27,7 +147,55 @@ impl LogCollector {
             target: target.to_string(),
             is_android,
             ssh_session: Some(ssh_session),
+            log_source: None,
+            log_lines: DEFAULT_LOG_LINES,
+            logcat_args: Vec::new(),
+            raw_logs: false,
+            shutdown: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Forces a Linux log source instead of auto-detecting journald / syslog
+    pub fn with_log_source(mut self, log_source: Option<LogSource>) -> Self {
+        self.log_source = log_source;
+        self
+    }
+
+    /// Fetches `log_lines` entries of history on the first poll
+    pub fn with_log_lines(mut self, log_lines: usize) -> Self {
+        self.log_lines = log_lines;
+        self
+    }
+
+    /// Extra logcat arguments (buffers, filter specs, `-v` format); `-v time` is used unless they pick a format
+    pub fn with_logcat_args(mut self, logcat_args: Option<&str>) -> Self {
+        self.logcat_args = logcat_args.map(|args| args.split_whitespace().map(str::to_string).collect()).unwrap_or_default();
+        self
+    }
+
+    /// Shows every fetched line unparsed, at INFO level, so nothing a parser doesn't match gets lost
+    pub fn with_raw_logs(mut self, raw_logs: bool) -> Self {
+        self.raw_logs = raw_logs;
+        self
+    }
+
+    /// Stops polling once `shutdown` is set
+    pub fn with_shutdown(mut self, shutdown: Arc<AtomicBool>) -> Self {
+        self.shutdown = shutdown;
+        self
+    }
+
+    /// Waits `interval` before the next poll; false when shutdown was requested meanwhile
+    async fn pause(&self, interval: Duration) -> bool {
+        let mut waited = Duration::ZERO;
+        while waited < interval {
+            if self.shutdown.load(Ordering::Relaxed) {
+                return false;
+            }
+            sleep(SHUTDOWN_CHECK_INTERVAL).await;
+            waited += SHUTDOWN_CHECK_INTERVAL;
         }
+        !self.shutdown.load(Ordering::Relaxed)
     }
 
     pub async fn start_log_collection(&self, log_sender: std::sync::Arc<std::sync::Mutex<Vec<LogEntry>>>) {
@@ -39,13 +207,12 @@ impl LogCollector {
     }
 
     async fn collect_android_logs(&self, log_sender: std::sync::Arc<std::sync::Mutex<Vec<LogEntry>>>) {
+        let mut lines = self.log_lines;
         loop {
-            match self.get_android_logs().await {
+            match self.get_android_logs(lines).await {
                 Ok(logs) => {
                     if let Ok(mut sender) = log_sender.lock() {
-                        for log in logs {
-                            sender.push(log);
-                        }
+                        crate::tui::push_polled_logs(&mut sender, logs);
                     }
                 }
                 Err(e) => {
@@ -55,23 +222,46 @@ impl LogCollector {
                         message: format!("Failed to get Android logs: {}", e),
                     };
                     if let Ok(mut sender) = log_sender.lock() {
-                        sender.push(error_log);
+                        crate::tui::push_log(&mut sender, error_log);
                     }
                 }
             }
-            sleep(Duration::from_secs(2)).await;
+            lines = POLL_LINES;
+            if !self.pause(Duration::from_secs(2)).await {
+                break;
+            }
         }
     }
 
     async fn collect_linux_logs(&self, log_sender: std::sync::Arc<std::sync::Mutex<Vec<LogEntry>>>) {
-        // Try journald first
-        if self.has_journald().await {
-            self.collect_journald_logs(log_sender).await;
-        } else {
-            self.collect_syslog_logs(log_sender).await;
+        match self.log_source {
+            Some(LogSource::Journald) => self.collect_journald_logs(log_sender).await,
+            Some(LogSource::Syslog) => self.collect_syslog_logs(log_sender).await,
+            Some(LogSource::Dmesg) => self.collect_dmesg_logs(log_sender).await,
+            None => match self.detect_init_system().await.as_deref() {
+                Some("systemd") if self.has_journald().await => self.collect_journald_logs(log_sender).await,
+                // OpenRC, SysV and BusyBox boards log to files, or only have the kernel ring buffer
+                Some(_) if self.has_syslog_file().await => self.collect_syslog_logs(log_sender).await,
+                Some(_) => self.collect_dmesg_logs(log_sender).await,
+                None if self.has_journald().await => self.collect_journald_logs(log_sender).await,
+                None => self.collect_syslog_logs(log_sender).await,
+            },
         }
     }
 
+    async fn detect_init_system(&self) -> Option<String> {
+        let collector = match &self.ssh_session {
+            Some(session) => SystemInfoCollector::new_with_ssh_session(&self.connection_type, &self.target, session.clone()),
+            None => SystemInfoCollector::new(&self.connection_type, &self.target),
+        };
+        collector.get_init_system().await
+    }
+
+    async fn has_syslog_file(&self) -> bool {
+        let tests: Vec<String> = SYSLOG_PATHS.iter().map(|path| format!("test -f {}", path)).collect();
+        self.execute_command(&tests.join(" || ")).await.is_ok()
+    }
+
     async fn has_journald(&self) -> bool {
         match self.execute_command("which journalctl").await {
             Ok(_) => true,
@@ -80,13 +270,12 @@ impl LogCollector {
     }
 
     async fn collect_journald_logs(&self, log_sender: std::sync::Arc<std::sync::Mutex<Vec<LogEntry>>>) {
+        let mut lines = self.log_lines;
         loop {
-            match self.get_journald_logs().await {
+            match self.get_journald_logs(lines).await {
                 Ok(logs) => {
                     if let Ok(mut sender) = log_sender.lock() {
-                        for log in logs {
-                            sender.push(log);
-                        }
+                        crate::tui::push_polled_logs(&mut sender, logs);
                     }
                 }
                 Err(e) => {
@@ -96,22 +285,24 @@ impl LogCollector {
                         message: format!("Failed to get journald logs: {}", e),
                     };
                     if let Ok(mut sender) = log_sender.lock() {
-                        sender.push(error_log);
+                        crate::tui::push_log(&mut sender, error_log);
                     }
                 }
             }
-            sleep(Duration::from_secs(3)).await;
+            lines = POLL_LINES;
+            if !self.pause(Duration::from_secs(3)).await {
+                break;
+            }
         }
     }
 
     async fn collect_syslog_logs(&self, log_sender: std::sync::Arc<std::sync::Mutex<Vec<LogEntry>>>) {
+        let mut lines = self.log_lines;
         loop {
-            match self.get_syslog_logs().await {
+            match self.get_syslog_logs(lines).await {
                 Ok(logs) => {
                     if let Ok(mut sender) = log_sender.lock() {
-                        for log in logs {
-                            sender.push(log);
-                        }
+                        crate::tui::push_polled_logs(&mut sender, logs);
                     }
                 }
                 Err(e) => {
@@ -121,58 +312,88 @@ impl LogCollector {
                         message: format!("Failed to get syslog: {}", e),
                     };
                     if let Ok(mut sender) = log_sender.lock() {
-                        sender.push(error_log);
+                        crate::tui::push_log(&mut sender, error_log);
                     }
                 }
             }
-            sleep(Duration::from_secs(5)).await;
+            lines = POLL_LINES;
+            if !self.pause(Duration::from_secs(5)).await {
+                break;
+            }
         }
     }
 
-    async fn get_android_logs(&self) -> Result<Vec<LogEntry>> {
-        let output = self.execute_command("logcat -d -v time").await?;
-        let mut logs = Vec::new();
-
-        for line in output.lines() {
-            if let Some(log_entry) = self.parse_android_log_line(line) {
-                logs.push(log_entry);
+    async fn collect_dmesg_logs(&self, log_sender: std::sync::Arc<std::sync::Mutex<Vec<LogEntry>>>) {
+        let mut lines = self.log_lines;
+        loop {
+            match self.get_dmesg_logs(lines).await {
+                Ok(logs) => {
+                    if let Ok(mut sender) = log_sender.lock() {
+                        crate::tui::push_polled_logs(&mut sender, logs);
+                    }
+                }
+                Err(e) => {
+                    let error_log = LogEntry {
+                        timestamp: chrono::Local::now().format("%H:%M:%S").to_string(),
+                        level: "ERROR".to_string(),
+                        message: format!("Failed to get dmesg: {}", e),
+                    };
+                    if let Ok(mut sender) = log_sender.lock() {
+                        crate::tui::push_log(&mut sender, error_log);
+                    }
+                }
+            }
+            lines = POLL_LINES;
+            if !self.pause(Duration::from_secs(3)).await {
+                break;
             }
         }
+    }
 
-        // Return last 20 entries
+    async fn get_android_logs(&self, lines: usize) -> Result<Vec<LogEntry>> {
+        let mut command = "logcat -d".to_string();
+        if !self.logcat_args.iter().any(|arg| arg == "-v" || arg.starts_with("--format")) {
+            command.push_str(" -v time");
+        }
+        for arg in &self.logcat_args {
+            command.push(' ');
+            command.push_str(&raw_command::quote(arg));
+        }
+        let output = self.execute_command(&command).await?;
+        let mut logs: Vec<LogEntry> = output
+            .lines()
+            .filter_map(|line| self.parse_or_raw(line, Self::parse_android_log_line))
+            .collect();
+
+        // Return the last `lines` entries
         logs.reverse();
-        logs.truncate(20);
+        logs.truncate(lines);
         Ok(logs)
     }
 
-    async fn get_journald_logs(&self) -> Result<Vec<LogEntry>> {
-        let output = self.execute_command("journalctl --no-pager -n 20 -o short-iso").await?;
-        let mut logs = Vec::new();
-
-        for line in output.lines() {
-            if let Some(log_entry) = self.parse_journald_log_line(line) {
-                logs.push(log_entry);
+    async fn get_journald_logs(&self, lines: usize) -> Result<Vec<LogEntry>> {
+        // Raw mode shows the text output below; JSON lines aren't worth reading as-is
+        let json = if self.raw_logs {
+            None
+        } else {
+            self.execute_command(&format!("journalctl --no-pager -n {} -o json", lines)).await.ok()
+        };
+        if let Some(output) = json {
+            let logs: Vec<LogEntry> = output.lines().filter_map(parse_journald_json_line).collect();
+            if !logs.is_empty() {
+                return Ok(logs);
             }
         }
 
-        Ok(logs)
+        // Fallback when JSON output isn't available: guess the level from the message text
+        let output = self.execute_command(&format!("journalctl --no-pager -n {} -o short-iso", lines)).await?;
+        Ok(output.lines().filter_map(|line| self.parse_or_raw(line, Self::parse_journald_log_line)).collect())
     }
 
-    async fn get_syslog_logs(&self) -> Result<Vec<LogEntry>> {
-        let syslog_paths = vec![
-            "/var/log/syslog",
-            "/var/log/messages",
-            "/var/log/kern.log",
-        ];
-
-        for path in syslog_paths {
-            if let Ok(output) = self.execute_command(&format!("tail -n 20 {}", path)).await {
-                let mut logs = Vec::new();
-                for line in output.lines() {
-                    if let Some(log_entry) = self.parse_syslog_line(line) {
-                        logs.push(log_entry);
-                    }
-                }
+    async fn get_syslog_logs(&self, lines: usize) -> Result<Vec<LogEntry>> {
+        for path in SYSLOG_PATHS {
+            if let Ok(output) = self.execute_command(&format!("tail -n {} {}", lines, path)).await {
+                let logs: Vec<LogEntry> = output.lines().filter_map(|line| self.parse_or_raw(line, Self::parse_syslog_line)).collect();
                 if !logs.is_empty() {
                     return Ok(logs);
                 }
@@ -182,20 +403,86 @@ impl LogCollector {
         Err(anyhow::anyhow!("No syslog files found"))
     }
 
+    async fn get_dmesg_logs(&self, lines: usize) -> Result<Vec<LogEntry>> {
+        let output = self.execute_command(&format!("{} | tail -n {}", DMESG_COMMAND, lines)).await?;
+        Ok(output.lines().filter_map(|line| self.parse_or_raw(line, Self::parse_dmesg_line)).collect())
+    }
+
+    /// With `--raw-logs` the line itself as an INFO entry; its own timestamp stays in the message
+    fn parse_or_raw(&self, line: &str, parse: fn(&Self, &str) -> Option<LogEntry>) -> Option<LogEntry> {
+        if !self.raw_logs {
+            return parse(self, line);
+        }
+        let line = line.trim_end();
+        (!line.is_empty()).then(|| LogEntry {
+            timestamp: String::new(),
+            level: "INFO".to_string(),
+            message: line.to_string(),
+        })
+    }
+
+    fn parse_dmesg_line(&self, line: &str) -> Option<LogEntry> {
+        // dmesg -r format: "<6>[   12.345678] message" or "<6>2025-09-05T18:49:25,123456+00:00 message"
+        let rest = line.strip_prefix('<')?;
+        let (prefix, rest) = rest.split_once('>')?;
+        // The prefix is facility * 8 + priority, as in /dev/kmsg
+        let priority = (prefix.parse::<u32>().ok()? & 7) as u8;
+
+        let rest = rest.trim_start();
+        let (timestamp, message) = if let Some(bracketed) = rest.strip_prefix('[') {
+            // Without --time-format (BusyBox) there is only the uptime, no wall clock to convert
+            let (uptime, message) = bracketed.split_once(']')?;
+            (uptime.trim().to_string(), message.trim())
+        } else {
+            let (iso, message) = rest.split_once(' ')?;
+            (clock_time(iso), message.trim())
+        };
+
+        Some(LogEntry {
+            timestamp,
+            level: level_from_priority(priority).to_string(),
+            message: format!("kernel: {}", message),
+        })
+    }
+
+    /// Parses the `time`, `threadtime` and `brief` formats; lines in other formats are kept
+    /// whole as INFO entries rather than dropped, only the buffer separators are skipped
     fn parse_android_log_line(&self, line: &str) -> Option<LogEntry> {
-        // Android logcat format: MM-DD HH:MM:SS.fff PID TID LEVEL TAG: MESSAGE
-        let parts: Vec<&str> = line.split_whitespace().collect();
-        if parts.len() < 6 {
+        let line = line.trim_end();
+        if line.is_empty() || line.starts_with("--------- beginning of") {
             return None;
         }
+        let parts: Vec<&str> = line.split_whitespace().collect();
 
-        let timestamp = format!("{} {}", parts[0], parts[1]);
-        let level = parts[4].to_uppercase();
-        let message = parts[5..].join(" ");
+        // threadtime: "09-05 18:49:25.123  1234  1250 E ActivityManager: message"
+        let threadtime = parts.len() >= 6 && parts[2].parse::<u32>().is_ok() && parts[3].parse::<u32>().is_ok();
+        if let Some(level) = parts.get(4).filter(|_| threadtime).and_then(|letter| level_from_logcat(letter)) {
+            let (_, message) = line.split_once(&format!(" {} ", parts[4]))?;
+            return Some(LogEntry {
+                timestamp: format!("{} {}", parts[0], parts[1]),
+                level: level.to_string(),
+                message: message.trim().to_string(),
+            });
+        }
 
+        // time: "09-05 18:49:25.123 E/ActivityManager( 1234): message"; brief is the same without the timestamp
+        let (timestamp, rest) = match parts.as_slice() {
+            [date, time, ..] if date.len() == 5 && date.as_bytes()[2] == b'-' => {
+                (format!("{} {}", date, time), line.splitn(3, ' ').nth(2).unwrap_or("").trim_start())
+            }
+            _ => (String::new(), line),
+        };
+        let parsed = rest.split_once('/').and_then(|(letter, rest)| {
+            let level = level_from_logcat(letter)?;
+            let (tag, message) = rest.split_once("):").or_else(|| rest.split_once(':'))?;
+            let tag = tag.split('(').next().unwrap_or(tag).trim();
+            Some((level, format!("{}: {}", tag, message.trim())))
+        });
+
+        let (level, message) = parsed.unwrap_or(("INFO", line.to_string()));
         Some(LogEntry {
             timestamp,
-            level,
+            level: level.to_string(),
             message,
         })
     }
@@ -221,7 +508,7 @@ impl LogCollector {
                 };
 
                 return Some(LogEntry {
-                    timestamp: timestamp_part.to_string(),
+                    timestamp: clock_time(timestamp_part),
                     level: level.to_string(),
                     message: message.to_string(),
                 });
@@ -259,47 +546,32 @@ impl LogCollector {
     }
 
     async fn execute_command(&self, command: &str) -> Result<String> {
-        match self.connection_type.as_str() {
-            "ssh" => self.execute_ssh_command(command).await,
+        verbose::command(&self.connection_type, &self.target, command);
+
+        let result = match self.connection_type.as_str() {
+            "ssh" => {
+                if let Some(ssh_session) = &self.ssh_session {
+                    // Use persistent SSH session
+                    ssh_session.execute_command(command).await
+                } else {
+                    self.execute_ssh_command(command).await
+                }
+            },
             "adb" => self.execute_adb_command(command).await,
-            _ => Err(anyhow::anyhow!("Unknown connection type: {}", self.connection_type)),
+            _ => Err(SbcError::UnknownConnectionType(self.connection_type.clone()).into()),
+        };
+
+        if let Ok(output) = &result {
+            verbose::output(&self.connection_type, &self.target, output);
         }
+        result
     }
 
     async fn execute_ssh_command(&self, command: &str) -> Result<String> {
         use std::process::Command;
         
-        // Parse target to get user@host
-        let (user, host) = if let Some((u, h)) = self.target.split_once('@') {
-            (u.to_string(), h.to_string())
-        } else {
-            // Try to resolve alias using ssh -G
-            if let Ok(output) = Command::new("ssh").arg("-G").arg(&self.target).output() {
-                if output.status.success() {
-                    let stdout = String::from_utf8_lossy(&output.stdout);
-                    let mut resolved_host = None;
-                    let mut resolved_user = None;
-                    
-                    for line in stdout.lines() {
-                        if let Some(rest) = line.strip_prefix("hostname ") {
-                            resolved_host = Some(rest.trim().to_string());
-                        } else if let Some(rest) = line.strip_prefix("user ") {
-                            resolved_user = Some(rest.trim().to_string());
-                        }
-                    }
-                    
-                    let host = resolved_host.unwrap_or(self.target.clone());
-                    let user = resolved_user.unwrap_or_else(|| {
-                        std::env::var("USER").unwrap_or_else(|_| "root".to_string())
-                    });
-                    (user, host)
-                } else {
-                    return Err(anyhow::anyhow!("Failed to resolve SSH alias: {}", self.target));
-                }
-            } else {
-                return Err(anyhow::anyhow!("SSH command not available"));
-            }
-        };
+        // Resolve user, host and port once per target (cached across collectors)
+        let resolved = ssh_resolver::resolve(&self.target)?;
 
         // Execute command via SSH with timeout and terminal reset
         let output = Command::new("ssh")
@@ -317,57 +589,26 @@ impl LogCollector {
             .arg("StrictHostKeyChecking=no")
             .arg("-o")
             .arg("UserKnownHostsFile=/dev/null")
-            .arg(&format!("{}@{}", user, host))
+            .args(ssh_resolver::external_args())
+            .arg("-p")
+            .arg(resolved.port.to_string())
+            .arg(&format!("{}@{}", resolved.user, resolved.host))
             .arg(&format!("timeout 30 bash -c '{}'", command))
             .output()?;
 
         if output.status.success() {
             Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
         } else {
-            Err(anyhow::anyhow!("SSH command failed: {}", String::from_utf8_lossy(&output.stderr)))
-        }
-    }
-
-    async fn execute_adb_command(&self, command: &str) -> Result<String> {
-        use std::process::Command;
-        
-        if self.target == "auto" {
-            // Try to find a device automatically
-            if let Ok(output) = Command::new("adb").arg("devices").output() {
-                let stdout = String::from_utf8_lossy(&output.stdout);
-                let lines: Vec<&str> = stdout.lines().collect();
-                
-                // Find first device that's not "List of devices attached"
-                for line in lines {
-                    if line.contains("\tdevice") {
-                        if let Some(serial) = line.split('\t').next() {
-                            if !serial.is_empty() {
-                                return self.execute_adb_command_with_serial(serial, command).await;
-                            }
-                        }
-                    }
-                }
+            Err(SbcError::CommandFailed {
+                exit_status: output.status.code().unwrap_or(-1),
+                output: String::from_utf8_lossy(&output.stderr).into_owned(),
             }
-            Err(anyhow::anyhow!("No ADB devices found"))
-        } else {
-            self.execute_adb_command_with_serial(&self.target, command).await
+            .into())
         }
     }
 
-    async fn execute_adb_command_with_serial(&self, serial: &str, command: &str) -> Result<String> {
-        use std::process::Command;
-        
-        let output = Command::new("adb")
-            .arg("-s")
-            .arg(serial)
-            .arg("shell")
-            .arg(command)
-            .output()?;
-
-        if output.status.success() {
-            Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
-        } else {
-            Err(anyhow::anyhow!("ADB command failed: {}", String::from_utf8_lossy(&output.stderr)))
-        }
+    async fn execute_adb_command(&self, command: &str) -> Result<String> {
+        // Talk to adbd natively through adb_client, no external adb binary needed
+        ADBSession::new(&self.target).execute_command(command).await
     }
 }
@@ -0,0 +1,69 @@
+use anyhow::Result;
+use crate::tui::LogEntry;
+use std::fs::OpenOptions;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::sync::mpsc::{self, Sender};
+use std::sync::Mutex;
+use std::thread::JoinHandle;
+
+/// Channel to the writer thread and the thread itself, set once when `--log-file` is given
+static EXPORTER: Mutex<Option<Exporter>> = Mutex::new(None);
+
+struct Exporter {
+    sender: Sender<LogEntry>,
+    writer: JoinHandle<()>,
+}
+
+/// Starts a background thread appending every recorded entry to `path`.
+/// Writing happens off the caller's thread so the TUI render loop never waits on disk.
+pub fn init(path: &Path) -> Result<()> {
+    let mut exporter = EXPORTER.lock().map_err(|_| anyhow::anyhow!("Log file export lock poisoned"))?;
+    if exporter.is_some() {
+        return Err(anyhow::anyhow!("Log file export is already initialized"));
+    }
+
+    let file = OpenOptions::new().create(true).append(true).open(path)?;
+    let (sender, receiver) = mpsc::channel::<LogEntry>();
+
+    let writer = std::thread::spawn(move || {
+        let mut writer = BufWriter::new(file);
+        while let Ok(entry) = receiver.recv() {
+            let _ = write_entry(&mut writer, &entry);
+            // Drain whatever else is queued before flushing
+            while let Ok(entry) = receiver.try_recv() {
+                let _ = write_entry(&mut writer, &entry);
+            }
+            let _ = writer.flush();
+        }
+    });
+
+    *exporter = Some(Exporter { sender, writer });
+    Ok(())
+}
+
+/// Queues an entry for the log file, a no-op unless `init` was called
+pub fn record(entry: &LogEntry) {
+    let Ok(exporter) = EXPORTER.lock() else {
+        return;
+    };
+    if let Some(exporter) = exporter.as_ref() {
+        let _ = exporter.sender.send(entry.clone());
+    }
+}
+
+/// Closes the channel and waits until the writer thread has written out every queued entry;
+/// later `record` calls are no-ops. Call before exiting, since exiting doesn't wait for the thread.
+pub fn finish() {
+    let exporter = EXPORTER.lock().ok().and_then(|mut exporter| exporter.take());
+    if let Some(Exporter { sender, writer }) = exporter {
+        drop(sender);
+        let _ = writer.join();
+    }
+}
+
+fn write_entry<W: Write>(writer: &mut W, entry: &LogEntry) -> std::io::Result<()> {
+    // Keep one entry per line with exactly three tab-separated columns
+    let message = entry.message.replace(['\t', '\n', '\r'], " ");
+    writeln!(writer, "{}\t{}\t{}", entry.timestamp, entry.level, message)
+}
@@ -1,13 +1,45 @@
 use anyhow::Result;
-use clap::Parser;
+use clap::{CommandFactory, FromArgMatches, Parser};
 
 mod tui;
 mod system_info;
 mod log_collector;
 mod ssh_session;
+mod ssh_resolver;
+mod adb_session;
+mod log_export;
+mod verbose;
+mod soc_db;
+mod extra_collectors;
+mod color;
+mod error;
+mod timing;
+mod scan;
+mod known_hosts;
+mod power;
+mod paths;
+mod raw_command;
+mod snapshot;
+mod profiles;
+mod interactive;
+mod userland;
+mod compare;
+mod metrics;
+mod serve;
+mod ping;
+mod proxy;
 
 use tui::{TuiApp, setup_terminal, restore_terminal};
 use system_info::SystemInfoCollector;
+use log_collector::LogSource;
+use ssh_session::{AuthMethod, SSHSession, SshOptions};
+use error::SbcError;
+use std::io::{IsTerminal, Write};
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+
+/// How long quitting waits for background tasks (log streams closing, blocked remote commands)
+const SHUTDOWN_GRACE: std::time::Duration = std::time::Duration::from_secs(2);
 
 #[derive(Parser)]
 #[command(name = "sbctool")]
@@ -16,19 +48,133 @@ use system_info::SystemInfoCollector;
 #[command(about = "A CLI tool to collect information from various Single Board Computers (SBCs).", long_about = None)]
 struct Cli {
 	#[command(subcommand)]
-	command: Commands,
+	command: Option<Commands>,
+	/// Named board from ~/.config/sbctool/profiles.toml; without a command, opens its target
+	#[arg(long, global = true, value_name = "NAME")]
+	profile: Option<String>,
+	/// Forget the stored SSH host key of a target (user@host or alias), e.g. after reflashing the board
+	#[arg(long, value_name = "TARGET")]
+	reset_hostkey: Option<String>,
+	/// Read the SSH password from the first line of stdin, or prompt for it without echo on a terminal (otherwise $SBCTOOL_PASSWORD is used)
+	#[arg(long, global = true)]
+	password_stdin: bool,
+	/// SSH authentication methods to try, in order (default: agent,key,password)
+	#[arg(long, global = true, value_enum, value_delimiter = ',', value_name = "METHODS")]
+	auth: Vec<AuthMethod>,
+	/// Private key for the `key` auth method, instead of the ssh config / default keys
+	#[arg(short, long, global = true, value_name = "PATH", value_parser = paths::parse_arg)]
+	identity: Option<std::path::PathBuf>,
+	/// SSH port, overriding ssh config and `ssh -G`
+	#[arg(short, long, global = true)]
+	port: Option<u16>,
+	/// SSH connect timeout in seconds (also the per-command read timeout unless --command-timeout is given)
+	#[arg(long, global = true, default_value = "10", value_name = "SECONDS", value_parser = clap::value_parser!(u64).range(1..))]
+	connect_timeout: u64,
+	/// How long a remote command may go without output before it fails, in seconds
+	#[arg(long, global = true, value_name = "SECONDS", value_parser = clap::value_parser!(u64).range(1..))]
+	command_timeout: Option<u64>,
+	/// Send an SSH keepalive every N idle seconds so NAT doesn't drop long sessions; a missed one triggers a reconnect (0 = off)
+	#[arg(long, global = true, default_value_t = ssh_session::DEFAULT_KEEPALIVE_SECS, value_name = "SECONDS")]
+	keepalive: u64,
+	/// Extra SSH connect + handshake attempts when the board doesn't answer
+	#[arg(long, global = true, default_value_t = ssh_session::DEFAULT_CONNECT_RETRIES, value_name = "N")]
+	connect_retries: u32,
+	/// Seconds before the first connect retry, doubled after each one (at most 5)
+	#[arg(long, global = true, default_value = "1", value_name = "SECONDS")]
+	connect_backoff: f64,
+	/// Keep retrying the SSH connect for up to this many seconds, e.g. while a freshly flashed board boots
+	#[arg(long, global = true, value_name = "SECONDS")]
+	wait: Option<u64>,
+	/// Append every log entry to this file (timestamp, level and message, tab-separated)
+	#[arg(long, global = true, value_name = "PATH", value_parser = paths::parse_arg)]
+	log_file: Option<std::path::PathBuf>,
+	/// Append every collected system info snapshot to this JSONL file, for `sbctool diff`
+	#[arg(long, global = true, value_name = "PATH", value_parser = paths::parse_arg)]
+	snapshot: Option<std::path::PathBuf>,
+	/// Log entries of history to fetch when the TUI starts (later polls fetch only recent ones)
+	#[arg(short = 'n', long, global = true, default_value_t = log_collector::DEFAULT_LOG_LINES, value_name = "N")]
+	log_lines: usize,
+	/// Show log lines unparsed in the TUI, at INFO level, instead of through the journald / syslog / dmesg / logcat parsers
+	#[arg(long, global = true)]
+	raw_logs: bool,
+	/// Also collect exported / claimed GPIO line states (sysfs and gpioinfo)
+	#[arg(long, global = true)]
+	gpio: bool,
+	/// Also count upgradable packages (apt, dnf, yum, pacman, apk, opkg) from the cached package lists; can take a few seconds
+	#[arg(long, global = true)]
+	updates: bool,
+	/// Re-collect system info in the TUI every N seconds (0 = only when pressing 'r')
+	#[arg(long, global = true, default_value = "0", value_name = "SECONDS")]
+	refresh: u64,
+	/// Disable colors in non-TUI output (also honors the NO_COLOR environment variable)
+	#[arg(long, global = true)]
+	no_color: bool,
+	/// adb server to use as host[:port] instead of 127.0.0.1:5037 (e.g. one on another machine)
+	#[arg(long, global = true, value_name = "HOST:PORT")]
+	adb_server: Option<String>,
+	/// Reach SSH targets through a proxy: socks5://[user:pass@]host:port or http://[user:pass@]host:port (HTTP CONNECT)
+	#[arg(long, global = true, value_name = "URL")]
+	proxy: Option<String>,
+	/// Reuse this ssh ControlMaster socket (e.g. ~/.ssh/cm-%r@%h:%p) for commands run through the ssh binary
+	#[arg(long, global = true, value_name = "PATH")]
+	control_path: Option<String>,
+	/// Print how long each remote command and connection phase took to stderr when done
+	#[arg(long, global = true)]
+	time: bool,
+	/// Print the equivalent plain ssh / scp / adb command for the target instead of running anything
+	#[arg(long, global = true)]
+	print_command: bool,
+	/// Only print results to stdout, no connection / progress messages (implied by `info --format json|yaml|toml`)
+	#[arg(short, long, global = true)]
+	quiet: bool,
+	/// Temperature in °C at which the TUI health line turns yellow
+	#[arg(long, global = true, value_name = "CELSIUS")]
+	temp_warn: Option<f64>,
+	/// Temperature in °C at which the TUI health line turns red
+	#[arg(long, global = true, value_name = "CELSIUS")]
+	temp_crit: Option<f64>,
+	/// Thermal zone shown as the temperature, by number or type (e.g. 1 or gpu-thermal); without it the TUI lists every zone
+	#[arg(long, global = true, value_name = "NAME|INDEX")]
+	thermal_zone: Option<String>,
+	/// Memory use in percent at which the TUI health line turns yellow
+	#[arg(long, global = true, value_name = "PERCENT")]
+	mem_warn: Option<f64>,
+	/// Memory use in percent at which the TUI health line turns red
+	#[arg(long, global = true, value_name = "PERCENT")]
+	mem_crit: Option<f64>,
+	/// Print every remote command to stderr (-vv also prints the raw output)
+	#[arg(short, long, global = true, action = clap::ArgAction::Count)]
+	verbose: u8,
 }
 
 #[derive(Parser)]
 enum Commands {
 	/// Connect to an SBC using SSH
 	Ssh {
-		/// The user@host or ssh_config alias to connect to (e.g., root@192.168.1.4, my-sbc)
-		#[arg(value_name = "TARGET")]
-		target: String,
+		/// One or more user@host or ssh_config aliases to connect to (e.g., root@192.168.1.4, my-sbc)
+		#[arg(value_name = "TARGET", required = true, num_args = 1..)]
+		targets: Vec<String>,
 		/// Timeout in seconds for TUI session (0 = no timeout)
 		#[arg(long, default_value = "0")]
 		timeout: u64,
+		/// Print a one-line summary every N seconds instead of launching the TUI
+		#[arg(long, value_name = "SECONDS", value_parser = clap::value_parser!(u64).range(1..))]
+		watch: Option<u64>,
+		/// Log source for the logs panel (default: journald if available, else syslog files)
+		#[arg(long, value_enum)]
+		log_source: Option<LogSource>,
+		/// Run this local shell script on the target (piped to `sh -s`) and print its output instead of the TUI
+		#[arg(long, value_name = "PATH", value_parser = paths::parse_arg)]
+		script: Option<std::path::PathBuf>,
+		/// Argument passed to the --script script as $1, $2, ... (repeatable)
+		#[arg(long = "script-arg", value_name = "ARG", requires = "script", allow_hyphen_values = true)]
+		script_args: Vec<String>,
+		/// Environment variable for the --script run, as KEY=VALUE (repeatable)
+		#[arg(long = "env", value_name = "KEY=VALUE", requires = "script", value_parser = parse_env_var)]
+		env: Vec<(String, String)>,
+		/// Remote working directory for the --script run
+		#[arg(long, value_name = "DIR", requires = "script")]
+		cwd: Option<String>,
 	},
 	/// Connect to an SBC using ADB
 	Adb {
@@ -38,64 +184,779 @@ enum Commands {
 		/// Timeout in seconds for TUI session (0 = no timeout)
 		#[arg(long, default_value = "0")]
 		timeout: u64,
+		/// Print a one-line summary every N seconds instead of launching the TUI
+		#[arg(long, value_name = "SECONDS", value_parser = clap::value_parser!(u64).range(1..))]
+		watch: Option<u64>,
+		/// Extra logcat arguments for the logs panel, e.g. "-b kernel", "*:E" or "-v threadtime"
+		#[arg(long, value_name = "ARGS", allow_hyphen_values = true)]
+		logcat_args: Option<String>,
 		/// Extra args, e.g. allowing `sbctool adb help`
 		#[arg(value_name = "ARGS", trailing_var_arg = true)]
 		extra: Vec<String>,
 	},
+	/// Copy files to or from an SBC over SSH (SCP)
+	Scp {
+		/// The user@host or ssh_config alias to connect to (e.g., root@192.168.1.4, my-sbc)
+		#[arg(value_name = "TARGET")]
+		target: String,
+		/// Source path; prefix with ':' for a remote path (e.g., ./app.bin or :/var/log/syslog)
+		#[arg(value_name = "SOURCE")]
+		source: Option<String>,
+		/// Destination path; prefix with ':' for a remote path (e.g., :/tmp/app.bin or ./syslog)
+		#[arg(value_name = "DEST")]
+		dest: Option<String>,
+	},
+	/// Open an interactive shell on an SBC, reusing sbctool's target resolution and authentication
+	Shell {
+		/// The user@host or ssh_config alias, or with --adb the device serial ("auto" to detect)
+		#[arg(value_name = "TARGET")]
+		target: String,
+		/// Treat TARGET as an ADB serial instead of an SSH target
+		#[arg(long)]
+		adb: bool,
+	},
+	/// List ADB devices and sweep the LAN for hosts with SSH or ADB ports open
+	Scan {
+		/// Subnet to sweep, e.g. 192.168.1.0/24 (default: the local /24)
+		#[arg(long, value_name = "CIDR")]
+		subnet: Option<String>,
+		/// Ports to probe
+		#[arg(long, value_delimiter = ',', default_value = "22,5555")]
+		ports: Vec<u16>,
+		/// Per-connection timeout in milliseconds
+		#[arg(long, default_value = "300", value_name = "MS")]
+		probe_timeout: u64,
+	},
+	/// Connect to an SBC, picking SSH or ADB from the target syntax
+	Connect {
+		/// user@host or ssh_config alias (SSH), ip:port or adb serial (ADB)
+		#[arg(value_name = "TARGET")]
+		target: String,
+		/// Timeout in seconds for TUI session (0 = no timeout)
+		#[arg(long, default_value = "0")]
+		timeout: u64,
+	},
+	/// Check that an SBC is reachable and accepts the login: connect, authenticate and run `true`, nothing else
+	Ping {
+		/// The user@host or ssh_config alias
+		#[arg(value_name = "TARGET")]
+		target: String,
+		/// Number of attempts, one second apart
+		#[arg(short, long, default_value = "1", value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+		count: u32,
+	},
+	/// Collect system info once, print a report and exit (no TUI)
+	Info {
+		/// The user@host or ssh_config alias, or with --adb the device serial ("auto" to detect)
+		#[arg(value_name = "TARGET")]
+		target: String,
+		/// Treat TARGET as an ADB serial instead of an SSH target
+		#[arg(long)]
+		adb: bool,
+		/// Output format
+		#[arg(long, value_enum, default_value = "table")]
+		format: InfoFormat,
+		/// Only output these fields, by their snake_case names (e.g. hostname,memory,temperature); needs --format json, pretty-json, yaml or toml
+		#[arg(long, value_delimiter = ',', value_name = "FIELDS")]
+		fields: Vec<String>,
+	},
+	/// Collect system info periodically and serve it over HTTP: /info and /logs as JSON, /metrics for Prometheus
+	Serve {
+		/// The user@host or ssh_config alias, or with --adb the device serial ("auto" to detect)
+		#[arg(value_name = "TARGET")]
+		target: String,
+		/// Treat TARGET as an ADB serial instead of an SSH target
+		#[arg(long)]
+		adb: bool,
+		/// Address and port to listen on, e.g. 0.0.0.0:8080 for other machines
+		#[arg(long, default_value = "127.0.0.1:8080", value_name = "ADDR:PORT")]
+		listen: String,
+		/// Seconds between collections
+		#[arg(long, default_value = "10", value_name = "SECONDS", value_parser = clap::value_parser!(u64).range(1..))]
+		interval: u64,
+	},
+	/// Reboot an SBC and wait until it goes down
+	Reboot {
+		/// The user@host or ssh_config alias, or with --adb the device serial ("auto" to detect)
+		#[arg(value_name = "TARGET")]
+		target: String,
+		/// Treat TARGET as an ADB serial instead of an SSH target
+		#[arg(long)]
+		adb: bool,
+		/// Don't ask for confirmation
+		#[arg(short, long)]
+		yes: bool,
+	},
+	/// Power off an SBC and wait until it goes down
+	Poweroff {
+		/// The user@host or ssh_config alias, or with --adb the device serial ("auto" to detect)
+		#[arg(value_name = "TARGET")]
+		target: String,
+		/// Treat TARGET as an ADB serial instead of an SSH target
+		#[arg(long)]
+		adb: bool,
+		/// Don't ask for confirmation
+		#[arg(short, long)]
+		yes: bool,
+	},
+	/// Collect system info from the same board over SSH and ADB and print both side by side
+	Compare {
+		/// The user@host or ssh_config alias
+		#[arg(value_name = "SSH_TARGET")]
+		ssh_target: String,
+		/// The device serial or ip:port ("auto" to detect)
+		#[arg(value_name = "ADB_SERIAL")]
+		adb_serial: String,
+		/// Only print the fields that differ
+		#[arg(long)]
+		only_mismatches: bool,
+		/// Also compare fields that change on every collection (CPU frequencies, top processes)
+		#[arg(long)]
+		all: bool,
+	},
+	/// Show what changed between the last two snapshots in a --snapshot file
+	Diff {
+		/// JSONL file written with --snapshot
+		#[arg(value_name = "FILE", value_parser = paths::parse_arg)]
+		file: std::path::PathBuf,
+		/// Compare this target's snapshots instead of the newest snapshot's target
+		#[arg(long)]
+		target: Option<String>,
+		/// Also compare fields that change on every collection (CPU frequencies, top processes)
+		#[arg(long)]
+		all: bool,
+	},
+	/// Print a shell completion script to stdout, e.g. `sbctool completions bash > /etc/bash_completion.d/sbctool`
+	#[command(hide = true)]
+	Completions {
+		#[arg(value_enum)]
+		shell: clap_complete::Shell,
+	},
 }
 
-#[tokio::main]
-async fn main() -> Result<()> {
-	let cli = Cli::parse();
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum InfoFormat {
+	Json,
+	PrettyJson,
+	Yaml,
+	Toml,
+	/// Numeric fields as Prometheus text-exposition gauges, e.g. for node_exporter's textfile collector
+	Prometheus,
+	Table,
+}
 
-	match &cli.command {
-		Commands::Ssh { target, timeout } => {
+fn main() {
+	let runtime = tokio::runtime::Runtime::new().expect("failed to start the tokio runtime");
+	let result = runtime.block_on(run());
+	// A task still blocked in a remote command (ssh2 read, ssh or adb call) would otherwise hold
+	// up the exit until that command times out
+	runtime.shutdown_timeout(SHUTDOWN_GRACE);
+	// The --log-file writer thread may still have entries queued
+	log_export::finish();
+
+	if let Err(e) = result {
+		// Same report as returning the error from main, but with an exit status scripts can tell apart
+		eprintln!("Error: {:?}", e);
+		let exit_code = e.chain().find_map(|cause| cause.downcast_ref::<SbcError>()).map_or(1, SbcError::exit_code);
+		std::process::exit(exit_code);
+	}
+}
+
+async fn run() -> Result<()> {
+	let matches = Cli::command().get_matches();
+	let mut cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+	if let Some(name) = cli.profile.clone() {
+		apply_profile(&mut cli, &matches, profiles::load(&name)?)?;
+	}
+	// Only --reset-hostkey works without a subcommand
+	if cli.command.is_none() && cli.reset_hostkey.is_none() {
+		Cli::command().error(clap::error::ErrorKind::MissingSubcommand, "a subcommand is required").exit();
+	}
+
+	verbose::set_level(cli.verbose);
+	tui::set_log_capacity(cli.log_lines);
+	let defaults = tui::HealthThresholds::default();
+	tui::set_health_thresholds(tui::HealthThresholds {
+		temp_warn: cli.temp_warn.unwrap_or(defaults.temp_warn),
+		temp_crit: cli.temp_crit.unwrap_or(defaults.temp_crit),
+		memory_warn: cli.mem_warn.unwrap_or(defaults.memory_warn),
+		memory_crit: cli.mem_crit.unwrap_or(defaults.memory_crit),
+	});
+	if let Some(zone) = &cli.thermal_zone {
+		tui::set_thermal_zone(zone);
+	}
+	// Structured info output and ping results are meant for pipes, so keep status lines out of them
+	let machine_readable = matches!(&cli.command, Some(Commands::Info { format, .. }) if !matches!(format, InfoFormat::Table))
+		|| matches!(&cli.command, Some(Commands::Ping { .. }));
+	verbose::set_quiet(cli.quiet || machine_readable);
+	color::init(cli.no_color);
+	timing::set_enabled(cli.time);
+	if let Some(path) = &cli.control_path {
+		ssh_resolver::set_control_path(path);
+	}
+	if let Some(address) = &cli.adb_server {
+		adb_session::set_server_address(address)?;
+	}
+
+	// Load collectors.toml now so config problems print before the TUI takes over
+	extra_collectors::configured();
+
+	if let Some(path) = &cli.log_file {
+		log_export::init(path)?;
+	}
+	if let Some(path) = &cli.snapshot {
+		snapshot::init(path)?;
+	}
+
+	if let Some(target) = &cli.reset_hostkey {
+		reset_hostkey(target, cli.port)?;
+	}
+	let Some(command) = &cli.command else {
+		return Ok(());
+	};
+	if cli.print_command {
+		return print_raw_command(&cli, command);
+	}
+
+	match command {
+		Commands::Ssh { targets, timeout, watch, log_source, script, script_args, env, cwd } => {
 			// Support `sbctool ssh help` style help
-			if target == "help" || target == "--help" || target == "-h" {
-				println!("Usage: sbctool ssh <user@host|alias> [--timeout SECONDS]\n\nExamples:\n  sbctool ssh user@192.168.1.4\n  sbctool ssh khadas\n  sbctool ssh khadas --timeout=10\n\nNotes:\n  - Aliases are resolved using 'ssh -G' when available; falls back to ~/.ssh/config and /etc/ssh/ssh_config.\n  - If user is omitted, tries ssh config, then $USER/LOGNAME.\n  - Launches TUI interface for real-time monitoring.\n  - Use --timeout=0 for no timeout (default).\n");
+			if targets.iter().any(|t| t == "help" || t == "--help" || t == "-h") {
+				println!("Usage: sbctool ssh <user@host|alias>... [--timeout SECONDS]\n\nExamples:\n  sbctool ssh user@192.168.1.4\n  sbctool ssh khadas\n  sbctool ssh khadas --timeout=10\n  sbctool ssh node1 node2 node3\n\nNotes:\n  - Aliases are resolved using 'ssh -G' when available; falls back to ~/.ssh/config and /etc/ssh/ssh_config.\n  - If user is omitted, tries ssh config, then $USER/LOGNAME.\n  - Launches TUI interface for real-time monitoring; several targets get one tab each (Tab/1-9 to switch).\n  - Use --timeout=0 for no timeout (default).\n  - --refresh SECONDS re-collects system info at that interval (default 0: only when pressing 'r').\n  - --watch SECONDS prints a one-line summary at that interval instead of the TUI (Ctrl-C to stop).\n  - --log-source journald|syslog|dmesg picks the logs panel source (default: auto-detect).\n  - --script PATH runs a local script on the target via 'sh -s' and prints its output; pass arguments with --script-arg ARG, variables with --env KEY=VALUE and the remote directory with --cwd DIR.\n  - -n/--log-lines N fetches N entries of log history at startup (default 20).\n  - -p/--port PORT overrides the port from ssh config.\n  - --control-path PATH reuses a running ssh ControlMaster for commands run through the ssh binary.\n  - --connect-timeout SECONDS bounds the SSH connect and each remote command (default 10); --command-timeout SECONDS sets the latter separately.\n  - --keepalive SECONDS sends SSH keepalives on idle sessions (default 30, 0 = off); an unanswered one reconnects.\n  - A failed connect or handshake is retried --connect-retries times (default 2), waiting --connect-backoff SECONDS (default 1, doubling); --wait SECONDS keeps retrying until the board answers or the time is up.\n  - Auth order: ssh-agent, keys, then password from --password-stdin or $SBCTOOL_PASSWORD; change it with --auth agent,key,password.\n  - -i/--identity PATH picks the key for the 'key' method and tries it first; its passphrase is prompted for or read from $SBCTOOL_KEY_PASSPHRASE.\n");
+				return Ok(())
+			}
+			
+			let mut ssh_options = ssh_options_from_cli(&cli)?;
+			if let Some(path) = script {
+				let [target] = targets.as_slice() else {
+					return Err(anyhow::anyhow!("--script takes a single target"));
+				};
+				ssh_options.env = env.clone();
+				ssh_options.cwd = cwd.clone();
+				run_script(target, path, script_args, &ssh_options).await?;
+				timing::report();
+				return Ok(())
+			}
+			if let Some(interval) = watch {
+				let [target] = targets.as_slice() else {
+					return Err(anyhow::anyhow!("--watch takes a single target"));
+				};
+
+				// Reuse one native session for every iteration
+				let session = SSHSession::new_with_options(target, &ssh_options).await?;
+				let collector = SystemInfoCollector::new_with_ssh_session("ssh", target, Arc::new(session)).with_gpio(cli.gpio).with_updates(cli.updates);
+				run_watch(&collector, *interval).await?;
+				timing::report();
 				return Ok(())
 			}
 			
 			// Launch TUI for SSH connection
-			launch_ssh_tui(target, *timeout).await?;
+			launch_ssh_tui(targets, *timeout, &ssh_options, *log_source, collect_options_from_cli(&cli)).await?;
 		}
-		Commands::Adb { serial, timeout, extra } => {
+		Commands::Adb { serial, timeout, watch, logcat_args, extra } => {
 			// handle `sbctool adb help`
 			if extra.iter().any(|a| a == "help" || a == "--help" || a == "-h") {
-				println!("Usage: sbctool adb [-s SERIAL] [--timeout SECONDS]\n\nExamples:\n  sbctool adb\n  sbctool adb -s <usb-serial>\n  sbctool adb -s <ip>\n  sbctool adb -s <ip:port>\n  sbctool adb --timeout=10\n\nBehavior:\n  - No -s: if exactly one USB device -> use USB; else list devices (server).\n  - -s ip:port: connect TCP direct to adbd.\n  - -s ip: default port 5555.\n  - -s usb-serial: use adb server to talk to that device.\n  - Launches TUI interface for real-time monitoring.\n  - Use --timeout=0 for no timeout (default).");
+				println!("Usage: sbctool adb [-s SERIAL] [--timeout SECONDS]\n\nExamples:\n  sbctool adb\n  sbctool adb -s <usb-serial>\n  sbctool adb -s <ip>\n  sbctool adb -s <ip:port>\n  sbctool adb -s [fe80::1]:5555\n  sbctool adb --timeout=10\n\nBehavior:\n  - No -s: if exactly one USB device -> use USB; else list devices (server).\n  - -s ip:port: connect TCP direct to adbd.\n  - -s ip: default port 5555.\n  - -s [ipv6] / [ipv6]:port / hostname:port: also direct TCP.\n  - -s usb-serial: use adb server to talk to that device.\n  - Launches TUI interface for real-time monitoring.\n  - Use --timeout=0 for no timeout (default).\n  - --refresh SECONDS re-collects system info at that interval (default 0: only when pressing 'r').\n  - --watch SECONDS prints a one-line summary at that interval instead of the TUI (Ctrl-C to stop).\n  - --logcat-args \"ARGS\" passes buffers, filters or a -v format to logcat, e.g. --logcat-args \"-b kernel *:W\".\n  - pull REMOTE LOCAL / push LOCAL REMOTE copy a file without the adb binary, e.g. sbctool adb -s <serial> pull /sdcard/log.txt ./log.txt.");
+				return Ok(())
+			}
+
+			// `sbctool adb [-s SERIAL] pull|push SOURCE DEST`
+			if let Some(action) = extra.first().filter(|action| *action == "pull" || *action == "push") {
+				let [_, source, dest] = extra.as_slice() else {
+					return Err(anyhow::anyhow!("Usage: sbctool adb [-s SERIAL] {} SOURCE DEST", action));
+				};
+				run_adb_transfer(serial.as_deref().unwrap_or("auto"), action == "pull", source, dest)?;
+				return Ok(())
+			}
+			
+			if let Some(interval) = watch {
+				let collector = SystemInfoCollector::new("adb", serial.as_deref().unwrap_or("auto")).with_gpio(cli.gpio).with_updates(cli.updates);
+				run_watch(&collector, *interval).await?;
+				timing::report();
 				return Ok(())
 			}
 			
 			// Launch TUI for ADB connection
-			launch_adb_tui(serial.clone(), *timeout).await?;
+			launch_adb_tui(serial.clone(), *timeout, logcat_args.as_deref(), collect_options_from_cli(&cli)).await?;
+		}
+		Commands::Scp { target, source, dest } => {
+			let (source, dest) = match (source, dest) {
+				(Some(source), Some(dest)) if target != "help" && target != "--help" && target != "-h" => (source, dest),
+				_ => {
+					println!("Usage: sbctool scp <user@host|alias> <SOURCE> <DEST>\n\nExamples:\n  sbctool scp khadas ./app.bin :/tmp/app.bin\n  sbctool scp root@192.168.1.4 :/var/log/syslog ./syslog\n\nNotes:\n  - Exactly one of SOURCE/DEST must be remote, marked with a leading ':'.\n  - Uses the same alias resolution and authentication as 'sbctool ssh'.\n  - File mode is preserved in both directions.");
+					return Ok(())
+				}
+			};
+
+			let ssh_options = ssh_options_from_cli(&cli)?;
+			run_scp(target, source, dest, &ssh_options).await?;
+		}
+		Commands::Shell { target, adb: true } => {
+			adb_session::ADBSession::new(target).interactive_shell()?;
+		}
+		Commands::Shell { target, .. } => {
+			let ssh_options = ssh_options_from_cli(&cli)?;
+			let session = SSHSession::new_with_options(target, &ssh_options).await?;
+			// Like ssh, exit with the status of the remote shell
+			let exit_status = session.interactive_shell().await?;
+			if exit_status != 0 {
+				std::process::exit(exit_status);
+			}
+		}
+		Commands::Scan { subnet, ports, probe_timeout } => {
+			scan::run(subnet.as_deref(), ports, std::time::Duration::from_millis(*probe_timeout)).await?;
 		}
+		Commands::Connect { target, timeout } => {
+			match detect_backend(target)? {
+				(Backend::Ssh, reason) => {
+					verbose::status(&format!("Using SSH for {} ({})", target, reason));
+					let ssh_options = ssh_options_from_cli(&cli)?;
+					launch_ssh_tui(std::slice::from_ref(target), *timeout, &ssh_options, None, collect_options_from_cli(&cli)).await?;
+				}
+				(Backend::Adb, reason) => {
+					verbose::status(&format!("Using ADB for {} ({})", target, reason));
+					launch_adb_tui(Some(target.clone()), *timeout, None, collect_options_from_cli(&cli)).await?;
+				}
+			}
+		}
+		Commands::Ping { target, count } => {
+			let ssh_options = ssh_options_from_cli(&cli)?;
+			ping::run(target, &ssh_options, *count).await?;
+		}
+		Commands::Info { target, adb, format, fields } => {
+			// Check the selection before spending time on connecting
+			validate_fields(fields, *format)?;
+			let collector = if *adb {
+				SystemInfoCollector::new("adb", target).with_gpio(cli.gpio).with_updates(cli.updates)
+			} else {
+				let ssh_options = ssh_options_from_cli(&cli)?;
+				let session = SSHSession::new_with_options(target, &ssh_options).await?;
+				SystemInfoCollector::new_with_ssh_session("ssh", target, Arc::new(session))
+					.with_progress(!verbose::quiet())
+					.with_gpio(cli.gpio)
+					.with_updates(cli.updates)
+			};
+			run_info(&collector, *format, fields).await?;
+		}
+		Commands::Serve { target, adb, listen, interval } => {
+			let ssh_options = if *adb { None } else { Some(ssh_options_from_cli(&cli)?) };
+			let interval = std::time::Duration::from_secs(*interval);
+			serve::run(target, ssh_options.as_ref(), listen, interval, cli.log_lines, cli.gpio, cli.updates).await?;
+		}
+		Commands::Reboot { target, adb, yes } => {
+			let ssh_options = if *adb { None } else { Some(ssh_options_from_cli(&cli)?) };
+			power::run(power::PowerAction::Reboot, target, *adb, *yes, ssh_options.as_ref()).await?;
+		}
+		Commands::Poweroff { target, adb, yes } => {
+			let ssh_options = if *adb { None } else { Some(ssh_options_from_cli(&cli)?) };
+			power::run(power::PowerAction::Poweroff, target, *adb, *yes, ssh_options.as_ref()).await?;
+		}
+		Commands::Compare { ssh_target, adb_serial, only_mismatches, all } => {
+			let ssh_options = ssh_options_from_cli(&cli)?;
+			let session = SSHSession::new_with_options(ssh_target, &ssh_options).await?;
+			let ssh_collector = SystemInfoCollector::new_with_ssh_session("ssh", ssh_target, Arc::new(session)).with_gpio(cli.gpio).with_updates(cli.updates);
+			let adb_collector = SystemInfoCollector::new("adb", adb_serial).with_gpio(cli.gpio).with_updates(cli.updates);
+			let (ssh_info, adb_info) = tokio::join!(ssh_collector.collect_system_info(), adb_collector.collect_system_info());
+			compare::print(
+				(&format!("SSH {}", ssh_target), &ssh_info?),
+				(&format!("ADB {}", adb_serial), &adb_info?),
+				*only_mismatches,
+				*all,
+			)?;
+		}
+		Commands::Diff { file, target, all } => {
+			snapshot::diff(file, target.as_deref(), *all)?;
+		}
+		Commands::Completions { shell } => {
+			clap_complete::generate(*shell, &mut Cli::command(), "sbctool", &mut std::io::stdout());
+		}
+	}
+
+	timing::report();
+	Ok(())
+}
+
+enum Backend {
+	Ssh,
+	Adb,
+}
+
+/// Picks the backend for `sbctool connect` from the target syntax, with the reason for it
+fn detect_backend(target: &str) -> Result<(Backend, &'static str)> {
+	if target.contains('@') {
+		return Ok((Backend::Ssh, "user@host"));
+	}
+	if target.contains(':') && matches!(adb_session::AdbRoute::for_target(target), adb_session::AdbRoute::Tcp(_)) {
+		return Ok((Backend::Adb, "ip:port"));
+	}
+	if adb_session::ADBSession::server_serials().iter().any(|serial| serial == target) {
+		return Ok((Backend::Adb, "serial known to the adb server"));
+	}
+	// ssh -G echoes unknown names back unchanged, so a different hostname means a configured alias
+	if ssh_resolver::resolve(target).is_ok_and(|resolved| resolved.host != target) {
+		return Ok((Backend::Ssh, "ssh_config alias"));
+	}
+
+	Err(SbcError::NoTarget(format!(
+		"Can't tell whether '{}' is an SSH or ADB target; use 'sbctool ssh {}' or 'sbctool adb -s {}'",
+		target, target, target
+	))
+	.into())
+}
+
+/// Fills in what the command line left unset from a `--profile`. Without a subcommand the
+/// profile's target is opened like `sbctool ssh` / `sbctool adb` would.
+fn apply_profile(cli: &mut Cli, matches: &clap::ArgMatches, profile: profiles::Profile) -> Result<()> {
+	cli.port = cli.port.or(profile.port);
+	if cli.identity.is_none() {
+		cli.identity = profile.identity_path();
+	}
+	// --refresh has a default, so only its source tells whether it was typed
+	if matches.value_source("refresh") != Some(clap::parser::ValueSource::CommandLine) {
+		cli.refresh = profile.refresh.unwrap_or(cli.refresh);
+	}
+	if let Some(path) = profile.collectors_path() {
+		extra_collectors::set_config_path(path);
+	}
+
+	match &mut cli.command {
+		None => {
+			let target = profile
+				.target
+				.ok_or_else(|| anyhow::anyhow!("Profile '{}' has no target", cli.profile.as_deref().unwrap_or_default()))?;
+			cli.command = Some(match profile.connection {
+				profiles::Connection::Ssh => Commands::Ssh {
+					targets: vec![target],
+					timeout: 0,
+					watch: None,
+					log_source: profile.log_source,
+					script: None,
+					script_args: Vec::new(),
+					env: Vec::new(),
+					cwd: None,
+				},
+				profiles::Connection::Adb => Commands::Adb { serial: Some(target), timeout: 0, watch: None, logcat_args: None, extra: Vec::new() },
+			});
+		}
+		Some(Commands::Ssh { log_source, .. }) => *log_source = log_source.or(profile.log_source),
+		Some(_) => {}
+	}
+	Ok(())
+}
+
+/// What the TUI's collector tasks gather and how often
+#[derive(Clone, Copy)]
+struct CollectOptions {
+	gpio: bool,
+	updates: bool,
+	/// Log entries fetched on the first poll / before following the journal
+	log_lines: usize,
+	/// Unparsed log lines; skips the live journald stream, which only has parsed entries
+	raw_logs: bool,
+	/// Seconds between automatic collections, 0 = only on 'r'
+	refresh: u64,
+}
+
+fn collect_options_from_cli(cli: &Cli) -> CollectOptions {
+	CollectOptions {
+		gpio: cli.gpio,
+		updates: cli.updates,
+		log_lines: cli.log_lines,
+		raw_logs: cli.raw_logs,
+		refresh: cli.refresh,
+	}
+}
+
+/// clap `value_parser` for `--env KEY=VALUE`; the key must be a valid shell variable name
+fn parse_env_var(raw: &str) -> Result<(String, String), String> {
+	let (key, value) = raw.split_once('=').ok_or_else(|| format!("expected KEY=VALUE, got '{}'", raw))?;
+	let valid = key.chars().next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+		&& key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+	if !valid {
+		return Err(format!("'{}' is not a valid variable name", key));
 	}
+	Ok((key.to_string(), value.to_string()))
+}
+
+/// Waits for the 'r' key or the `--refresh` interval; false once the TUI has dropped its sender
+async fn wait_for_refresh(refresh_rx: &mut tokio::sync::mpsc::UnboundedReceiver<()>, interval: u64) -> bool {
+	if interval == 0 {
+		return refresh_rx.recv().await.is_some();
+	}
+
+	tokio::select! {
+		request = refresh_rx.recv() => request.is_some(),
+		_ = tokio::time::sleep(std::time::Duration::from_secs(interval)) => true,
+	}
+}
+
+fn ssh_options_from_cli(cli: &Cli) -> Result<SshOptions> {
+	let password = if cli.password_stdin && std::io::stdin().is_terminal() {
+		// Typed by hand: don't echo it
+		Some(rpassword::prompt_password("Password: ")?)
+	} else if cli.password_stdin {
+		// Only the line ending goes; other trailing whitespace can be part of the password
+		let mut line = String::new();
+		std::io::stdin().read_line(&mut line)?;
+		let line = line.strip_suffix('\n').unwrap_or(&line);
+		Some(line.strip_suffix('\r').unwrap_or(line).to_string())
+	} else {
+		std::env::var("SBCTOOL_PASSWORD").ok()
+	};
+
+	let identity = cli.identity.clone();
+	if let Some(path) = identity.as_ref().filter(|path| !path.exists()) {
+		return Err(anyhow::anyhow!("Identity file not found: {}", path.display()));
+	}
+	let auth_methods = if !cli.auth.is_empty() {
+		cli.auth.clone()
+	} else if identity.is_some() {
+		// Like `ssh -i`, an explicit key goes before the agent's keys
+		vec![AuthMethod::Key, AuthMethod::Agent, AuthMethod::Password]
+	} else {
+		ssh_session::DEFAULT_AUTH_METHODS.to_vec()
+	};
+
+	// Ask for an explicit key's passphrase now, before the TUI owns the terminal
+	let key_passphrase = match std::env::var("SBCTOOL_KEY_PASSPHRASE") {
+		Ok(passphrase) => Some(passphrase),
+		Err(_) => match identity.as_ref().filter(|path| ssh_session::key_is_encrypted(path)) {
+			Some(path) if std::io::stdin().is_terminal() => Some(rpassword::prompt_password(format!("Passphrase for {}: ", path.display()))?),
+			_ => None,
+		},
+	};
+
+	// Collection through the external ssh binary follows the same choices
+	ssh_resolver::set_external_auth(
+		auth_methods.contains(&AuthMethod::Agent),
+		auth_methods.contains(&AuthMethod::Key),
+		identity.clone(),
+	);
+
+	Ok(SshOptions {
+		password,
+		timeout: std::time::Duration::from_secs(cli.connect_timeout),
+		command_timeout: std::time::Duration::from_secs(cli.command_timeout.unwrap_or(cli.connect_timeout)),
+		keepalive: std::time::Duration::from_secs(cli.keepalive),
+		connect_retries: cli.connect_retries,
+		connect_backoff: std::time::Duration::try_from_secs_f64(cli.connect_backoff)
+			.map_err(|_| anyhow::anyhow!("--connect-backoff must be a non-negative number of seconds"))?,
+		wait: cli.wait.map(std::time::Duration::from_secs),
+		port: cli.port,
+		auth_methods,
+		identity,
+		key_passphrase,
+		proxy: cli.proxy.as_deref().map(proxy::Proxy::parse).transpose()?,
+	})
+}
+
+/// `--print-command`: resolves the target like the real command would and prints the plain
+/// ssh / scp / adb line for it, without connecting
+fn print_raw_command(cli: &Cli, command: &Commands) -> Result<()> {
+	let identity = cli.identity.as_deref();
+	let resolve = |target: &str| -> Result<ssh_resolver::ResolvedTarget> {
+		let mut resolved = ssh_resolver::resolve(target)?;
+		if let Some(port) = cli.port {
+			resolved.port = port;
+		}
+		Ok(resolved)
+	};
+	let ssh = |target: &str, remote_command: Option<&str>| -> Result<String> {
+		Ok(raw_command::ssh(&resolve(target)?, identity, remote_command))
+	};
+
+	let lines = match command {
+		Commands::Ssh { targets, script: Some(path), script_args, env, cwd, .. } => {
+			let remote_command: String = std::iter::once(format!("{}sh -s --", raw_command::env_prefix(cwd.as_deref(), env))).chain(script_args.iter().map(|arg| raw_command::quote(arg))).collect::<Vec<_>>().join(" ");
+			let input = raw_command::quote(&path.display().to_string());
+			targets
+				.iter()
+				.map(|target| Ok(format!("{} < {}", ssh(target, Some(&remote_command))?, input)))
+				.collect::<Result<Vec<_>>>()?
+		}
+		Commands::Ssh { targets, .. } => targets.iter().map(|target| ssh(target, None)).collect::<Result<Vec<_>>>()?,
+		Commands::Adb { serial, extra, .. } if matches!(extra.first().map(String::as_str), Some("pull" | "push")) => {
+			let args: Vec<&str> = extra.iter().map(String::as_str).collect();
+			vec![raw_command::adb(serial.as_deref().unwrap_or("auto"), &args)]
+		}
+		Commands::Adb { serial, .. } => vec![raw_command::adb(serial.as_deref().unwrap_or("auto"), &["shell"])],
+		Commands::Scp { target, source: Some(source), dest: Some(dest) } => {
+			vec![raw_command::scp(&resolve(target)?, identity, source, dest)]
+		}
+		Commands::Shell { target, adb: true } => vec![raw_command::adb(target, &["shell"])],
+		Commands::Compare { ssh_target, adb_serial, .. } => vec![ssh(ssh_target, None)?, raw_command::adb(adb_serial, &["shell"])],
+		Commands::Shell { target, .. } => vec![ssh(target, None)?],
+		Commands::Connect { target, .. } => match detect_backend(target)? {
+			(Backend::Ssh, _) => vec![ssh(target, None)?],
+			(Backend::Adb, _) => vec![raw_command::adb(target, &["shell"])],
+		},
+		Commands::Ping { target, .. } => vec![ssh(target, Some("true"))?],
+		Commands::Info { target, adb: true, .. } => vec![raw_command::adb(target, &["shell"])],
+		Commands::Info { target, .. } => vec![ssh(target, None)?],
+		Commands::Serve { target, adb: true, .. } => vec![raw_command::adb(target, &["shell"])],
+		Commands::Serve { target, .. } => vec![ssh(target, None)?],
+		Commands::Reboot { target, adb: true, .. } => vec![raw_command::adb(target, &["reboot"])],
+		Commands::Reboot { target, .. } => vec![ssh(target, Some("sudo systemctl reboot"))?],
+		Commands::Poweroff { target, adb: true, .. } => vec![raw_command::adb(target, &["shell", "reboot", "-p"])],
+		Commands::Poweroff { target, .. } => vec![ssh(target, Some("sudo systemctl poweroff"))?],
+		Commands::Scp { .. } | Commands::Scan { .. } | Commands::Diff { .. } | Commands::Completions { .. } => {
+			return Err(anyhow::anyhow!("--print-command needs a connecting command with its target, e.g. 'sbctool ssh khadas --print-command'"));
+		}
+	};
+
+	for line in lines {
+		println!("{}", line);
+	}
+	Ok(())
+}
 
+/// Drops the stored host key for `target` so the next connection records the new one
+fn reset_hostkey(target: &str, port: Option<u16>) -> Result<()> {
+	let resolved = ssh_resolver::resolve(target)?;
+	let port = port.unwrap_or(resolved.port);
+	match known_hosts::forget(&resolved.host, port)? {
+		0 => println!("No stored host key for {}:{}", resolved.host, port),
+		removed => println!("Removed {} stored host key(s) for {}:{}", removed, resolved.host, port),
+	}
 	Ok(())
 }
 
-async fn launch_ssh_tui(target: &str, timeout: u64) -> Result<()> {
-	println!("Connecting to {} via SSH...", target);
+async fn launch_ssh_tui(targets: &[String], timeout: u64, ssh_options: &SshOptions, log_source: Option<LogSource>, collect_options: CollectOptions) -> Result<()> {
+	// Connect to every target before the terminal takes over the screen, all at once so
+	// unreachable boards don't add up their connect timeouts
+	let connections = futures::future::join_all(targets.iter().map(|target| connect_ssh_target(target, ssh_options))).await;
 
 	// Setup terminal
 	let mut terminal = setup_terminal()?;
 	
-	// Create TUI app
-	let mut app = TuiApp::new();
+	// Create TUI app with one tab per target
+	let mut app = TuiApp::with_targets(targets);
+	let shutdown = app.shutdown.clone();
+	let mut log_tasks = Vec::new();
+	for ((view, target), (stream_session, shared_session)) in app.targets.iter_mut().zip(targets).zip(connections) {
+		log_tasks.push(spawn_ssh_target(view, target, stream_session, shared_session, log_source, collect_options, shutdown.clone()));
+	}
+	
+	// Run TUI
+	let result = app.run(&mut terminal, timeout);
+	
+	// Restore terminal
+	restore_terminal(&mut terminal)?;
+	result?;
+
+	// The TUI set `shutdown` on the way out; give the live log streams a moment to stop the
+	// remote journalctl and close their sessions
+	let deadline = tokio::time::Instant::now() + SHUTDOWN_GRACE;
+	for task in log_tasks {
+		if tokio::time::timeout_at(deadline, task).await.is_err() {
+			break;
+		}
+	}
 	
+	Ok(())
+}
+
+/// Opens the live log stream session and the session shared by collection for one target;
+/// without the shared one, collection goes through the ssh binary. The connects block (TCP,
+/// handshake, auth), so they run on a thread of their own to overlap with other targets'.
+async fn connect_ssh_target(target: &str, ssh_options: &SshOptions) -> (Result<SSHSession>, Option<Arc<SSHSession>>) {
+	verbose::status(&format!("Connecting to {} via SSH...", target));
+	let (target, ssh_options) = (target.to_string(), ssh_options.clone());
+	let runtime = tokio::runtime::Handle::current();
+	let connected = tokio::task::spawn_blocking(move || {
+		runtime.block_on(async {
+			let stream_session = SSHSession::new_with_options(&target, &ssh_options).await;
+			let shared_session = match SSHSession::new_with_options(&target, &ssh_options).await {
+				Ok(session) => Some(Arc::new(session)),
+				Err(e) => {
+					verbose::status(&format!("Native SSH session to {} failed ({}); collecting through the ssh binary", target, e));
+					None
+				}
+			};
+			(stream_session, shared_session)
+		})
+	})
+	.await;
+	connected.unwrap_or_else(|e| (Err(e.into()), None))
+}
+
+/// Starts the collector and log tasks feeding one target's view
+fn spawn_ssh_target(
+	view: &mut tui::TargetView,
+	target: &str,
+	stream_session: Result<SSHSession>,
+	shared_session: Option<Arc<SSHSession>>,
+	log_source: Option<LogSource>,
+	collect_options: CollectOptions,
+	shutdown: Arc<std::sync::atomic::AtomicBool>,
+) -> tokio::task::JoinHandle<()> {
 	// Add initial log entry
-	app.add_log(tui::LogEntry {
+	view.add_log(tui::LogEntry {
 		timestamp: chrono::Local::now().format("%H:%M:%S").to_string(),
 		level: "INFO".to_string(),
 		message: format!("Connecting to {} via SSH", target),
 	});
+
+	// Reconnect attempts show up in this target's logs panel
+	if let Ok(session) = &stream_session {
+		session.set_log_sender(view.logs.clone());
+	}
+	if let Some(session) = &shared_session {
+		session.set_log_sender(view.logs.clone());
+	}
 	
 	// Create system info collector (temporarily disable persistent SSH for testing)
-	let collector = SystemInfoCollector::new("ssh", target);
+	let collector = match &shared_session {
+		Some(session) => SystemInfoCollector::new_with_ssh_session("ssh", target, session.clone()),
+		None => SystemInfoCollector::new("ssh", target),
+	}
+	.with_gpio(collect_options.gpio)
+	.with_updates(collect_options.updates);
 	
+	// Channel used by the TUI 'r' key to request a new collection (besides --refresh)
+	let (refresh_tx, mut refresh_rx) = tokio::sync::mpsc::unbounded_channel::<()>();
+	view.set_refresh_sender(refresh_tx.clone());
+
+	// 'R' rebuilds the shared session right away instead of waiting out the reconnect backoff,
+	// has the log task reconnect the stream's session and collects again; without a shared
+	// session (ssh binary fallback) only the stream and the collection restart
+	let (reconnect_tx, mut reconnect_rx) = tokio::sync::mpsc::unbounded_channel::<()>();
+	view.set_reconnect_sender(reconnect_tx);
+	let reconnect_session = shared_session.clone();
+	let reconnect_logs = view.logs.clone();
+	let restart_stream = Arc::new(std::sync::atomic::AtomicBool::new(false));
+	let reconnect_stream = restart_stream.clone();
+	tokio::spawn(async move {
+		while reconnect_rx.recv().await.is_some() {
+			match &reconnect_session {
+				// The session logs the attempt and its outcome itself
+				Some(session) => {
+					let _ = session.force_reconnect().await;
+				}
+				None => {
+					if let Ok(mut logs) = reconnect_logs.lock() {
+						tui::push_log(&mut logs, tui::LogEntry {
+							timestamp: chrono::Local::now().format("%H:%M:%S").to_string(),
+							level: "INFO".to_string(),
+							message: "No persistent SSH session; every collection connects anew".to_string(),
+						});
+					}
+				}
+			}
+			reconnect_stream.store(true, Ordering::Relaxed);
+			if refresh_tx.send(()).is_err() {
+				break;
+			}
+		}
+	});
+
 	// Spawn async task to collect system info
-	let app_clone = app.system_info.clone();
-	let log_sender_clone = app.logs.clone();
+	let app_clone = view.system_info.clone();
+	let history_clone = view.history.clone();
+	let log_sender_clone = view.logs.clone();
+	let last_refresh_clone = view.last_refresh.clone();
+	let connection_lost_clone = view.connection_lost.clone();
+	let collector_shutdown = shutdown.clone();
 	tokio::spawn(async move {
 		// Add info log
 		let info_log = tui::LogEntry {
@@ -104,62 +965,121 @@ async fn launch_ssh_tui(target: &str, timeout: u64) -> Result<()> {
 			message: "Starting system info collection...".to_string(),
 		};
 		if let Ok(mut logs) = log_sender_clone.lock() {
-			logs.push(info_log);
+			tui::push_log(&mut logs, info_log);
 		}
 		
-		match collector.collect_system_info().await {
-			Ok(info) => {
-				if let Ok(mut system_info) = app_clone.lock() {
-					*system_info = Some(info);
+		let mut first_collection = true;
+		loop {
+			match collector.collect_system_info().await {
+				Ok(info) => {
+					connection_lost_clone.store(false, Ordering::Relaxed);
+					if let Ok(mut history) = history_clone.lock() {
+						history.push(&info);
+					}
+					if let Ok(mut system_info) = app_clone.lock() {
+						*system_info = Some(info);
+					}
+					if let Ok(mut last_refresh) = last_refresh_clone.lock() {
+						*last_refresh = Some(chrono::Local::now().format("%H:%M:%S").to_string());
+					}
+					// Add success log once; periodic refreshes only update the timestamp
+					if first_collection {
+						let success_log = tui::LogEntry {
+							timestamp: chrono::Local::now().format("%H:%M:%S").to_string(),
+							level: "INFO".to_string(),
+							message: "System info collected successfully".to_string(),
+						};
+						if let Ok(mut logs) = log_sender_clone.lock() {
+							tui::push_log(&mut logs, success_log);
+						}
+						first_collection = false;
+					}
 				}
-				// Add success log
-				let success_log = tui::LogEntry {
-					timestamp: chrono::Local::now().format("%H:%M:%S").to_string(),
-					level: "INFO".to_string(),
-					message: "System info collected successfully".to_string(),
-				};
-				if let Ok(mut logs) = log_sender_clone.lock() {
-					logs.push(success_log);
+				Err(e) => {
+					connection_lost_clone.store(true, Ordering::Relaxed);
+					// Add error log
+					let error_log = tui::LogEntry {
+						timestamp: chrono::Local::now().format("%H:%M:%S").to_string(),
+						level: "ERROR".to_string(),
+						message: format!("Failed to collect system info: {}", e),
+					};
+					if let Ok(mut logs) = log_sender_clone.lock() {
+						tui::push_log(&mut logs, error_log);
+					}
 				}
 			}
-			Err(e) => {
-				// Add error log
-				let error_log = tui::LogEntry {
-					timestamp: chrono::Local::now().format("%H:%M:%S").to_string(),
-					level: "ERROR".to_string(),
-					message: format!("Failed to collect system info: {}", e),
-				};
-				if let Ok(mut logs) = log_sender_clone.lock() {
-					logs.push(error_log);
-				}
+
+			// Wait for the next refresh; the TUI dropping its sender ends the task
+			if !wait_for_refresh(&mut refresh_rx, collect_options.refresh).await || collector_shutdown.load(Ordering::Relaxed) {
+				break;
 			}
 		}
 	});
 	
-	// Spawn async task to collect logs
-	let log_collector = log_collector::LogCollector::new("ssh", target, false);
-	let log_sender = app.logs.clone();
+	// Spawn async task to stream logs, falling back to polling when streaming isn't supported
+	let log_collector = match &shared_session {
+		Some(session) => log_collector::LogCollector::new_with_ssh_session("ssh", target, false, session.clone()),
+		None => log_collector::LogCollector::new("ssh", target, false),
+	}
+	.with_log_source(log_source)
+	.with_log_lines(collect_options.log_lines)
+	.with_raw_logs(collect_options.raw_logs)
+	.with_shutdown(shutdown.clone());
+	let log_sender = view.logs.clone();
 	tokio::spawn(async move {
+		// The live stream follows journald, so another explicit source goes straight to polling
+		if matches!(log_source, None | Some(LogSource::Journald)) && !collect_options.raw_logs {
+			let fallback_reason = match stream_session {
+				Ok(session) => loop {
+					let (stop_shutdown, stop_restart) = (shutdown.clone(), restart_stream.clone());
+					let stop = move || stop_shutdown.load(Ordering::Relaxed) || stop_restart.load(Ordering::Relaxed);
+					let result = session.start_log_stream(log_sender.clone(), stop, collect_options.log_lines).await;
+					if shutdown.load(Ordering::Relaxed) {
+						session.disconnect().await;
+						return;
+					}
+					// 'R': follow the logs again on a fresh session; this fetches the history once more
+					if restart_stream.swap(false, Ordering::Relaxed) {
+						match session.force_reconnect().await {
+							Ok(()) => continue,
+							Err(e) => break e.to_string(),
+						}
+					}
+					break match result {
+						Ok(()) => "live log stream ended".to_string(),
+						Err(e) => e.to_string(),
+					};
+				},
+				Err(e) => e.to_string(),
+			};
+
+			if shutdown.load(Ordering::Relaxed) {
+				return;
+			}
+
+			let warn_log = tui::LogEntry {
+				timestamp: chrono::Local::now().format("%H:%M:%S").to_string(),
+				level: "WARN".to_string(),
+				message: format!("Live log streaming unavailable ({}), falling back to polling", fallback_reason),
+			};
+			if let Ok(mut logs) = log_sender.lock() {
+				tui::push_log(&mut logs, warn_log);
+			}
+		}
+
 		log_collector.start_log_collection(log_sender).await;
-	});
-	
-	// Run TUI
-	app.run(&mut terminal, timeout)?;
-	
-	// Restore terminal
-	restore_terminal(&mut terminal)?;
-	
-	Ok(())
+	})
 }
 
-async fn launch_adb_tui(serial: Option<String>, timeout: u64) -> Result<()> {
+async fn launch_adb_tui(serial: Option<String>, timeout: u64, logcat_args: Option<&str>, collect_options: CollectOptions) -> Result<()> {
 	let target = if let Some(s) = &serial {
 		s.clone()
 	} else {
 		"auto".to_string()
 	};
 	
-	println!("Connecting to ADB device: {}", target);
+	let route = adb_session::AdbRoute::for_target(&target).description();
+	verbose::status(&format!("Connecting to ADB device: {} ({})", target, route));
 
 	// Setup terminal
 	let mut terminal = setup_terminal()?;
@@ -171,39 +1091,65 @@ async fn launch_adb_tui(serial: Option<String>, timeout: u64) -> Result<()> {
 	app.add_log(tui::LogEntry {
 		timestamp: chrono::Local::now().format("%H:%M:%S").to_string(),
 		level: "INFO".to_string(),
-		message: format!("Connecting to ADB device: {}", target),
+		message: format!("Connecting to ADB device: {} ({})", target, route),
 	});
 	
 	// Create system info collector
-	let collector = SystemInfoCollector::new("adb", &target);
+	let collector = SystemInfoCollector::new("adb", &target).with_gpio(collect_options.gpio).with_updates(collect_options.updates);
 	
+	// Channel used by the TUI 'r' key to request a new collection (besides --refresh)
+	let (refresh_tx, mut refresh_rx) = tokio::sync::mpsc::unbounded_channel::<()>();
+	app.set_refresh_sender(refresh_tx);
+
 	// Spawn async task to collect system info
-	let app_clone = app.system_info.clone();
-	let log_sender_clone = app.logs.clone();
+	let app_clone = app.targets[0].system_info.clone();
+	let history_clone = app.targets[0].history.clone();
+	let log_sender_clone = app.targets[0].logs.clone();
+	let last_refresh_clone = app.targets[0].last_refresh.clone();
+	let connection_lost_clone = app.targets[0].connection_lost.clone();
 	tokio::spawn(async move {
-		match collector.collect_system_info().await {
-			Ok(info) => {
-				if let Ok(mut system_info) = app_clone.lock() {
-					*system_info = Some(info);
+		loop {
+			match collector.collect_system_info().await {
+				Ok(info) => {
+					connection_lost_clone.store(false, Ordering::Relaxed);
+					if let Ok(mut history) = history_clone.lock() {
+						history.push(&info);
+					}
+					if let Ok(mut system_info) = app_clone.lock() {
+						*system_info = Some(info);
+					}
+					if let Ok(mut last_refresh) = last_refresh_clone.lock() {
+						*last_refresh = Some(chrono::Local::now().format("%H:%M:%S").to_string());
+					}
 				}
-			}
-			Err(e) => {
-				// Add error log
-				let error_log = tui::LogEntry {
-					timestamp: chrono::Local::now().format("%H:%M:%S").to_string(),
-					level: "ERROR".to_string(),
-					message: format!("Failed to collect system info: {}", e),
-				};
-				if let Ok(mut logs) = log_sender_clone.lock() {
-					logs.push(error_log);
+				Err(e) => {
+					connection_lost_clone.store(true, Ordering::Relaxed);
+					// Add error log
+					let error_log = tui::LogEntry {
+						timestamp: chrono::Local::now().format("%H:%M:%S").to_string(),
+						level: "ERROR".to_string(),
+						message: format!("Failed to collect system info: {}", e),
+					};
+					if let Ok(mut logs) = log_sender_clone.lock() {
+						tui::push_log(&mut logs, error_log);
+					}
 				}
 			}
+
+			// Wait for the next refresh; the TUI dropping its sender ends the task
+			if !wait_for_refresh(&mut refresh_rx, collect_options.refresh).await {
+				break;
+			}
 		}
 	});
 	
 	// Spawn async task to collect logs (Android logcat)
-	let log_collector = log_collector::LogCollector::new("adb", &target, true);
-	let log_sender = app.logs.clone();
+	let log_collector = log_collector::LogCollector::new("adb", &target, true)
+		.with_log_lines(collect_options.log_lines)
+		.with_logcat_args(logcat_args)
+		.with_raw_logs(collect_options.raw_logs)
+		.with_shutdown(app.shutdown.clone());
+	let log_sender = app.targets[0].logs.clone();
 	tokio::spawn(async move {
 		log_collector.start_log_collection(log_sender).await;
 	});
@@ -215,4 +1161,352 @@ async fn launch_adb_tui(serial: Option<String>, timeout: u64) -> Result<()> {
 	restore_terminal(&mut terminal)?;
 	
 	Ok(())
-}
\ No newline at end of file
+}
+
+async fn run_watch(collector: &SystemInfoCollector, interval: u64) -> Result<()> {
+	let interval = std::time::Duration::from_secs(interval);
+
+	loop {
+		let timestamp = chrono::Local::now().format("%H:%M:%S").to_string();
+		tokio::select! {
+			_ = tokio::signal::ctrl_c() => break,
+			result = collector.collect_system_info() => {
+				let entry = match result {
+					Ok(info) => {
+						let summary = format_summary_line(&info);
+						println!("[{}] {}", timestamp, summary);
+						tui::LogEntry { timestamp, level: "INFO".to_string(), message: summary }
+					}
+					Err(e) => {
+						let message = format!("Failed to collect system info: {}", e);
+						eprintln!("[{}] {}", timestamp, message);
+						tui::LogEntry { timestamp, level: "ERROR".to_string(), message }
+					}
+				};
+				log_export::record(&entry);
+			}
+		}
+		std::io::stdout().flush()?;
+
+		tokio::select! {
+			_ = tokio::signal::ctrl_c() => break,
+			_ = tokio::time::sleep(interval) => {}
+		}
+	}
+
+	std::io::stdout().flush()?;
+	Ok(())
+}
+
+fn format_summary_line(info: &tui::SystemInfo) -> String {
+	let temperature = match info.temperature {
+		Some(temp) => color::temperature(temp),
+		None => "n/a".to_string(),
+	};
+	let load = match info.load_average {
+		Some((one, five, fifteen)) => format!("{:.2} {:.2} {:.2}", one, five, fifteen),
+		None => "n/a".to_string(),
+	};
+	format!("{} | up {} | load {} | mem {} | temp {}", color::bold(&info.hostname), info.uptime, load, info.memory, temperature)
+}
+
+async fn run_info(collector: &SystemInfoCollector, format: InfoFormat, fields: &[String]) -> Result<()> {
+	let info = collector.collect_system_info().await?;
+
+	if !fields.is_empty() {
+		// Undetected fields stay omitted, like in the full output
+		let mut selected = serde_json::to_value(&info)?;
+		if let Some(object) = selected.as_object_mut() {
+			object.retain(|key, _| fields.contains(key));
+		}
+		match format {
+			InfoFormat::Json => println!("{}", serde_json::to_string(&selected)?),
+			InfoFormat::PrettyJson => println!("{}", serde_json::to_string_pretty(&selected)?),
+			InfoFormat::Yaml => print!("{}", serde_yaml::to_string(&selected)?),
+			InfoFormat::Toml => print!("{}", toml::to_string(&selected)?),
+			InfoFormat::Prometheus | InfoFormat::Table => unreachable!("rejected by validate_fields"),
+		}
+		return Ok(());
+	}
+
+	match format {
+		InfoFormat::Json => println!("{}", serde_json::to_string(&info)?),
+		InfoFormat::PrettyJson => println!("{}", serde_json::to_string_pretty(&info)?),
+		InfoFormat::Yaml => print!("{}", serde_yaml::to_string(&info)?),
+		InfoFormat::Toml => print!("{}", toml::to_string(&info)?),
+		InfoFormat::Prometheus => print!("{}", metrics::render(&info)),
+		InfoFormat::Table => print!("{}", format_info_table(&info)),
+	}
+
+	Ok(())
+}
+
+/// Rejects `--fields` with the table format or with names that aren't `SystemInfo` fields
+fn validate_fields(fields: &[String], format: InfoFormat) -> Result<()> {
+	if fields.is_empty() {
+		return Ok(());
+	}
+	if matches!(format, InfoFormat::Prometheus | InfoFormat::Table) {
+		return Err(anyhow::anyhow!("--fields needs --format json, pretty-json, yaml or toml"));
+	}
+	let unknown: Vec<&str> = fields
+		.iter()
+		.map(String::as_str)
+		.filter(|field| !tui::SystemInfo::FIELD_NAMES.contains(field))
+		.collect();
+	if !unknown.is_empty() {
+		return Err(anyhow::anyhow!(
+			"Unknown field(s): {}. Valid fields: {}",
+			unknown.join(", "),
+			tui::SystemInfo::FIELD_NAMES.join(", ")
+		));
+	}
+	Ok(())
+}
+
+/// Renders the report as key/value rows with the values aligned in one column
+fn format_info_table(info: &tui::SystemInfo) -> String {
+	let not_available = || "n/a".to_string();
+	let mut rows: Vec<(String, String)> = vec![
+		("Hostname".to_string(), info.hostname.clone()),
+		("Chip".to_string(), info.chip.clone().unwrap_or_else(not_available)),
+		("Serial".to_string(), info.serial_number.clone().unwrap_or_else(not_available)),
+		("GPU".to_string(), info.gpu.clone().unwrap_or_else(not_available)),
+		("VPU".to_string(), info.vpu.clone().unwrap_or_else(not_available)),
+		("CPU".to_string(), info.cpu_info.clone()),
+	];
+
+	// Headless boards have no display, so no row rather than "n/a"
+	if let Some(display) = &info.display {
+		rows.insert(5, ("Display".to_string(), display.clone()));
+	}
+
+	if !info.cpu_freqs.is_empty() {
+		let freqs: Vec<String> = info.cpu_freqs
+			.iter()
+			.map(|f| format!("{}:{}/{}", f.core, f.cur_mhz, f.max_mhz))
+			.collect();
+		rows.push(("CPU MHz".to_string(), freqs.join(" ")));
+	}
+	if let Some(usage) = info.cpu_usage_percent {
+		let cores: Vec<String> = info.cpu_core_usage.iter().map(|core| format!("{:.0}%", core)).collect();
+		rows.push(("CPU busy".to_string(), if cores.is_empty() {
+			format!("{:.0}%", usage)
+		} else {
+			format!("{:.0}% (per core: {})", usage, cores.join(" "))
+		}));
+	}
+
+	rows.extend([
+		("Architecture".to_string(), info.architecture.clone()),
+		("Kernel".to_string(), info.kernel.clone()),
+		("OS".to_string(), info.os_info.clone()),
+		("Init".to_string(), info.init_system.clone().unwrap_or_else(not_available)),
+		("Virtualization".to_string(), info.virtualization.clone().unwrap_or_else(not_available)),
+		("Memory".to_string(), match (info.memory_used_percent, &info.mem_available) {
+			(Some(percent), Some(available)) => match &info.mem_cached {
+				Some(cached) => format!("{} ({:.0}% used, {} available, {} cached)", info.memory, percent, available, cached),
+				None => format!("{} ({:.0}% used, {} available)", info.memory, percent, available),
+			},
+			(Some(percent), None) => format!("{} ({:.0}% used)", info.memory, percent),
+			_ => info.memory.clone(),
+		}),
+		("Swap".to_string(), info.swap.clone()),
+		("Uptime".to_string(), info.uptime.clone()),
+		("Load".to_string(), match info.load_average {
+			Some((one, five, fifteen)) => format!("{:.2} {:.2} {:.2}", one, five, fifteen),
+			None => not_available(),
+		}),
+		("Temperature".to_string(), match info.temperature {
+			Some(temp) => color::temperature(temp),
+			None => not_available(),
+		}),
+	]);
+
+	if info.thermal_zones.len() > 1 {
+		let zones: Vec<String> = info.thermal_zones.iter().map(|zone| format!("{} {}", zone.label, color::temperature(zone.celsius()))).collect();
+		rows.push(("Thermal zones".to_string(), zones.join(", ")));
+	}
+
+	if let Some(userland) = &info.userland {
+		rows.push(("Userland".to_string(), userland.clone()));
+	}
+	if let Some(updates) = &info.pending_updates {
+		rows.push(("Packages".to_string(), updates.label()));
+	}
+
+	for fan in &info.fans {
+		rows.push((format!("Fan {}", fan.label), fan.value_label()));
+	}
+
+	if let Some(power) = &info.power_status {
+		rows.push(("Power".to_string(), power.clone()));
+	}
+
+	if let Some(battery) = &info.battery {
+		rows.push(("Battery".to_string(), battery.summary()));
+	}
+
+	for (key, value) in [("Root", &info.boot_info.root), ("Root mount", &info.boot_info.root_mount), ("Bootloader", &info.boot_info.bootloader), ("Cmdline", &info.boot_info.cmdline)] {
+		if let Some(value) = value {
+			rows.push((key.to_string(), value.clone()));
+		}
+	}
+
+	if !info.overlays.is_empty() {
+		rows.push(("Overlays".to_string(), info.overlays.join(", ")));
+	}
+
+	for device in &info.storage_devices {
+		let mut value = device.size_label();
+		for detail in [&device.media, &device.model].into_iter().flatten() {
+			value.push_str(&format!(" {}", detail));
+		}
+		if let Some(wear) = device.wear_label() {
+			value.push_str(&format!(", wear {}", wear));
+		}
+		rows.push((format!("Disk {}", device.name), value));
+	}
+	for mount in &info.mounts {
+		let mut value = format!("{} {} {}", mount.device, mount.fstype, mount.size_label());
+		if let Some(used) = mount.used_percent {
+			value.push_str(&format!(" {:.0}% used", used));
+		}
+		if mount.read_only {
+			value.push_str(" (read-only)");
+		}
+		rows.push((format!("Mount {}", mount.mountpoint), value));
+	}
+
+	for device in &info.pci_devices {
+		rows.push(("PCI".to_string(), device.clone()));
+	}
+
+	if !info.modules.is_empty() {
+		let names: Vec<&str> = info.modules.iter().map(|module| module.name.as_str()).collect();
+		rows.push(("Modules".to_string(), format!("{} loaded: {}", names.len(), names.join(" "))));
+	}
+
+	for alert in &info.alerts {
+		rows.push((format!("Alert {}", alert.kind), format!("[{}] {}", alert.timestamp, alert.message)));
+	}
+
+	for unit in &info.failed_units {
+		rows.push((format!("Failed {}", unit.name), unit.summary()));
+	}
+
+	for process in &info.top_processes {
+		let percent = |value: Option<f64>| value.map(|v| format!("{:.1}%", v)).unwrap_or_else(not_available);
+		rows.push((
+			format!("PID {}", process.pid),
+			format!("{} (cpu {}, mem {})", process.command, percent(process.cpu_percent), percent(process.mem_percent)),
+		));
+	}
+
+	for extra in &info.extra {
+		rows.push((extra.label.clone(), extra.value.clone().unwrap_or_else(not_available)));
+	}
+
+	for gpio in &info.gpio {
+		let mut value = format!("{} {}", gpio.name.as_deref().unwrap_or("-"), gpio.direction);
+		if let Some(level) = gpio.value {
+			value.push_str(&format!("={}", level));
+		}
+		if let Some(consumer) = &gpio.consumer {
+			value.push_str(&format!(" [{}]", consumer));
+		}
+		rows.push((format!("GPIO {}:{}", gpio.chip, gpio.line), value));
+	}
+
+	for iface in info.network.iter().filter(|iface| iface.name != "lo") {
+		let mut value = iface.state.clone();
+		for addr in iface.ipv4.iter().chain(iface.ipv6.iter()) {
+			value.push(' ');
+			value.push_str(addr);
+		}
+		if let Some(mac) = &iface.mac {
+			value.push_str(&format!(" [{}]", mac));
+		}
+		if let Some(wifi) = &iface.wifi {
+			for detail in [wifi.ssid.clone(), wifi.signal_dbm.map(|dbm| format!("{} dBm", dbm)), wifi.tx_bitrate.clone()].into_iter().flatten() {
+				value.push_str(&format!(", {}", detail));
+			}
+		}
+		rows.push((format!("Net {}", iface.name), value));
+	}
+
+	let width = rows.iter().map(|(key, _)| key.len()).max().unwrap_or(0);
+	rows.iter()
+		// Pad before coloring so escape codes don't skew the alignment
+		.map(|(key, value)| format!("{}  {}\n", color::cyan(&format!("{:<width$}", key, width = width)), value))
+		.collect()
+}
+
+/// Runs a local script on `target` and streams its combined output to stdout; a nonzero exit
+/// status becomes a `CommandFailed` error
+async fn run_script(target: &str, path: &std::path::Path, args: &[String], ssh_options: &SshOptions) -> Result<()> {
+	let script = std::fs::read(path).map_err(|e| anyhow::anyhow!("Can't read script {}: {}", path.display(), e))?;
+	let session = SSHSession::new_with_options(target, ssh_options).await?;
+
+	let mut stdout = std::io::stdout();
+	let exit_status = session
+		.run_script(&script, args, |output| {
+			let _ = stdout.write_all(output);
+			let _ = stdout.flush();
+		})
+		.await?;
+
+	if exit_status != 0 {
+		return Err(SbcError::CommandFailed { exit_status, output: String::new() }.into());
+	}
+	Ok(())
+}
+
+/// `adb pull` / `adb push` through adb_client, with the same progress output as `scp`
+fn run_adb_transfer(target: &str, pull: bool, source: &str, dest: &str) -> Result<()> {
+	let session = adb_session::ADBSession::new(target);
+	let transferred = if pull {
+		verbose::status(&format!("Pulling {}:{} -> {}", target, source, dest));
+		session.pull(source, &paths::expand(dest), print_progress)?
+	} else {
+		verbose::status(&format!("Pushing {} -> {}:{}", source, target, dest));
+		session.push(&paths::expand(source), dest, print_progress)?
+	};
+
+	if !verbose::quiet() {
+		println!("\nTransferred {} bytes", transferred);
+	}
+	Ok(())
+}
+
+async fn run_scp(target: &str, source: &str, dest: &str, ssh_options: &SshOptions) -> Result<()> {
+	let session = SSHSession::new_with_options(target, ssh_options).await?;
+
+	let transferred = match (source.strip_prefix(':'), dest.strip_prefix(':')) {
+		(None, Some(remote)) => {
+			verbose::status(&format!("Uploading {} -> {}:{}", source, target, remote));
+			session.upload_file(&paths::expand(source), remote, print_progress).await?
+		}
+		(Some(remote), None) => {
+			verbose::status(&format!("Downloading {}:{} -> {}", target, remote, dest));
+			session.download_file(remote, &paths::expand(dest), print_progress).await?
+		}
+		_ => {
+			return Err(anyhow::anyhow!("Exactly one of SOURCE or DEST must be a remote path prefixed with ':'"));
+		}
+	};
+
+	if !verbose::quiet() {
+		println!("\nTransferred {} bytes", transferred);
+	}
+	Ok(())
+}
+
+fn print_progress(transferred: u64, total: u64) {
+	if verbose::quiet() {
+		return;
+	}
+	let percent = if total > 0 { transferred * 100 / total } else { 100 };
+	print!("\r{} / {} bytes ({}%)", transferred, total, percent);
+	let _ = std::io::stdout().flush();
+}
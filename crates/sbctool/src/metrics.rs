@@ -0,0 +1,71 @@
+use crate::tui::SystemInfo;
+use std::fmt::Write;
+
+/// Numeric fields of `info` in the Prometheus text exposition format, labelled with the hostname,
+/// plus an `sbctool_info` gauge carrying the text fields as labels
+pub fn render(info: &SystemInfo) -> String {
+    let labels = format!("hostname=\"{}\"", escape(&info.hostname));
+    let mut out = String::new();
+    let mut gauge = |name: &str, help: &str, value: f64| {
+        let _ = writeln!(out, "# HELP {} {}", name, help);
+        let _ = writeln!(out, "# TYPE {} gauge", name);
+        let _ = writeln!(out, "{}{{{}}} {}", name, labels, value);
+    };
+
+    if let Some(temperature) = info.temperature {
+        gauge("sbctool_temperature_celsius", "SoC temperature.", temperature);
+    }
+    if let Some(busy) = info.cpu_usage_percent {
+        gauge("sbctool_cpu_busy_ratio", "Share of CPU time not idle or in iowait, over half a second.", busy / 100.0);
+    }
+    if let Some(used) = info.memory_used_percent {
+        gauge("sbctool_memory_used_ratio", "Share of memory in use (MemTotal minus MemAvailable).", used / 100.0);
+    }
+    if let Some(total) = info.memory_total_bytes {
+        gauge("sbctool_memory_total_bytes", "MemTotal.", total as f64);
+        if let Some(used) = info.memory_used_percent {
+            gauge("sbctool_memory_used_bytes", "MemTotal minus MemAvailable.", (total as f64 * used / 100.0).round());
+        }
+    }
+    if let Some((one, five, fifteen)) = info.load_average {
+        gauge("sbctool_load1", "1-minute load average.", one);
+        gauge("sbctool_load5", "5-minute load average.", five);
+        gauge("sbctool_load15", "15-minute load average.", fifteen);
+    }
+    if let Some(uptime) = info.uptime_seconds {
+        gauge("sbctool_uptime_seconds", "Time since boot.", uptime as f64);
+    }
+    if let Some(cores) = info.cpu_cores {
+        gauge("sbctool_cpu_cores", "Processors listed in /proc/cpuinfo.", cores as f64);
+    }
+
+    // One series per thermal zone, labelled with its type
+    if !info.thermal_zones.is_empty() {
+        let _ = writeln!(out, "# HELP sbctool_thermal_zone_celsius Temperature of each thermal zone.");
+        let _ = writeln!(out, "# TYPE sbctool_thermal_zone_celsius gauge");
+        for zone in &info.thermal_zones {
+            let _ = writeln!(out, "sbctool_thermal_zone_celsius{{{},zone=\"{}\"}} {}", labels, escape(&zone.label), zone.celsius());
+        }
+    }
+
+    // Text fields go into the labels of a constant gauge, like node_exporter's node_uname_info
+    let text_labels: Vec<String> = [
+        ("kernel", Some(&info.kernel)),
+        ("architecture", Some(&info.architecture)),
+        ("os", Some(&info.os_info)),
+        ("chip", info.chip.as_ref()),
+    ]
+    .into_iter()
+    .filter_map(|(name, value)| Some((name, value?)))
+    .map(|(name, value)| format!(",{}=\"{}\"", name, escape(value)))
+    .collect();
+    let _ = writeln!(out, "# HELP sbctool_info Text fields of the board, as labels.");
+    let _ = writeln!(out, "# TYPE sbctool_info gauge");
+    let _ = writeln!(out, "sbctool_info{{{}{}}} 1", labels, text_labels.concat());
+    out
+}
+
+/// Label values are double-quoted, so backslashes, quotes and newlines need escaping
+pub fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
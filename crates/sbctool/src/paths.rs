@@ -0,0 +1,39 @@
+use std::path::PathBuf;
+
+/// Expands a leading `~` or `~user` and `$VAR` / `${VAR}` anywhere in a user-supplied path.
+/// Unknown users and unset variables are left as written, so the "not found" error shows them.
+pub fn expand(raw: &str) -> PathBuf {
+    let with_home = match other_users_home(raw) {
+        Some(expanded) => expanded,
+        None => shellexpand::tilde(raw).into_owned(),
+    };
+    PathBuf::from(shellexpand::env_with_context_no_errors(&with_home, |name| std::env::var(name).ok()).into_owned())
+}
+
+/// clap `value_parser` for path arguments, so every `PathBuf` flag is expanded the same way
+pub fn parse_arg(raw: &str) -> Result<PathBuf, String> {
+    Ok(expand(raw))
+}
+
+/// `~user/rest` with the home directory from /etc/passwd; None for plain `~` or an unknown user
+#[cfg(unix)]
+fn other_users_home(raw: &str) -> Option<String> {
+    let rest = raw.strip_prefix('~')?;
+    let (user, tail) = rest.split_at(rest.find('/').unwrap_or(rest.len()));
+    if user.is_empty() {
+        return None;
+    }
+
+    // name:password:uid:gid:gecos:home:shell
+    let passwd = std::fs::read_to_string("/etc/passwd").ok()?;
+    let home = passwd.lines().find_map(|line| {
+        let fields: Vec<&str> = line.split(':').collect();
+        (fields.len() >= 6 && fields[0] == user).then(|| fields[5].to_string())
+    })?;
+    Some(format!("{}{}", home, tail))
+}
+
+#[cfg(not(unix))]
+fn other_users_home(_raw: &str) -> Option<String> {
+    None
+}
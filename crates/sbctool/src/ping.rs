@@ -0,0 +1,68 @@
+use crate::ssh_session::{SSHSession, SshOptions};
+use anyhow::Result;
+use std::time::{Duration, Instant};
+
+/// Pause between attempts, like ping's default interval
+const PING_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Connects, authenticates and runs `true` on `target` `count` times, printing one
+/// `key=value` line per attempt and a summary line. Each attempt is a single connect, so
+/// --connect-retries doesn't hide a flaky board. Fails with the last attempt's error if any
+/// attempt failed, so the exit status tells auth failures from unreachable boards.
+pub async fn run(target: &str, ssh_options: &SshOptions, count: u32) -> Result<()> {
+    let options = SshOptions { connect_retries: 0, ..ssh_options.clone() };
+    let mut latencies = Vec::new();
+    let mut last_error = None;
+
+    for seq in 1..=count {
+        if seq > 1 {
+            tokio::time::sleep(PING_INTERVAL).await;
+        }
+        match attempt(target, &options).await {
+            Ok((connect, exec)) => {
+                println!(
+                    "seq={} target={} status=ok connect_ms={:.1} exec_ms={:.1}",
+                    seq,
+                    target,
+                    millis(connect),
+                    millis(exec)
+                );
+                latencies.push(millis(connect));
+            }
+            Err(e) => {
+                println!("seq={} target={} status=failed error={:?}", seq, target, e.to_string());
+                last_error = Some(e);
+            }
+        }
+    }
+
+    let sent = count as usize;
+    let mut summary = format!("summary target={} sent={} ok={} failed={}", target, sent, latencies.len(), sent - latencies.len());
+    if !latencies.is_empty() {
+        let min = latencies.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = latencies.iter().copied().fold(0.0, f64::max);
+        let avg = latencies.iter().sum::<f64>() / latencies.len() as f64;
+        summary.push_str(&format!(" min_ms={:.1} avg_ms={:.1} max_ms={:.1}", min, avg, max));
+    }
+    println!("{}", summary);
+
+    match last_error {
+        Some(e) => Err(e),
+        None => Ok(()),
+    }
+}
+
+/// Time to a logged-in session (resolve, connect, handshake, auth) and for the `true` round trip
+async fn attempt(target: &str, options: &SshOptions) -> Result<(Duration, Duration)> {
+    let started = Instant::now();
+    let session = SSHSession::new_with_options(target, options).await?;
+    let connect = started.elapsed();
+
+    let started = Instant::now();
+    session.execute_command("true").await?;
+    Ok((connect, started.elapsed()))
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
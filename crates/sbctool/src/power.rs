@@ -0,0 +1,134 @@
+use crate::adb_session::ADBSession;
+use crate::ssh_resolver;
+use crate::ssh_session::{SSHSession, SshOptions};
+use crate::verbose;
+use anyhow::Result;
+use std::io::Write;
+use std::time::{Duration, Instant};
+use tokio::net::TcpStream;
+
+/// How long to wait for the board to drop off after the command was accepted
+const DOWN_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Pause between reachability probes, also the TCP connect timeout of each probe
+const PROBE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Fails early when neither root nor passwordless sudo is available, since the detached
+/// shutdown below can't report that
+const PRIVILEGE_CHECK: &str = "[ $(id -u) -eq 0 ] || sudo -n true";
+
+#[derive(Clone, Copy)]
+pub enum PowerAction {
+    Reboot,
+    Poweroff,
+}
+
+impl PowerAction {
+    fn verb(&self) -> &'static str {
+        match self {
+            PowerAction::Reboot => "reboot",
+            PowerAction::Poweroff => "power off",
+        }
+    }
+
+    /// systemd first, then the classic binaries found on BusyBox / SysV boards. Detached with a
+    /// short delay so the SSH command returns before the connection goes away.
+    fn ssh_command(&self) -> &'static str {
+        match self {
+            PowerAction::Reboot => {
+                "S=; [ $(id -u) -eq 0 ] || S=\"sudo -n\"; nohup sh -c \"sleep 1; $S systemctl reboot || $S reboot\" >/dev/null 2>&1 &"
+            }
+            PowerAction::Poweroff => {
+                "S=; [ $(id -u) -eq 0 ] || S=\"sudo -n\"; nohup sh -c \"sleep 1; $S systemctl poweroff || $S poweroff\" >/dev/null 2>&1 &"
+            }
+        }
+    }
+}
+
+/// Asks for confirmation (unless `yes`), sends the action and waits for the board to go down
+pub async fn run(action: PowerAction, target: &str, adb: bool, yes: bool, ssh_options: Option<&SshOptions>) -> Result<()> {
+    if !yes && !confirm(&format!("{} {}?", capitalize(action.verb()), target))? {
+        println!("Aborted");
+        return Ok(());
+    }
+
+    if adb {
+        let session = ADBSession::new(target);
+        match action {
+            PowerAction::Reboot => session.reboot()?,
+            // adbd has no power-off service, so use the shell
+            PowerAction::Poweroff => {
+                session.execute_command("reboot -p").await?;
+            }
+        }
+        verbose::status(&format!("Sent {} to {}, waiting for it to go down...", action.verb(), target));
+
+        let started = Instant::now();
+        while started.elapsed() < DOWN_TIMEOUT {
+            if session.execute_command("true").await.is_err() {
+                return report_down(target, started);
+            }
+            tokio::time::sleep(PROBE_INTERVAL).await;
+        }
+        return Err(still_up(action, target));
+    }
+
+    let session = SSHSession::new_with_options(target, &ssh_options.cloned().unwrap_or_default()).await?;
+    session
+        .execute_command(PRIVILEGE_CHECK)
+        .await
+        .map_err(|_| anyhow::anyhow!("Can't {} {}: needs root or passwordless sudo", action.verb(), target))?;
+    // Only retried when it never started, so the board can't get the action twice
+    session.execute_command(action.ssh_command()).await?;
+    verbose::status(&format!("Sent {} to {}, waiting for it to go down...", action.verb(), target));
+
+    let resolved = ssh_resolver::resolve(target)?;
+    if resolved.proxy_jump.is_some() {
+        verbose::status("Behind a ProxyJump host, so going down can't be confirmed from here");
+        return Ok(());
+    }
+    let address = tokio::net::lookup_host((resolved.host.as_str(), resolved.port))
+        .await?
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("Could not resolve {}", resolved.host))?;
+
+    // The SSH port stops accepting connections once sshd is stopped
+    let started = Instant::now();
+    while started.elapsed() < DOWN_TIMEOUT {
+        tokio::time::sleep(PROBE_INTERVAL).await;
+        if !matches!(tokio::time::timeout(PROBE_INTERVAL, TcpStream::connect(address)).await, Ok(Ok(_))) {
+            return report_down(target, started);
+        }
+    }
+    Err(still_up(action, target))
+}
+
+fn report_down(target: &str, started: Instant) -> Result<()> {
+    println!("{} went down after {}s", target, started.elapsed().as_secs());
+    Ok(())
+}
+
+fn still_up(action: PowerAction, target: &str) -> anyhow::Error {
+    anyhow::anyhow!(
+        "{} is still reachable {}s after the {} request; it may have been ignored",
+        target,
+        DOWN_TIMEOUT.as_secs(),
+        action.verb()
+    )
+}
+
+fn confirm(question: &str) -> Result<bool> {
+    print!("{} [y/N] ", question);
+    std::io::stdout().flush()?;
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer)?;
+    Ok(matches!(answer.trim(), "y" | "Y" | "yes" | "YES"))
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
@@ -0,0 +1,76 @@
+use anyhow::Result;
+use crate::log_collector::LogSource;
+use crate::paths;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// Named boards for `--profile`, e.g.
+///
+/// ```toml
+/// [khadas]
+/// target = "root@192.168.1.4"
+/// port = 2222
+/// identity = "~/.ssh/khadas"
+/// refresh = 5
+/// log_source = "dmesg"
+///
+/// [tvbox]
+/// connection = "adb"
+/// target = "192.168.1.15:5555"
+/// ```
+const PROFILES_PATH: &str = "~/.config/sbctool/profiles.toml";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Connection {
+    #[default]
+    Ssh,
+    Adb,
+}
+
+/// Defaults for one board; flags given on the command line win over every field
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Profile {
+    /// user@host or ssh_config alias, or the adb serial
+    pub target: Option<String>,
+    #[serde(default)]
+    pub connection: Connection,
+    pub port: Option<u16>,
+    pub identity: Option<String>,
+    /// Seconds between TUI re-collections
+    pub refresh: Option<u64>,
+    pub log_source: Option<LogSource>,
+    /// collectors.toml-style file used instead of the default one
+    pub collectors: Option<String>,
+}
+
+impl Profile {
+    pub fn identity_path(&self) -> Option<PathBuf> {
+        self.identity.as_deref().map(paths::expand)
+    }
+
+    pub fn collectors_path(&self) -> Option<PathBuf> {
+        self.collectors.as_deref().map(paths::expand)
+    }
+}
+
+/// Looks up `name` in the profiles file; the error lists the profiles that do exist
+pub fn load(name: &str) -> Result<Profile> {
+    let path = paths::expand(PROFILES_PATH);
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| anyhow::anyhow!("Can't read profiles from {}: {}", path.display(), e))?;
+    let mut profiles: BTreeMap<String, Profile> =
+        toml::from_str(&content).map_err(|e| anyhow::anyhow!("{}: {}", path.display(), e))?;
+
+    profiles.remove(name).ok_or_else(|| {
+        let known: Vec<&str> = profiles.keys().map(String::as_str).collect();
+        anyhow::anyhow!(
+            "No profile '{}' in {} (profiles: {})",
+            name,
+            path.display(),
+            if known.is_empty() { "none".to_string() } else { known.join(", ") }
+        )
+    })
+}
@@ -0,0 +1,206 @@
+use anyhow::Result;
+use base64::Engine;
+use crate::error::SbcError;
+use std::io::{Read, Write};
+use std::net::{IpAddr, TcpStream};
+use std::time::Duration;
+
+/// Longest HTTP CONNECT response header accepted before giving up on the proxy
+const MAX_HTTP_RESPONSE: usize = 8192;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Kind {
+    /// SOCKS5 (RFC 1928); host names are always resolved by the proxy, as with socks5h
+    Socks5,
+    /// HTTP CONNECT
+    Http,
+}
+
+/// `--proxy` for the native SSH connection: socks5://[user:pass@]host:port or http://[user:pass@]host:port
+#[derive(Debug, Clone)]
+pub struct Proxy {
+    kind: Kind,
+    pub host: String,
+    pub port: u16,
+    credentials: Option<(String, String)>,
+}
+
+impl Proxy {
+    pub fn parse(url: &str) -> Result<Self> {
+        let invalid = || anyhow::anyhow!("Invalid --proxy {}: expected socks5://host:port or http://host:port", url);
+        let (scheme, rest) = url.split_once("://").ok_or_else(invalid)?;
+        let (kind, default_port) = match scheme.to_ascii_lowercase().as_str() {
+            "socks5" | "socks5h" => (Kind::Socks5, 1080),
+            "http" => (Kind::Http, 8080),
+            _ => return Err(invalid()),
+        };
+
+        let rest = rest.trim_end_matches('/');
+        let (credentials, address) = match rest.rsplit_once('@') {
+            Some((userinfo, address)) => {
+                let (user, password) = userinfo.split_once(':').unwrap_or((userinfo, ""));
+                (Some((user.to_string(), password.to_string())), address)
+            }
+            None => (None, rest),
+        };
+
+        // "[::1]:1080" keeps its brackets off the host; a missing port gets the scheme's usual one
+        let (host, port) = match address.rsplit_once(':') {
+            Some((host, port)) if !host.is_empty() && (!host.contains(':') || host.ends_with(']')) => {
+                (host, port.parse::<u16>().map_err(|_| invalid())?)
+            }
+            _ => (address, default_port),
+        };
+        let host = host.trim_start_matches('[').trim_end_matches(']');
+        if host.is_empty() {
+            return Err(invalid());
+        }
+        Ok(Self { kind, host: host.to_string(), port, credentials })
+    }
+
+    /// Asks the proxy behind `tcp` to connect on to `host:port`; afterwards `tcp` carries that
+    /// connection and can go to the SSH handshake like a direct one
+    pub fn tunnel(&self, tcp: &mut TcpStream, host: &str, port: u16, timeout: Duration) -> Result<()> {
+        tcp.set_read_timeout(Some(timeout))?;
+        tcp.set_write_timeout(Some(timeout))?;
+        let result = match self.kind {
+            Kind::Socks5 => self.socks5(tcp, host, port),
+            Kind::Http => self.http_connect(tcp, host, port),
+        };
+        result.map_err(|e| match e.downcast::<SbcError>() {
+            Ok(e) => e.into(),
+            Err(e) => SbcError::Unreachable {
+                host: host.to_string(),
+                port,
+                reason: format!("proxy {}:{}: {}", self.host, self.port, e),
+            }
+            .into(),
+        })
+    }
+
+    fn socks5(&self, tcp: &mut TcpStream, host: &str, port: u16) -> Result<()> {
+        // Greeting: offer no auth, plus username/password when the URL has credentials
+        let methods: &[u8] = if self.credentials.is_some() { &[0x00, 0x02] } else { &[0x00] };
+        let mut greeting = vec![0x05, methods.len() as u8];
+        greeting.extend_from_slice(methods);
+        tcp.write_all(&greeting)?;
+
+        let mut choice = [0u8; 2];
+        tcp.read_exact(&mut choice)?;
+        match (choice, &self.credentials) {
+            ([0x05, 0x00], _) => {}
+            ([0x05, 0x02], Some((user, password))) => {
+                if user.len() > 255 || password.len() > 255 {
+                    return Err(anyhow::anyhow!("SOCKS5 username and password are limited to 255 bytes"));
+                }
+                let mut request = vec![0x01, user.len() as u8];
+                request.extend_from_slice(user.as_bytes());
+                request.push(password.len() as u8);
+                request.extend_from_slice(password.as_bytes());
+                tcp.write_all(&request)?;
+
+                let mut status = [0u8; 2];
+                tcp.read_exact(&mut status)?;
+                if status[1] != 0x00 {
+                    return Err(anyhow::anyhow!("SOCKS5 username/password rejected"));
+                }
+            }
+            ([0x05, 0xff], _) => return Err(anyhow::anyhow!("SOCKS5 proxy accepts none of our auth methods")),
+            _ => return Err(anyhow::anyhow!("not a SOCKS5 proxy")),
+        }
+
+        // CONNECT by IP when given one, else by name so the proxy resolves it
+        let mut request = vec![0x05, 0x01, 0x00];
+        match host.parse::<IpAddr>() {
+            Ok(IpAddr::V4(ip)) => {
+                request.push(0x01);
+                request.extend_from_slice(&ip.octets());
+            }
+            Ok(IpAddr::V6(ip)) => {
+                request.push(0x04);
+                request.extend_from_slice(&ip.octets());
+            }
+            Err(_) => {
+                if host.len() > 255 {
+                    return Err(anyhow::anyhow!("host name too long for SOCKS5"));
+                }
+                request.push(0x03);
+                request.push(host.len() as u8);
+                request.extend_from_slice(host.as_bytes());
+            }
+        }
+        request.extend_from_slice(&port.to_be_bytes());
+        tcp.write_all(&request)?;
+
+        let mut reply = [0u8; 4];
+        tcp.read_exact(&mut reply)?;
+        if reply[1] != 0x00 {
+            return Err(match reply[1] {
+                // The proxy couldn't resolve the name, like a local lookup failing
+                0x04 if host.parse::<IpAddr>().is_err() => SbcError::NoTarget(format!("Host name {} did not resolve at the proxy", host)).into(),
+                code => anyhow::anyhow!(socks5_error(code)),
+            });
+        }
+
+        // Skip the bound address the proxy reports
+        let address_len = match reply[3] {
+            0x01 => 4,
+            0x04 => 16,
+            0x03 => {
+                let mut len = [0u8; 1];
+                tcp.read_exact(&mut len)?;
+                len[0] as usize
+            }
+            _ => return Err(anyhow::anyhow!("malformed SOCKS5 reply")),
+        };
+        let mut bound = vec![0u8; address_len + 2];
+        tcp.read_exact(&mut bound)?;
+        Ok(())
+    }
+
+    fn http_connect(&self, tcp: &mut TcpStream, host: &str, port: u16) -> Result<()> {
+        let authority = if host.contains(':') { format!("[{}]:{}", host, port) } else { format!("{}:{}", host, port) };
+        let mut request = format!("CONNECT {0} HTTP/1.1\r\nHost: {0}\r\n", authority);
+        if let Some((user, password)) = &self.credentials {
+            let token = base64::engine::general_purpose::STANDARD.encode(format!("{}:{}", user, password));
+            request.push_str(&format!("Proxy-Authorization: Basic {}\r\n", token));
+        }
+        request.push_str("\r\n");
+        tcp.write_all(request.as_bytes())?;
+
+        // Byte by byte, so nothing of the SSH banner that follows is read into the header
+        let mut response = Vec::new();
+        let mut byte = [0u8; 1];
+        while !response.ends_with(b"\r\n\r\n") {
+            if response.len() >= MAX_HTTP_RESPONSE {
+                return Err(anyhow::anyhow!("HTTP proxy response header too long"));
+            }
+            tcp.read_exact(&mut byte)?;
+            response.push(byte[0]);
+        }
+
+        // "HTTP/1.1 200 Connection established"
+        let response = String::from_utf8_lossy(&response);
+        let status_line = response.lines().next().unwrap_or("");
+        match status_line.split_whitespace().nth(1) {
+            Some("200") => Ok(()),
+            Some(_) => Err(anyhow::anyhow!("HTTP proxy answered {}", status_line.trim())),
+            None => Err(anyhow::anyhow!("not an HTTP proxy")),
+        }
+    }
+}
+
+fn socks5_error(code: u8) -> String {
+    let reason = match code {
+        0x01 => "general failure",
+        0x02 => "connection not allowed by ruleset",
+        0x03 => "network unreachable",
+        0x04 => "host unreachable",
+        0x05 => "connection refused",
+        0x06 => "TTL expired",
+        0x07 => "command not supported",
+        0x08 => "address type not supported",
+        _ => "unknown error",
+    };
+    format!("SOCKS5 proxy: {} (code {})", reason, code)
+}
@@ -0,0 +1,116 @@
+use crate::adb_session::{self, AdbRoute};
+use crate::ssh_resolver::ResolvedTarget;
+use std::path::Path;
+
+/// `ssh` line reaching the same host as sbctool, with `remote_command` appended when given
+pub fn ssh(resolved: &ResolvedTarget, identity: Option<&Path>, remote_command: Option<&str>) -> String {
+    let mut args = vec!["ssh".to_string()];
+    if resolved.port != 22 {
+        args.extend(["-p".to_string(), resolved.port.to_string()]);
+    }
+    args.extend(common_ssh_args(resolved, identity));
+    args.push(format!("{}@{}", resolved.user, resolved.host));
+    args.extend(remote_command.map(str::to_string));
+    join(&args)
+}
+
+/// `scp` line for a transfer; the side prefixed with ':' becomes `user@host:path`
+pub fn scp(resolved: &ResolvedTarget, identity: Option<&Path>, source: &str, dest: &str) -> String {
+    // scp wants IPv6 literals in brackets before the ':path'
+    let host = if resolved.host.contains(':') {
+        format!("[{}]", resolved.host)
+    } else {
+        resolved.host.clone()
+    };
+    let remote = |path: &str| match path.strip_prefix(':') {
+        Some(path) => format!("{}@{}:{}", resolved.user, host, path),
+        None => path.to_string(),
+    };
+
+    let mut args = vec!["scp".to_string(), "-p".to_string()];
+    if resolved.port != 22 {
+        args.extend(["-P".to_string(), resolved.port.to_string()]);
+    }
+    args.extend(common_ssh_args(resolved, identity));
+    args.extend([remote(source), remote(dest)]);
+    join(&args)
+}
+
+/// `adb` line for the device sbctool would pick, e.g. `adb -s <serial> shell`; direct TCP
+/// targets get the `adb connect` they need first
+pub fn adb(target: &str, adb_args: &[&str]) -> String {
+    let mut base = vec!["adb".to_string()];
+    if let Some(server) = adb_session::server_override() {
+        base.extend(["-H".to_string(), server.ip().to_string(), "-P".to_string(), server.port().to_string()]);
+    }
+
+    let mut args = base.clone();
+    let connect = match AdbRoute::for_target(target) {
+        // Like sbctool, plain adb uses the only attached device
+        AdbRoute::Auto => None,
+        AdbRoute::Tcp(address) => {
+            args.extend(["-s".to_string(), address.to_string()]);
+            let mut connect = base;
+            connect.extend(["connect".to_string(), address.to_string()]);
+            Some(join(&connect))
+        }
+        AdbRoute::Server => {
+            args.extend(["-s".to_string(), target.to_string()]);
+            None
+        }
+    };
+    args.extend(adb_args.iter().map(|arg| arg.to_string()));
+
+    match connect {
+        Some(connect) => format!("{} && {}", connect, join(&args)),
+        None => join(&args),
+    }
+}
+
+/// Shell prefix that enters `cwd` and exports `env` before a remote command, e.g.
+/// `cd /opt/app || exit 1; export MODE=test; `. A leading `~/` stays unquoted so the remote shell expands it.
+pub fn env_prefix(cwd: Option<&str>, env: &[(String, String)]) -> String {
+    let mut prefix = String::new();
+    if let Some(cwd) = cwd {
+        let dir = match cwd.strip_prefix("~/") {
+            Some("") => "~".to_string(),
+            Some(rest) => format!("~/{}", quote(rest)),
+            None if cwd == "~" => "~".to_string(),
+            None => quote(cwd),
+        };
+        prefix.push_str(&format!("cd {} || exit 1; ", dir));
+    }
+    if !env.is_empty() {
+        let assignments: Vec<String> = env.iter().map(|(key, value)| format!("{}={}", key, quote(value))).collect();
+        prefix.push_str(&format!("export {}; ", assignments.join(" ")));
+    }
+    prefix
+}
+
+fn common_ssh_args(resolved: &ResolvedTarget, identity: Option<&Path>) -> Vec<String> {
+    let mut args = Vec::new();
+    if let Some(identity) = identity {
+        args.extend(["-i".to_string(), identity.display().to_string()]);
+    }
+    if let Some(jump) = &resolved.proxy_jump {
+        args.extend(["-J".to_string(), jump.clone()]);
+    }
+    args
+}
+
+fn join(args: &[String]) -> String {
+    args.iter().map(|arg| quote(arg)).collect::<Vec<_>>().join(" ")
+}
+
+/// Single-quotes an argument for a POSIX shell unless it only has characters that are safe bare
+pub fn quote(arg: &str) -> String {
+    let safe = !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '@' | '%' | '+' | '=' | ':' | ',' | '.' | '/' | '_' | '-'));
+    if safe {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', "'\\''"))
+    }
+}
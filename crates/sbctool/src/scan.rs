@@ -0,0 +1,139 @@
+use anyhow::Result;
+use crate::adb_session::ADBSession;
+use futures::stream::{self, StreamExt};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, UdpSocket};
+use std::process::Command;
+use std::time::Duration;
+use tokio::net::TcpStream;
+
+/// Connection attempts in flight at once
+const MAX_CONCURRENT_PROBES: usize = 128;
+
+/// Smallest prefix accepted for `--subnet`, so a typo can't start a sweep of millions of hosts
+const MIN_PREFIX: u8 = 16;
+
+/// Lists ADB devices, then sweeps `subnet` (default: the local /24) for hosts answering on `ports`
+pub async fn run(subnet: Option<&str>, ports: &[u16], timeout: Duration) -> Result<()> {
+    println!("ADB devices:");
+    let devices = ADBSession::describe_devices();
+    if devices.is_empty() {
+        println!("  (none)");
+    }
+    for device in devices {
+        println!("  {}", device);
+    }
+
+    let (network, prefix) = match subnet {
+        Some(subnet) => parse_subnet(subnet)?,
+        None => (local_ipv4()?, 24),
+    };
+    let hosts = hosts_in(network, prefix);
+    println!(
+        "\nScanning {}/{} ({} hosts) on port(s) {}...",
+        network_address(network, prefix),
+        prefix,
+        hosts.len(),
+        ports.iter().map(|port| port.to_string()).collect::<Vec<_>>().join(",")
+    );
+
+    // Addresses are fed in as slots free up, so a /16 doesn't start all its probes at once
+    let probes = hosts.iter().flat_map(|&host| ports.iter().map(move |&port| (host, port)));
+    let mut answers = stream::iter(probes)
+        .map(|(host, port)| async move {
+            let address = SocketAddr::new(IpAddr::V4(host), port);
+            matches!(tokio::time::timeout(timeout, TcpStream::connect(address)).await, Ok(Ok(_))).then_some((host, port))
+        })
+        .buffer_unordered(MAX_CONCURRENT_PROBES);
+
+    let mut open: Vec<(Ipv4Addr, Vec<u16>)> = Vec::new();
+    while let Some(answer) = answers.next().await {
+        let Some((host, port)) = answer else { continue };
+        match open.iter_mut().find(|(existing, _)| *existing == host) {
+            Some((_, ports)) => ports.push(port),
+            None => open.push((host, vec![port])),
+        }
+    }
+
+    if open.is_empty() {
+        println!("No hosts answered");
+        return Ok(());
+    }
+    open.sort();
+    for (host, mut ports) in open {
+        ports.sort();
+        let services: Vec<String> = ports.iter().map(|port| format!("{} ({})", port, service_name(*port))).collect();
+        println!("  {:<15}  {}", host, services.join(", "));
+    }
+    Ok(())
+}
+
+fn service_name(port: u16) -> &'static str {
+    match port {
+        22 => "ssh",
+        5555 => "adb",
+        _ => "open",
+    }
+}
+
+/// Address of the interface that routes to the internet; connecting a UDP socket sends nothing.
+/// Without a default route (an isolated bench LAN) the first global address from `ip` is used
+fn local_ipv4() -> Result<Ipv4Addr> {
+    routed_ipv4().or_else(interface_ipv4).ok_or_else(|| {
+        anyhow::anyhow!("Could not determine the local IPv4 subnet; pass --subnet (e.g. 192.168.1.0/24)")
+    })
+}
+
+fn routed_ipv4() -> Option<Ipv4Addr> {
+    let socket = UdpSocket::bind("0.0.0.0:0").ok()?;
+    socket.connect("8.8.8.8:80").ok()?;
+    match socket.local_addr().ok()?.ip() {
+        IpAddr::V4(ip) if !ip.is_loopback() && !ip.is_unspecified() => Some(ip),
+        _ => None,
+    }
+}
+
+fn interface_ipv4() -> Option<Ipv4Addr> {
+    let output = Command::new("ip").args(["-o", "-4", "addr", "show", "scope", "global"]).output().ok()?;
+    parse_ip_addr(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// First address in `ip -o -4 addr` output: "2: eth0    inet 192.168.1.5/24 brd ... scope global eth0"
+fn parse_ip_addr(output: &str) -> Option<Ipv4Addr> {
+    output.lines().find_map(|line| {
+        let mut fields = line.split_whitespace();
+        fields.find(|field| *field == "inet")?;
+        let address: Ipv4Addr = fields.next()?.split('/').next()?.parse().ok()?;
+        (!address.is_loopback()).then_some(address)
+    })
+}
+
+/// "192.168.1.0/24"; a bare address is taken as its /24
+fn parse_subnet(subnet: &str) -> Result<(Ipv4Addr, u8)> {
+    let (address, prefix) = subnet.split_once('/').unwrap_or((subnet, "24"));
+    let address: Ipv4Addr = address
+        .parse()
+        .map_err(|_| anyhow::anyhow!("Invalid subnet address: {}", address))?;
+    let prefix: u8 = prefix
+        .parse()
+        .ok()
+        .filter(|prefix| (MIN_PREFIX..=32).contains(prefix))
+        .ok_or_else(|| anyhow::anyhow!("Subnet prefix must be between /{} and /32: {}", MIN_PREFIX, subnet))?;
+    Ok((address, prefix))
+}
+
+fn network_address(address: Ipv4Addr, prefix: u8) -> Ipv4Addr {
+    let mask = u32::MAX.checked_shl(32 - prefix as u32).unwrap_or(0);
+    Ipv4Addr::from(u32::from(address) & mask)
+}
+
+/// Usable host addresses, without the network and broadcast addresses (except for /31 and /32)
+fn hosts_in(address: Ipv4Addr, prefix: u8) -> Vec<Ipv4Addr> {
+    let network = u32::from(network_address(address, prefix));
+    let size = 1u64 << (32 - prefix as u32);
+    let (first, last) = if size <= 2 {
+        (network as u64, network as u64 + size - 1)
+    } else {
+        (network as u64 + 1, network as u64 + size - 2)
+    };
+    (first..=last).map(|host| Ipv4Addr::from(host as u32)).collect()
+}
@@ -0,0 +1,130 @@
+use anyhow::Result;
+use crate::log_collector::LogCollector;
+use crate::metrics;
+use crate::ssh_session::{SSHSession, SshOptions};
+use crate::system_info::SystemInfoCollector;
+use crate::tui::{LogEntry, SystemInfo};
+use crate::verbose;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tiny_http::{Header, Response, Server};
+
+/// What the collector task last produced, read by the HTTP handlers
+#[derive(Default)]
+struct Latest {
+    info: Option<SystemInfo>,
+    /// Error of the last collection, cleared by the next successful one
+    error: Option<String>,
+}
+
+/// Collects `target` every `interval` over one persistent session and serves the result on
+/// `listen` until the process is stopped:
+/// `/info` (SystemInfo JSON), `/logs` (recent log entries as JSON) and `/metrics` (Prometheus text)
+pub async fn run(
+    target: &str,
+    ssh_options: Option<&SshOptions>,
+    listen: &str,
+    interval: Duration,
+    log_lines: usize,
+    gpio: bool,
+    updates: bool,
+) -> Result<()> {
+    let (collector, log_collector) = match ssh_options {
+        Some(ssh_options) => {
+            verbose::status(&format!("Connecting to {} via SSH...", target));
+            let session = Arc::new(SSHSession::new_with_options(target, ssh_options).await?);
+            (
+                SystemInfoCollector::new_with_ssh_session("ssh", target, session.clone()),
+                LogCollector::new_with_ssh_session("ssh", target, false, session),
+            )
+        }
+        None => (SystemInfoCollector::new("adb", target), LogCollector::new("adb", target, true)),
+    };
+    let collector = collector.with_gpio(gpio).with_updates(updates);
+    let log_collector = log_collector.with_log_lines(log_lines);
+
+    let server = Server::http(listen).map_err(|e| anyhow::anyhow!("Can't listen on {}: {}", listen, e))?;
+    verbose::status(&format!("Serving {} on http://{} (/info, /logs, /metrics)", target, listen));
+
+    let latest = Arc::new(Mutex::new(Latest::default()));
+    let logs = Arc::new(Mutex::new(Vec::new()));
+
+    let latest_clone = latest.clone();
+    tokio::spawn(async move {
+        loop {
+            let result = collector.collect_system_info().await;
+            if let Ok(mut latest) = latest_clone.lock() {
+                match result {
+                    Ok(info) => *latest = Latest { info: Some(info), error: None },
+                    Err(e) => latest.error = Some(e.to_string()),
+                }
+            }
+            tokio::time::sleep(interval).await;
+        }
+    });
+
+    let log_sender = logs.clone();
+    tokio::spawn(async move {
+        log_collector.start_log_collection(log_sender).await;
+    });
+
+    // tiny_http blocks on accept, so the handlers get a thread of their own
+    tokio::task::spawn_blocking(move || {
+        for request in server.incoming_requests() {
+            let response = respond(request.url(), &latest, &logs);
+            let _ = request.respond(response);
+        }
+    })
+    .await?;
+    Ok(())
+}
+
+fn respond(url: &str, latest: &Mutex<Latest>, logs: &Mutex<Vec<LogEntry>>) -> Response<std::io::Cursor<Vec<u8>>> {
+    let path = url.split('?').next().unwrap_or(url);
+    let Ok(latest) = latest.lock() else {
+        return text(500, "state unavailable\n");
+    };
+
+    match (path, &latest.info) {
+        ("/info", Some(info)) => json(200, &serde_json::to_value(info).unwrap_or_default()),
+        ("/metrics", Some(info)) => {
+            let up = u8::from(latest.error.is_none());
+            let body = format!(
+                "# HELP sbctool_up Whether the last collection succeeded.\n# TYPE sbctool_up gauge\nsbctool_up{{hostname=\"{}\"}} {}\n{}",
+                metrics::escape(&info.hostname),
+                up,
+                metrics::render(info)
+            );
+            with_type(200, "text/plain; version=0.0.4", body)
+        }
+        ("/info" | "/metrics", None) => {
+            let error = latest.error.as_deref().unwrap_or("first collection still running");
+            json(503, &serde_json::json!({ "error": error }))
+        }
+        ("/logs", _) => {
+            let entries: Vec<serde_json::Value> = logs
+                .lock()
+                .map(|logs| {
+                    logs.iter()
+                        .map(|entry| serde_json::json!({ "timestamp": entry.timestamp, "level": entry.level, "message": entry.message }))
+                        .collect()
+                })
+                .unwrap_or_default();
+            json(200, &serde_json::Value::Array(entries))
+        }
+        _ => text(404, "not found; try /info, /logs or /metrics\n"),
+    }
+}
+
+fn json(status: u16, value: &serde_json::Value) -> Response<std::io::Cursor<Vec<u8>>> {
+    with_type(status, "application/json", value.to_string())
+}
+
+fn text(status: u16, body: &str) -> Response<std::io::Cursor<Vec<u8>>> {
+    with_type(status, "text/plain; charset=utf-8", body.to_string())
+}
+
+fn with_type(status: u16, content_type: &str, body: String) -> Response<std::io::Cursor<Vec<u8>>> {
+    let header = Header::from_bytes(&b"Content-Type"[..], content_type.as_bytes()).expect("static header is valid");
+    Response::from_data(body.into_bytes()).with_status_code(status).with_header(header)
+}
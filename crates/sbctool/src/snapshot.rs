@@ -0,0 +1,126 @@
+use anyhow::Result;
+use crate::color;
+use crate::tui::SystemInfo;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+
+/// File from `--snapshot`, appended to after every successful collection
+static SNAPSHOT_FILE: OnceLock<Mutex<File>> = OnceLock::new();
+
+/// Fields that change on every collection and would drown the interesting differences
+pub const VOLATILE_FIELDS: [&str; 2] = ["cpu_freqs", "top_processes"];
+
+pub fn init(path: &Path) -> Result<()> {
+    let file = OpenOptions::new().create(true).append(true).open(path)?;
+    SNAPSHOT_FILE
+        .set(Mutex::new(file))
+        .map_err(|_| anyhow::anyhow!("Snapshots are already initialized"))
+}
+
+/// Appends `{"timestamp", "target", "info"}` as one JSON line, a no-op unless `init` was called
+pub fn record(target: &str, info: &SystemInfo) {
+    let Some(file) = SNAPSHOT_FILE.get() else { return };
+    let line = serde_json::json!({
+        "timestamp": chrono::Local::now().to_rfc3339(),
+        "target": target,
+        "info": info,
+    });
+    if let Ok(mut file) = file.lock() {
+        let _ = writeln!(file, "{}", line);
+    }
+}
+
+/// Prints what changed between the last two snapshots of `target` (default: the target of the
+/// newest snapshot). `all` also compares the fields in `VOLATILE_FIELDS`.
+pub fn diff(path: &Path, target: Option<&str>, all: bool) -> Result<()> {
+    let file = File::open(path).map_err(|e| anyhow::anyhow!("Can't read snapshots from {}: {}", path.display(), e))?;
+    let mut snapshots = Vec::new();
+    for (number, line) in BufReader::new(file).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let snapshot: Value = serde_json::from_str(&line)
+            .map_err(|e| anyhow::anyhow!("{}:{}: not a snapshot: {}", path.display(), number + 1, e))?;
+        snapshots.push(snapshot);
+    }
+
+    let target = match target {
+        Some(target) => target.to_string(),
+        None => snapshots
+            .last()
+            .and_then(|snapshot| snapshot["target"].as_str())
+            .ok_or_else(|| anyhow::anyhow!("No snapshots in {}", path.display()))?
+            .to_string(),
+    };
+    let of_target: Vec<&Value> = snapshots.iter().filter(|snapshot| snapshot["target"] == target.as_str()).collect();
+    let [.., before, after] = of_target.as_slice() else {
+        return Err(anyhow::anyhow!("Need two snapshots of {} to compare, found {}", target, of_target.len()));
+    };
+
+    let mut old = BTreeMap::new();
+    let mut new = BTreeMap::new();
+    flatten(&before["info"], "", &mut old);
+    flatten(&after["info"], "", &mut new);
+    if !all {
+        let volatile = |key: &String| VOLATILE_FIELDS.iter().any(|field| key.starts_with(field));
+        old.retain(|key, _| !volatile(key));
+        new.retain(|key, _| !volatile(key));
+    }
+
+    println!(
+        "{} {} -> {}",
+        color::bold(&target),
+        before["timestamp"].as_str().unwrap_or("?"),
+        after["timestamp"].as_str().unwrap_or("?")
+    );
+    let mut changes = 0;
+    for (key, old_value) in &old {
+        match new.get(key) {
+            Some(new_value) if new_value == old_value => continue,
+            Some(new_value) => println!("  ~ {}: {} -> {}", color::cyan(key), old_value, new_value),
+            None => println!("  - {}: {}", color::cyan(key), old_value),
+        }
+        changes += 1;
+    }
+    for (key, new_value) in new.iter().filter(|(key, _)| !old.contains_key(*key)) {
+        println!("  + {}: {}", color::cyan(key), new_value);
+        changes += 1;
+    }
+    if changes == 0 {
+        println!("  no changes");
+    }
+    Ok(())
+}
+
+/// Flattens nested objects and arrays into "path: value" pairs. Array items with a `name`
+/// (modules, failed units, interfaces, ...) are keyed by it, so a new entry doesn't shift the rest.
+pub fn flatten(value: &Value, path: &str, out: &mut BTreeMap<String, String>) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                let path = if path.is_empty() { key.clone() } else { format!("{}.{}", path, key) };
+                flatten(value, &path, out);
+            }
+        }
+        Value::Array(items) => {
+            for (index, item) in items.iter().enumerate() {
+                let key = match item.get("name").and_then(Value::as_str) {
+                    Some(name) => name.to_string(),
+                    None => index.to_string(),
+                };
+                flatten(item, &format!("{}[{}]", path, key), out);
+            }
+        }
+        Value::String(text) => {
+            out.insert(path.to_string(), text.clone());
+        }
+        other => {
+            out.insert(path.to_string(), other.to_string());
+        }
+    }
+}
@@ -0,0 +1,76 @@
+/// One known SoC, keyed by the device-tree compatible string the kernel exposes
+pub struct SocEntry {
+    pub compatible: &'static str,
+    pub name: &'static str,
+    pub cores: &'static str,
+}
+
+/// Known SBC SoCs. Add new boards by appending a line; the compatible string must
+/// match an entry of `/proc/device-tree/compatible` exactly.
+pub static SOCS: &[SocEntry] = &[
+    // Rockchip
+    SocEntry { compatible: "rockchip,rk3588", name: "Rockchip RK3588", cores: "4x A76 + 4x A55" },
+    SocEntry { compatible: "rockchip,rk3588s", name: "Rockchip RK3588S", cores: "4x A76 + 4x A55" },
+    SocEntry { compatible: "rockchip,rk3576", name: "Rockchip RK3576", cores: "4x A72 + 4x A53" },
+    SocEntry { compatible: "rockchip,rk3568", name: "Rockchip RK3568", cores: "4x A55" },
+    SocEntry { compatible: "rockchip,rk3566", name: "Rockchip RK3566", cores: "4x A55" },
+    SocEntry { compatible: "rockchip,rk3399", name: "Rockchip RK3399", cores: "2x A72 + 4x A53" },
+    SocEntry { compatible: "rockchip,rk3328", name: "Rockchip RK3328", cores: "4x A53" },
+    SocEntry { compatible: "rockchip,rk3308", name: "Rockchip RK3308", cores: "4x A35" },
+    SocEntry { compatible: "rockchip,rk3288", name: "Rockchip RK3288", cores: "4x A17" },
+    // Amlogic
+    SocEntry { compatible: "amlogic,a311d", name: "Amlogic A311D", cores: "4x A73 + 2x A53" },
+    SocEntry { compatible: "amlogic,s922x", name: "Amlogic S922X", cores: "4x A73 + 2x A53" },
+    SocEntry { compatible: "amlogic,g12b", name: "Amlogic G12B (S922X/A311D)", cores: "4x A73 + 2x A53" },
+    SocEntry { compatible: "amlogic,sm1", name: "Amlogic S905X3", cores: "4x A55" },
+    SocEntry { compatible: "amlogic,g12a", name: "Amlogic S905X2", cores: "4x A53" },
+    SocEntry { compatible: "amlogic,meson-gxm", name: "Amlogic S912", cores: "8x A53" },
+    SocEntry { compatible: "amlogic,meson-gxl", name: "Amlogic S905X", cores: "4x A53" },
+    SocEntry { compatible: "amlogic,meson-gxbb", name: "Amlogic S905", cores: "4x A53" },
+    SocEntry { compatible: "amlogic,meson-axg", name: "Amlogic A113D", cores: "4x A53" },
+    // Allwinner
+    SocEntry { compatible: "allwinner,sun50i-h618", name: "Allwinner H618", cores: "4x A53" },
+    SocEntry { compatible: "allwinner,sun50i-h616", name: "Allwinner H616", cores: "4x A53" },
+    SocEntry { compatible: "allwinner,sun50i-h6", name: "Allwinner H6", cores: "4x A53" },
+    SocEntry { compatible: "allwinner,sun50i-h5", name: "Allwinner H5", cores: "4x A53" },
+    SocEntry { compatible: "allwinner,sun50i-a64", name: "Allwinner A64", cores: "4x A53" },
+    SocEntry { compatible: "allwinner,sun8i-h3", name: "Allwinner H3", cores: "4x A7" },
+    SocEntry { compatible: "allwinner,sun7i-a20", name: "Allwinner A20", cores: "2x A7" },
+    SocEntry { compatible: "allwinner,sun20i-d1", name: "Allwinner D1", cores: "1x C906 (RISC-V)" },
+    // Broadcom (Raspberry Pi)
+    SocEntry { compatible: "brcm,bcm2712", name: "Broadcom BCM2712", cores: "4x A76" },
+    SocEntry { compatible: "brcm,bcm2711", name: "Broadcom BCM2711", cores: "4x A72" },
+    SocEntry { compatible: "brcm,bcm2837", name: "Broadcom BCM2837", cores: "4x A53" },
+    SocEntry { compatible: "brcm,bcm2836", name: "Broadcom BCM2836", cores: "4x A7" },
+    SocEntry { compatible: "brcm,bcm2835", name: "Broadcom BCM2835", cores: "1x ARM1176" },
+    // Nvidia
+    SocEntry { compatible: "nvidia,tegra234", name: "Nvidia Tegra234 (Orin)", cores: "up to 12x A78AE" },
+    SocEntry { compatible: "nvidia,tegra194", name: "Nvidia Tegra194 (Xavier)", cores: "8x Carmel" },
+    SocEntry { compatible: "nvidia,tegra210", name: "Nvidia Tegra210 (X1)", cores: "4x A57" },
+    // Qualcomm
+    SocEntry { compatible: "qcom,qcs6490", name: "Qualcomm QCS6490", cores: "1x A78 + 3x A78 + 4x A55" },
+    SocEntry { compatible: "qcom,qrb5165", name: "Qualcomm QRB5165", cores: "1x A77 + 3x A77 + 4x A55" },
+    SocEntry { compatible: "qcom,apq8016", name: "Qualcomm APQ8016", cores: "4x A53" },
+    // NXP
+    SocEntry { compatible: "fsl,imx8mp", name: "NXP i.MX 8M Plus", cores: "4x A53" },
+    SocEntry { compatible: "fsl,imx8mm", name: "NXP i.MX 8M Mini", cores: "4x A53" },
+    SocEntry { compatible: "fsl,imx8mq", name: "NXP i.MX 8M", cores: "4x A53" },
+    SocEntry { compatible: "fsl,imx6q", name: "NXP i.MX 6Quad", cores: "4x A9" },
+    // Others
+    SocEntry { compatible: "samsung,exynos5422", name: "Samsung Exynos 5422", cores: "4x A15 + 4x A7" },
+    SocEntry { compatible: "ti,am625", name: "TI AM625", cores: "4x A53" },
+    SocEntry { compatible: "mediatek,mt8195", name: "MediaTek MT8195", cores: "4x A78 + 4x A55" },
+    SocEntry { compatible: "starfive,jh7110", name: "StarFive JH7110", cores: "4x U74 (RISC-V)" },
+    SocEntry { compatible: "thead,th1520", name: "T-Head TH1520", cores: "4x C910 (RISC-V)" },
+];
+
+/// Looks up a raw `/proc/device-tree/compatible` value (NUL or whitespace separated).
+/// Entries are checked in the order the device tree lists them, so a board-specific
+/// match wins over the generic SoC one.
+pub fn lookup(compatible: &str) -> Option<String> {
+    compatible
+        .split(|c: char| c == '\0' || c.is_whitespace())
+        .filter(|entry| !entry.is_empty())
+        .find_map(|entry| SOCS.iter().find(|soc| soc.compatible == entry))
+        .map(|soc| format!("{} ({})", soc.name, soc.cores))
+}
@@ -0,0 +1,142 @@
+use crate::error::SbcError;
+use crate::paths;
+use anyhow::Result;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::{Mutex, OnceLock};
+
+/// Connection details resolved from the target and `ssh -G`
+#[derive(Debug, Clone)]
+pub struct ResolvedTarget {
+    pub user: String,
+    pub host: String,
+    pub port: u16,
+    pub identity_files: Vec<PathBuf>,
+    pub proxy_jump: Option<String>,
+}
+
+/// `--control-path`: socket of an already running ssh ControlMaster, used for every target
+static CONTROL_PATH: OnceLock<String> = OnceLock::new();
+
+/// `--auth` / `--identity` as far as the external `ssh` binary can honor them (it runs in BatchMode, so no password)
+static EXTERNAL_AUTH: OnceLock<ExternalAuth> = OnceLock::new();
+
+struct ExternalAuth {
+    agent: bool,
+    key: bool,
+    identity: Option<PathBuf>,
+}
+
+/// Successful resolutions keyed by the target as given on the command line
+static CACHE: OnceLock<Mutex<HashMap<String, ResolvedTarget>>> = OnceLock::new();
+
+/// Resolves a `user@host` or ssh_config alias, running `ssh -G` at most once per target.
+/// Failures are not cached so a later call can retry.
+pub fn resolve(target: &str) -> Result<ResolvedTarget> {
+    let cache = CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+    if let Some(resolved) = cache.lock().ok().and_then(|cache| cache.get(target).cloned()) {
+        return Ok(resolved);
+    }
+
+    let resolved = resolve_uncached(target)?;
+    if let Ok(mut cache) = cache.lock() {
+        cache.insert(target.to_string(), resolved.clone());
+    }
+    Ok(resolved)
+}
+
+/// Replaces the port for `target` (e.g. from `--port`) so every later `resolve` agrees on it
+pub fn override_port(target: &str, port: u16) -> Result<()> {
+    let mut resolved = resolve(target)?;
+    resolved.port = port;
+
+    let cache = CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+    if let Ok(mut cache) = cache.lock() {
+        cache.insert(target.to_string(), resolved);
+    }
+    Ok(())
+}
+
+pub fn set_control_path(path: &str) {
+    let _ = CONTROL_PATH.set(path.to_string());
+}
+
+pub fn set_external_auth(agent: bool, key: bool, identity: Option<PathBuf>) {
+    let _ = EXTERNAL_AUTH.set(ExternalAuth { agent, key, identity });
+}
+
+/// Extra options for the external `ssh`: ride an existing ControlMaster instead of opening
+/// a new connection (ssh falls back to connecting itself when the socket doesn't exist),
+/// and restrict authentication to what `--auth` / `--identity` allow.
+pub fn external_args() -> Vec<String> {
+    let mut args = Vec::new();
+    if let Some(path) = CONTROL_PATH.get() {
+        args.extend(["-o".to_string(), format!("ControlPath={}", path), "-o".to_string(), "ControlMaster=no".to_string()]);
+    }
+
+    if let Some(auth) = EXTERNAL_AUTH.get() {
+        if !auth.agent {
+            args.extend(["-o".to_string(), "IdentityAgent=none".to_string()]);
+        }
+        // IdentityFile accumulates across ssh_config and -o, so the config's key files can only be
+        // dropped by turning off the publickey method. Agent keys need that method too, so with
+        // the agent allowed ssh still offers the files after them.
+        if !auth.key && !auth.agent {
+            args.extend(["-o".to_string(), "PubkeyAuthentication=no".to_string()]);
+        }
+        if let Some(identity) = auth.identity.as_ref().filter(|_| auth.key) {
+            args.extend(["-i".to_string(), identity.display().to_string(), "-o".to_string(), "IdentitiesOnly=yes".to_string()]);
+        }
+    }
+    args
+}
+
+fn resolve_uncached(target: &str) -> Result<ResolvedTarget> {
+    let (explicit_user, alias) = match target.split_once('@') {
+        Some((user, host)) => (Some(user.to_string()), host),
+        None => (None, target),
+    };
+
+    let mut resolved_user = None;
+    let mut resolved = ResolvedTarget {
+        user: String::new(),
+        host: alias.to_string(),
+        port: 22,
+        identity_files: Vec::new(),
+        proxy_jump: None,
+    };
+
+    // Resolve alias, port, keys and jump host using ssh -G
+    match Command::new("ssh").arg("-G").arg(target).output() {
+        Ok(output) if output.status.success() => {
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            for line in stdout.lines() {
+                if let Some(rest) = line.strip_prefix("hostname ") {
+                    resolved.host = rest.trim().to_string();
+                } else if let Some(rest) = line.strip_prefix("user ") {
+                    resolved_user = Some(rest.trim().to_string());
+                } else if let Some(rest) = line.strip_prefix("port ") {
+                    resolved.port = rest.trim().parse().unwrap_or(22);
+                } else if let Some(rest) = line.strip_prefix("identityfile ") {
+                    resolved.identity_files.push(paths::expand(rest.trim()));
+                } else if let Some(rest) = line.strip_prefix("proxyjump ") {
+                    let jump = rest.trim();
+                    if !jump.is_empty() && jump != "none" {
+                        resolved.proxy_jump = Some(jump.to_string());
+                    }
+                }
+            }
+        }
+        // An explicit user@host still works without ssh config
+        _ if explicit_user.is_some() => {}
+        Ok(_) => return Err(SbcError::NoTarget(format!("Failed to resolve SSH alias: {}", target)).into()),
+        Err(_) => return Err(anyhow::anyhow!("SSH command not available")),
+    }
+
+    resolved.user = explicit_user.or(resolved_user).unwrap_or_else(|| {
+        std::env::var("USER").unwrap_or_else(|_| "root".to_string())
+    });
+
+    Ok(resolved)
+}
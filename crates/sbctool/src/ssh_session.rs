@@ -1,112 +1,991 @@
 use anyhow::Result;
-use ssh2::Session;
-use std::net::TcpStream;
-use std::sync::Arc;
+use crate::error::SbcError;
+use crate::interactive;
+use crate::known_hosts;
+use crate::paths;
+use crate::proxy::Proxy;
+use crate::raw_command;
+use crate::ssh_resolver::{self, ResolvedTarget};
+use crate::timing;
+use crate::verbose;
+use ssh2::{Channel, ExtendedData, Session};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use crate::tui::LogEntry;
+use std::sync::{Arc, OnceLock, Weak};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
 use tokio::sync::Mutex;
 use std::time::Duration;
-use std::io::Read;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+/// Default private keys in order of preference, as OpenSSH names them
+const DEFAULT_KEY_NAMES: [&str; 3] = ["id_ed25519", "id_ecdsa", "id_rsa"];
+
+/// Stands in for the output of a batched command that failed, followed by the error
+pub const FAILED_COMMAND_PREFIX: &str = "Error: ";
+
+/// Follows the journal as JSON so the real syslog priority is available; the history length is appended
+const LOG_STREAM_COMMAND: &str = "journalctl -f -o json -n";
+
+/// journalctl only notices a closed channel on its next write, which can be minutes away on a quiet
+/// board; this watcher kills it as soon as our end sends EOF. The watcher writes to /dev/null, so
+/// journalctl exiting on its own still ends the stream.
+const LOG_STREAM_WATCHER: &str = "pid=$!; (cat <&3 >/dev/null; kill $pid 2>/dev/null) >/dev/null 2>&1 & wait $pid";
+
+/// First reconnect delay, doubled after every failed attempt
+const RECONNECT_BASE_DELAY: Duration = Duration::from_secs(1);
+
+/// Upper bound for the reconnect delay
+const RECONNECT_MAX_DELAY: Duration = Duration::from_secs(30);
+
+/// Upper bound for the delay between initial connect attempts, so `--wait` notices a booted board quickly
+const CONNECT_RETRY_MAX_DELAY: Duration = Duration::from_secs(5);
+
+/// Extra connect attempts when `--connect-retries` isn't given
+pub const DEFAULT_CONNECT_RETRIES: u32 = 2;
+
+/// Keepalive interval when `--keepalive` isn't given; short enough for typical NAT idle timeouts
+pub const DEFAULT_KEEPALIVE_SECS: u64 = 30;
+
+/// One way of authenticating, tried in the order given to `--auth`
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq)]
+pub enum AuthMethod {
+    Agent,
+    Key,
+    Password,
+}
+
+/// Order used when `--auth` isn't given
+pub const DEFAULT_AUTH_METHODS: [AuthMethod; 3] = [AuthMethod::Agent, AuthMethod::Key, AuthMethod::Password];
+
+/// Connection options for the native ssh2 session
+#[derive(Debug, Clone)]
+pub struct SshOptions {
+    /// Password for the `password` auth method
+    pub password: Option<String>,
+    /// TCP connect timeout
+    pub timeout: Duration,
+    /// Read / write timeout for the session, i.e. how long a silent command may block
+    pub command_timeout: Duration,
+    /// Interval of SSH keepalive messages while the session is idle; zero disables them
+    pub keepalive: Duration,
+    /// Extra attempts at the TCP connect + handshake when the first one fails
+    pub connect_retries: u32,
+    /// Delay before the first retry, doubled after each one
+    pub connect_backoff: Duration,
+    /// From `--wait`: keep retrying the connect until this much time has passed, instead of `connect_retries` times
+    pub wait: Option<Duration>,
+    /// Port from `--port`, taking precedence over ssh config
+    pub port: Option<u16>,
+    /// Methods to try, in order
+    pub auth_methods: Vec<AuthMethod>,
+    /// Key from `--identity`, used instead of the ssh config / default keys
+    pub identity: Option<PathBuf>,
+    /// Variables from `--env` for every remote command
+    pub env: Vec<(String, String)>,
+    /// Remote working directory from `--cwd`
+    pub cwd: Option<String>,
+    /// Passphrase for encrypted keys, from $SBCTOOL_KEY_PASSPHRASE or a prompt
+    pub key_passphrase: Option<String>,
+    /// SOCKS5 / HTTP proxy from `--proxy` for the TCP connection (to the jump host, if any)
+    pub proxy: Option<Proxy>,
+}
+
+impl Default for SshOptions {
+    fn default() -> Self {
+        Self {
+            password: None,
+            timeout: Duration::from_secs(10),
+            command_timeout: Duration::from_secs(10),
+            keepalive: Duration::from_secs(DEFAULT_KEEPALIVE_SECS),
+            connect_retries: DEFAULT_CONNECT_RETRIES,
+            connect_backoff: RECONNECT_BASE_DELAY,
+            wait: None,
+            port: None,
+            auth_methods: DEFAULT_AUTH_METHODS.to_vec(),
+            identity: None,
+            env: Vec::new(),
+            cwd: None,
+            key_passphrase: None,
+            proxy: None,
+        }
+    }
+}
+
+/// Everything a remote command produced
+#[derive(Debug, Clone)]
+pub struct CommandOutput {
+    pub stdout: String,
+    pub stderr: String,
+    pub exit_status: i32,
+}
+
+/// An authenticated session and what the caller should report about it
+struct Connected {
+    session: Session,
+    /// Auth method that succeeded, e.g. "ssh-agent"
+    method: String,
+    /// Host key changes for the target (and the jump host, if any)
+    warnings: Vec<String>,
+}
 
 pub struct SSHSession {
     session: Arc<Mutex<Session>>,
     target: String,
+    options: SshOptions,
+    timeout_ms: u32,
+    /// Bumped on every successful reconnect so concurrent callers only reconnect once
+    generation: AtomicU64,
+    /// Failed reconnects in a row, drives the backoff delay
+    reconnect_failures: AtomicU32,
+    /// Where reconnect attempts are reported; stderr when unset
+    log_sender: OnceLock<Arc<std::sync::Mutex<Vec<LogEntry>>>>,
+    /// Set by the keepalive task when the server stopped answering; the next command reconnects first
+    keepalive_failed: Arc<AtomicBool>,
+    /// The server refused an `env` request, so `--env` is exported by the shell from then on
+    setenv_rejected: AtomicBool,
 }
 
 impl SSHSession {
     pub async fn new(target: &str) -> Result<Self> {
-        let (user, host) = Self::parse_target(target).await?;
-        println!("SSH Session: Connecting to {}@{}", user, host);
-        
-        // Connect to the remote host
-        let tcp = TcpStream::connect(&host)?;
-        tcp.set_read_timeout(Some(Duration::from_secs(10)))?;
-        tcp.set_write_timeout(Some(Duration::from_secs(10)))?;
-        
-        // Create SSH session
-        let mut sess = Session::new()?;
-        sess.set_tcp_stream(tcp);
-        sess.handshake()?;
+        Self::new_with_options(target, &SshOptions::default()).await
+    }
+
+    pub async fn new_with_options(target: &str, options: &SshOptions) -> Result<Self> {
+        // Recorded in the resolver so external ssh fallbacks and reconnects use it too
+        if let Some(port) = options.port {
+            ssh_resolver::override_port(target, port)?;
+        }
+        let resolved = ssh_resolver::resolve(target)?;
+        verbose::status(&format!("SSH Session: Connecting to {}@{}:{}", resolved.user, resolved.host, resolved.port));
+        if let Some(jump) = &resolved.proxy_jump {
+            verbose::status(&format!("SSH Session: Using ProxyJump {}", jump));
+        }
+        if let Some(proxy) = &options.proxy {
+            verbose::status(&format!("SSH Session: Using proxy {}:{}", proxy.host, proxy.port));
+        }
         
-        // Authenticate (try public key first, then password)
-        // For now, we'll use a simple approach - in production you'd want proper key handling
-        if sess.userauth_agent(&user).is_err() {
-            // If agent auth fails, try password (this is a simplified approach)
-            // In production, you might want to handle this more gracefully
-            return Err(anyhow::anyhow!("SSH authentication failed"));
+        let connected = Self::connect_with_retry(&resolved, options).await?;
+        for warning in &connected.warnings {
+            eprintln!("WARNING: {}", warning);
         }
+        verbose::status(&format!("Authenticated {}@{} with {}", resolved.user, resolved.host, connected.method));
         
+        let session = Arc::new(Mutex::new(connected.session));
+        let keepalive_failed = Arc::new(AtomicBool::new(false));
+        if !options.keepalive.is_zero() {
+            Self::spawn_keepalive(Arc::downgrade(&session), options.keepalive, keepalive_failed.clone());
+        }
+
         Ok(SSHSession {
-            session: Arc::new(Mutex::new(sess)),
+            session,
             target: target.to_string(),
+            options: options.clone(),
+            timeout_ms: options.command_timeout.as_millis().min(u32::MAX as u128) as u32,
+            generation: AtomicU64::new(0),
+            reconnect_failures: AtomicU32::new(0),
+            log_sender: OnceLock::new(),
+            keepalive_failed,
+            setenv_rejected: AtomicBool::new(false),
         })
     }
-    
-    async fn parse_target(target: &str) -> Result<(String, String)> {
-        if let Some((user, host)) = target.split_once('@') {
-            Ok((user.to_string(), host.to_string()))
-        } else {
-            // Try to resolve alias using ssh -G
-            use std::process::Command;
-            
-            if let Ok(output) = Command::new("ssh").arg("-G").arg(target).output() {
-                if output.status.success() {
-                    let stdout = String::from_utf8_lossy(&output.stdout);
-                    let mut resolved_host = None;
-                    let mut resolved_user = None;
-                    
-                    for line in stdout.lines() {
-                        if let Some(rest) = line.strip_prefix("hostname ") {
-                            resolved_host = Some(rest.trim().to_string());
-                        } else if let Some(rest) = line.strip_prefix("user ") {
-                            resolved_user = Some(rest.trim().to_string());
+
+    /// libssh2 only sends keepalives when asked to, so ask on a timer until the session is dropped.
+    /// libssh2 doesn't wait for the keepalive's reply, so each round also opens and closes a
+    /// channel, which needs the server to answer within the session timeout. No answer marks the
+    /// connection dead instead of letting the next command hang on it.
+    fn spawn_keepalive(session: Weak<Mutex<Session>>, interval: Duration, failed: Arc<AtomicBool>) {
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(interval).await;
+                let Some(session) = session.upgrade() else { break };
+                let answered = tokio::task::spawn_blocking(move || {
+                    let session = session.blocking_lock();
+                    session.keepalive_send().is_ok()
+                        && match session.channel_session() {
+                            Ok(mut channel) => channel.close().is_ok(),
+                            // LIBSSH2_ERROR_CHANNEL_FAILURE: refused, but the server did answer
+                            Err(e) => e.code() == ssh2::ErrorCode::Session(-21),
+                        }
+                })
+                .await
+                .unwrap_or(false);
+                if !answered {
+                    failed.store(true, Ordering::Relaxed);
+                }
+            }
+        });
+    }
+
+    /// False once a keepalive went unanswered, until the next command reconnects
+    pub fn is_alive(&self) -> bool {
+        !self.keepalive_failed.load(Ordering::Relaxed)
+    }
+
+    /// Reports reconnect attempts to the TUI logs panel instead of stderr
+    pub fn set_log_sender(&self, log_sender: Arc<std::sync::Mutex<Vec<LogEntry>>>) {
+        let _ = self.log_sender.set(log_sender);
+    }
+
+    /// Connects to the remote host, tunnelling through the jump host when configured,
+    /// and returns the authenticated session with the auth method used
+    async fn connect(resolved: &ResolvedTarget, options: &SshOptions) -> Result<Connected> {
+        let (sess, jump_warnings) = Self::connect_transport(resolved, options).await?;
+        let mut connected = Self::login(sess, resolved, options)?;
+        connected.warnings.extend(jump_warnings);
+        Ok(connected)
+    }
+
+    /// Like `connect`, but retries the TCP connect + handshake of a board that is still booting
+    /// or whose sshd is too busy to answer: `connect_retries` times with a doubling delay, or until
+    /// the `wait` deadline. Authentication and host key checks run once, after the handshake.
+    async fn connect_with_retry(resolved: &ResolvedTarget, options: &SshOptions) -> Result<Connected> {
+        let deadline = options.wait.map(|wait| std::time::Instant::now() + wait);
+        let mut attempt_options = options.clone();
+        let mut retries = 0;
+        let (sess, jump_warnings) = loop {
+            // Don't let one attempt run past the deadline
+            if let Some(deadline) = deadline {
+                let left = deadline.saturating_duration_since(std::time::Instant::now());
+                attempt_options.timeout = options.timeout.min(left).max(Duration::from_secs(1));
+            }
+
+            let e = match Self::connect_transport(resolved, &attempt_options).await {
+                Ok(transport) => break transport,
+                Err(e) => e,
+            };
+            let delay = options.connect_backoff.saturating_mul(1u32 << retries.min(5)).min(CONNECT_RETRY_MAX_DELAY);
+            let retry = match e.downcast_ref::<SbcError>() {
+                Some(SbcError::AuthFailed { .. }) => false,
+                // A name that doesn't resolve is usually a typo, unless we were asked to wait for it (mDNS)
+                Some(SbcError::NoTarget(_)) if deadline.is_none() => false,
+                _ => match deadline {
+                    Some(deadline) => std::time::Instant::now() + delay < deadline,
+                    None => retries < options.connect_retries,
+                },
+            };
+            if !retry {
+                return Err(e);
+            }
+
+            retries += 1;
+            verbose::status(&match deadline {
+                Some(deadline) => format!(
+                    "Waiting for {}:{} ({}), {}s left",
+                    resolved.host,
+                    resolved.port,
+                    e,
+                    deadline.saturating_duration_since(std::time::Instant::now()).as_secs()
+                ),
+                None => format!(
+                    "Connecting to {}:{} failed ({}), retry {}/{} in {}s",
+                    resolved.host,
+                    resolved.port,
+                    e,
+                    retries,
+                    options.connect_retries,
+                    delay.as_secs_f32()
+                ),
+            });
+            tokio::time::sleep(delay).await;
+        };
+
+        let mut connected = Self::login(sess, resolved, options)?;
+        connected.warnings.extend(jump_warnings);
+        Ok(connected)
+    }
+
+    /// TCP connection (direct or through the jump host) and SSH handshake, before any credentials
+    async fn connect_transport(resolved: &ResolvedTarget, options: &SshOptions) -> Result<(Session, Vec<String>)> {
+        let (tcp, jump_warnings) = match &resolved.proxy_jump {
+            Some(jump) => Self::connect_via_jump(jump, resolved, options).await?,
+            None => (Self::connect_tcp_or_proxy(&resolved.host, resolved.port, options)?, Vec::new()),
+        };
+        Ok((Self::handshake(tcp, resolved, options)?, jump_warnings))
+    }
+
+    /// Replaces a dead session, waiting with exponential backoff (capped) between failed attempts.
+    /// The wait doesn't hold the session, so keepalives and other callers aren't stalled by it.
+    async fn reconnect(&self, generation: u64, cause: &anyhow::Error) -> Result<()> {
+        // Another caller already reconnected
+        if self.generation.load(Ordering::Relaxed) != generation {
+            return Ok(());
+        }
+
+        let failures = self.reconnect_failures.load(Ordering::Relaxed);
+        let delay = RECONNECT_BASE_DELAY.saturating_mul(1u32 << failures.min(5)).min(RECONNECT_MAX_DELAY);
+        self.log("WARN", format!("SSH connection to {} lost ({}), reconnecting in {}s", self.target, cause, delay.as_secs()));
+        tokio::time::sleep(delay).await;
+
+        let mut session = self.session.lock().await;
+        // Another caller reconnected during the wait
+        if self.generation.load(Ordering::Relaxed) != generation {
+            return Ok(());
+        }
+        self.replace_session(&mut session).await
+    }
+
+    /// Drops the current session and connects again right away, skipping the backoff;
+    /// for when the board is known to have rebooted
+    pub async fn force_reconnect(&self) -> Result<()> {
+        let mut session = self.session.lock().await;
+        self.log("INFO", format!("Reconnecting to {} on request", self.target));
+        self.keepalive_failed.store(false, Ordering::Relaxed);
+        self.replace_session(&mut session).await
+    }
+
+    /// Says goodbye to the server instead of just dropping the TCP connection
+    pub async fn disconnect(&self) {
+        let session = self.session.lock().await;
+        let _ = session.disconnect(None, "sbctool exiting", None);
+    }
+
+    async fn replace_session(&self, session: &mut Session) -> Result<()> {
+        let result = match ssh_resolver::resolve(&self.target) {
+            Ok(resolved) => Self::connect(&resolved, &self.options).await,
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(connected) => {
+                for warning in connected.warnings {
+                    self.log("WARN", warning);
+                }
+                *session = connected.session;
+                self.generation.fetch_add(1, Ordering::Relaxed);
+                self.reconnect_failures.store(0, Ordering::Relaxed);
+                self.log("INFO", format!("Reconnected to {}", self.target));
+                Ok(())
+            }
+            Err(e) => {
+                self.reconnect_failures.fetch_add(1, Ordering::Relaxed);
+                self.log("ERROR", format!("Reconnect to {} failed: {}", self.target, e));
+                Err(e)
+            }
+        }
+    }
+
+    fn log(&self, level: &str, message: String) {
+        let Some(log_sender) = self.log_sender.get() else {
+            verbose::status(&message);
+            return;
+        };
+        if let Ok(mut logs) = log_sender.lock() {
+            crate::tui::push_log(&mut logs, LogEntry {
+                timestamp: chrono::Local::now().format("%H:%M:%S").to_string(),
+                level: level.to_string(),
+                message,
+            });
+        }
+    }
+
+    /// Direct TCP connection, or through `--proxy`, which then also resolves the host name
+    fn connect_tcp_or_proxy(host: &str, port: u16, options: &SshOptions) -> Result<TcpStream> {
+        let Some(proxy) = &options.proxy else {
+            return Self::connect_tcp(host, port, options.timeout);
+        };
+        let mut tcp = Self::connect_tcp(&proxy.host, proxy.port, options.timeout)?;
+        let started = std::time::Instant::now();
+        proxy.tunnel(&mut tcp, host, port, options.timeout)?;
+        timing::record(&format!("proxy connect {}:{}", host, port), started.elapsed());
+        Ok(tcp)
+    }
+
+    fn connect_tcp(host: &str, port: u16, timeout: Duration) -> Result<TcpStream> {
+        let started = std::time::Instant::now();
+        let result = Self::connect_tcp_untimed(host, port, timeout);
+        timing::record(&format!("connect {}:{}", host, port), started.elapsed());
+        result
+    }
+
+    fn connect_tcp_untimed(host: &str, port: u16, timeout: Duration) -> Result<TcpStream> {
+        let addrs: Vec<SocketAddr> = (host, port)
+            .to_socket_addrs()
+            .map_err(|e| SbcError::NoTarget(format!("Host name {} did not resolve: {}", host, e)))?
+            .collect();
+        if addrs.is_empty() {
+            return Err(SbcError::NoTarget(format!("Host name {} did not resolve", host)).into());
+        }
+
+        let mut last_error = None;
+        for addr in addrs {
+            match TcpStream::connect_timeout(&addr, timeout) {
+                Ok(tcp) => return Ok(tcp),
+                Err(e) => last_error = Some(e),
+            }
+        }
+
+        match last_error {
+            Some(e) if e.kind() == std::io::ErrorKind::TimedOut => Err(SbcError::ConnectTimeout {
+                host: host.to_string(),
+                port,
+                seconds: timeout.as_secs(),
+            }
+            .into()),
+            reason => Err(SbcError::Unreachable {
+                host: host.to_string(),
+                port,
+                reason: reason.map(|e| e.to_string()).unwrap_or_else(|| "no address to try".to_string()),
+            }
+            .into()),
+        }
+    }
+
+    fn open_session(tcp: TcpStream, resolved: &ResolvedTarget, options: &SshOptions) -> Result<Connected> {
+        let sess = Self::handshake(tcp, resolved, options)?;
+        Self::login(sess, resolved, options)
+    }
+
+    fn handshake(tcp: TcpStream, resolved: &ResolvedTarget, options: &SshOptions) -> Result<Session> {
+        tcp.set_read_timeout(Some(options.command_timeout))?;
+        tcp.set_write_timeout(Some(options.command_timeout))?;
+
+        // Create SSH session
+        let mut sess = Session::new()?;
+        sess.set_tcp_stream(tcp);
+        sess.set_timeout(options.command_timeout.as_millis().min(u32::MAX as u128) as u32);
+        if !options.keepalive.is_zero() {
+            sess.set_keepalive(true, options.keepalive.as_secs().min(u32::MAX as u64) as u32);
+        }
+        let started = std::time::Instant::now();
+        sess.handshake()?;
+        timing::record(&format!("handshake {}", resolved.host), started.elapsed());
+        Ok(sess)
+    }
+
+    /// Host key check and authentication on a session that completed the handshake
+    fn login(sess: Session, resolved: &ResolvedTarget, options: &SshOptions) -> Result<Connected> {
+        // Check the server against sbctool's own known_hosts first: one whose key changed
+        // may not be the board, so it doesn't get the password
+        let check = known_hosts::verify(&resolved.host, resolved.port, &sess)?;
+        let host_key_changed = matches!(check, known_hosts::HostKeyCheck::Changed(_));
+        let warnings = check.warning().into_iter().collect();
+
+        // Authenticate with the methods from --auth, in order
+        let started = std::time::Instant::now();
+        let method = Self::authenticate(&sess, &resolved.user, &resolved.identity_files, options, host_key_changed);
+        timing::record(&format!("auth {}@{}", resolved.user, resolved.host), started.elapsed());
+        let method = method?;
+
+        Ok(Connected { session: sess, method, warnings })
+    }
+
+    /// Tries each method of `options.auth_methods` in order, except the password when the
+    /// host key changed. On failure the error lists every method attempted and why it didn't work.
+    fn authenticate(sess: &Session, user: &str, identity_files: &[PathBuf], options: &SshOptions, host_key_changed: bool) -> Result<String> {
+        let mut attempts = Vec::new();
+
+        for method in &options.auth_methods {
+            match method {
+                AuthMethod::Agent => {
+                    if sess.userauth_agent(user).is_ok() && sess.authenticated() {
+                        return Ok("ssh-agent".to_string());
+                    }
+                    attempts.push("agent: no accepted key".to_string());
+                }
+                AuthMethod::Key => {
+                    let keys = match &options.identity {
+                        Some(identity) => vec![identity.clone()],
+                        None if identity_files.is_empty() => Self::default_identity_files(),
+                        None => identity_files.to_vec(),
+                    };
+
+                    let mut tried = Vec::new();
+                    for key in keys.iter().filter(|key| key.exists()) {
+                        // libssh2 can't always derive an ed25519 public key, so pass the .pub when it's there
+                        let public_key = Self::public_key_for(key);
+                        let passphrase = options.key_passphrase.as_deref();
+                        if sess.userauth_pubkey_file(user, public_key.as_deref(), key, passphrase).is_ok() && sess.authenticated() {
+                            return Ok(format!("public key ({})", key.display()));
+                        }
+                        if passphrase.is_none() && key_is_encrypted(key) {
+                            tried.push(format!("{} (passphrase-protected, set SBCTOOL_KEY_PASSPHRASE)", key.display()));
+                        } else {
+                            tried.push(key.display().to_string());
                         }
                     }
-                    
-                    let host = resolved_host.unwrap_or(target.to_string());
-                    let user = resolved_user.unwrap_or_else(|| {
-                        std::env::var("USER").unwrap_or_else(|_| "root".to_string())
+                    attempts.push(if tried.is_empty() {
+                        "key: no key file found".to_string()
+                    } else {
+                        format!("key: rejected {}", tried.join(", "))
                     });
-                    Ok((user, host))
-                } else {
-                    Err(anyhow::anyhow!("Failed to resolve SSH alias: {}", target))
                 }
-            } else {
-                Err(anyhow::anyhow!("SSH command not available"))
+                AuthMethod::Password => match &options.password {
+                    Some(_) if host_key_changed => attempts.push("password: not sent, the host key changed".to_string()),
+                    Some(password) => {
+                        if sess.userauth_password(user, password).is_ok() && sess.authenticated() {
+                            return Ok("password".to_string());
+                        }
+                        attempts.push("password: rejected".to_string());
+                    }
+                    None => attempts.push("password: none given (use --password-stdin or SBCTOOL_PASSWORD)".to_string()),
+                },
             }
         }
+
+        Err(SbcError::AuthFailed {
+            user: user.to_string(),
+            attempts: attempts.join("; "),
+        }
+        .into())
+    }
+
+    /// The first of `~/.ssh/id_ed25519`, `id_ecdsa` and `id_rsa` that exists, used when ssh config lists none
+    fn default_identity_files() -> Vec<PathBuf> {
+        DEFAULT_KEY_NAMES
+            .iter()
+            .map(|name| paths::expand(&format!("~/.ssh/{}", name)))
+            .find(|key| key.exists())
+            .into_iter()
+            .collect()
+    }
+
+    fn public_key_for(private_key: &Path) -> Option<PathBuf> {
+        let mut public_key = private_key.as_os_str().to_owned();
+        public_key.push(".pub");
+        let public_key = PathBuf::from(public_key);
+        public_key.exists().then_some(public_key)
+    }
+
+    /// Opens a `direct-tcpip` channel on the jump host and exposes it as a local
+    /// TCP socket, since ssh2 can only run a session over a real socket.
+    async fn connect_via_jump(jump: &str, dest: &ResolvedTarget, options: &SshOptions) -> Result<(TcpStream, Vec<String>)> {
+        if jump.contains(',') {
+            return Err(anyhow::anyhow!("Only a single ProxyJump hop is supported: {}", jump));
+        }
+
+        // ProxyJump syntax is [user@]host[:port]
+        let (jump_target, jump_port) = match jump.rsplit_once(':').and_then(|(h, p)| p.parse::<u16>().ok().map(|p| (h, p))) {
+            Some((host, port)) => (host, Some(port)),
+            None => (jump, None),
+        };
+
+        let mut jump_resolved = ssh_resolver::resolve(jump_target)?;
+        if let Some(port) = jump_port {
+            jump_resolved.port = port;
+        }
+
+        let jump_tcp = Self::connect_tcp_or_proxy(&jump_resolved.host, jump_resolved.port, options)?;
+        let Connected { session: jump_sess, warnings, .. } = Self::open_session(jump_tcp, &jump_resolved, options)?;
+        let channel = jump_sess.channel_direct_tcpip(&dest.host, dest.port, None)?;
+
+        let listener = TcpListener::bind(("127.0.0.1", 0))?;
+        let local_addr = listener.local_addr()?;
+        std::thread::spawn(move || {
+            if let Ok((socket, _)) = listener.accept() {
+                Self::pump_jump_channel(jump_sess, channel, socket);
+            }
+        });
+
+        Ok((TcpStream::connect(local_addr)?, warnings))
+    }
+
+    /// Copies bytes between the local bridge socket and the jump host channel until either side closes
+    fn pump_jump_channel(jump_sess: Session, mut channel: Channel, mut socket: TcpStream) {
+        jump_sess.set_blocking(false);
+        if socket.set_nonblocking(true).is_err() {
+            return;
+        }
+
+        let mut buffer = [0; 16 * 1024];
+        loop {
+            let mut idle = true;
+
+            match socket.read(&mut buffer) {
+                Ok(0) => break,
+                Ok(n) => {
+                    idle = false;
+                    if Self::write_all_nonblocking(&mut channel, &buffer[..n]).is_err() {
+                        break;
+                    }
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {}
+                Err(_) => break,
+            }
+
+            match channel.read(&mut buffer) {
+                Ok(0) => break,
+                Ok(n) => {
+                    idle = false;
+                    if Self::write_all_nonblocking(&mut socket, &buffer[..n]).is_err() {
+                        break;
+                    }
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {}
+                Err(_) => break,
+            }
+
+            if idle {
+                std::thread::sleep(Duration::from_millis(5));
+            }
+        }
+
+        let _ = channel.close();
+    }
+
+    fn write_all_nonblocking<W: Write>(writer: &mut W, mut data: &[u8]) -> std::io::Result<()> {
+        while !data.is_empty() {
+            match writer.write(data) {
+                Ok(0) => return Err(std::io::ErrorKind::WriteZero.into()),
+                Ok(n) => data = &data[n..],
+                Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                    std::thread::sleep(Duration::from_millis(1));
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        writer.flush()
     }
     
+    /// Runs a command and returns its trimmed stdout, or an error for a nonzero exit status
     pub async fn execute_command(&self, command: &str) -> Result<String> {
-        let session = self.session.lock().await;
+        let output = self.execute_command_full(command).await?;
         
-        // Create a new channel for this command
+        if output.exit_status == 0 {
+            Ok(output.stdout.trim().to_string())
+        } else {
+            Err(SbcError::CommandFailed {
+                exit_status: output.exit_status,
+                output: output.stderr,
+            }
+            .into())
+        }
+    }
+
+    /// Runs a command and returns stdout, stderr and the exit status untouched.
+    /// Only transport failures are errors; a nonzero exit status is not.
+    pub async fn execute_command_full(&self, command: &str) -> Result<CommandOutput> {
+        if self.keepalive_failed.swap(false, Ordering::Relaxed) {
+            let generation = self.generation.load(Ordering::Relaxed);
+            self.reconnect(generation, &anyhow::anyhow!("keepalive not answered")).await?;
+        }
+        let generation = self.generation.load(Ordering::Relaxed);
+        let e = {
+            let session = self.session.lock().await;
+            match self.start_command(&session, command) {
+                Ok(channel) => return self.read_output(&session, channel),
+                Err(e) => e,
+            }
+        };
+
+        // The channel couldn't be opened or the command couldn't be started: the board rebooted or
+        // the link dropped. Nothing ran yet, so running it after reconnecting can't run it twice.
+        // Timeouts and read errors of a started command are returned as they are; it may still run.
+        self.reconnect(generation, &e).await?;
+        let session = self.session.lock().await;
+        let channel = self.start_command(&session, command)?;
+        self.read_output(&session, channel)
+    }
+
+    /// Opens a channel and starts `command` on it. A failed exec on an open channel is an
+    /// `ExecRefused`, so callers can tell it from a dead session.
+    fn start_command(&self, session: &Session, command: &str) -> Result<Channel> {
         let mut channel = session.channel_session()?;
-        channel.exec(command)?;
-        
-        // Read the output
-        let mut output = String::new();
-        channel.read_to_string(&mut output)?;
-        
+        self.exec(&mut channel, command).map_err(|e| SbcError::ExecRefused(e.to_string()))?;
+        Ok(channel)
+    }
+
+    /// Reads a started command's stdout and stderr together and waits for its exit status.
+    /// Non-blocking like `pump_script`: reading stdout to the end first would leave a command
+    /// that fills the channel window with stderr stuck until the timeout.
+    fn read_output(&self, session: &Session, mut channel: Channel) -> Result<CommandOutput> {
+        session.set_blocking(false);
+        let result = self.pump_output(&channel);
+        session.set_blocking(true);
+        let (stdout, stderr) = result?;
+
         // Wait for the command to complete
         channel.wait_close()?;
         let exit_status = channel.exit_status()?;
-        
-        if exit_status == 0 {
-            Ok(output.trim().to_string())
-        } else {
-            Err(anyhow::anyhow!("Command failed with exit status: {}", exit_status))
+
+        Ok(CommandOutput {
+            stdout: String::from_utf8_lossy(&stdout).into_owned(),
+            stderr: String::from_utf8_lossy(&stderr).into_owned(),
+            exit_status,
+        })
+    }
+
+    /// Collects stdout and stderr until the command closes both; fails when neither produced
+    /// anything for the command timeout. The session is non-blocking here.
+    fn pump_output(&self, channel: &Channel) -> Result<(Vec<u8>, Vec<u8>)> {
+        let mut outputs = [Vec::new(), Vec::new()];
+        let mut open = [true, true];
+        let mut buffer = [0; 16 * 1024];
+        let mut last_output = std::time::Instant::now();
+        while open.contains(&true) {
+            let mut idle = true;
+
+            // Stream 0 is stdout, 1 is stderr
+            for (id, output) in outputs.iter_mut().enumerate() {
+                if !open[id] {
+                    continue;
+                }
+                match channel.stream(id as i32).read(&mut buffer) {
+                    Ok(0) => open[id] = false,
+                    Ok(n) => {
+                        idle = false;
+                        output.extend_from_slice(&buffer[..n]);
+                    }
+                    Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {}
+                    Err(e) => return Err(anyhow::anyhow!("Error reading command output: {}", e)),
+                }
+            }
+
+            if !idle {
+                last_output = std::time::Instant::now();
+            } else if last_output.elapsed() > self.options.command_timeout {
+                return Err(anyhow::anyhow!("Command gave no output for {}s", self.options.command_timeout.as_secs()));
+            } else {
+                std::thread::sleep(Duration::from_millis(5));
+            }
         }
+
+        let [stdout, stderr] = outputs;
+        Ok((stdout, stderr))
     }
     
+    /// Starts `command` on the channel with `--env` and `--cwd` applied. Variables go out as SSH
+    /// `env` requests first, but most sshd configs only accept a few names (`AcceptEnv`), so after
+    /// the first refusal they are exported by the remote shell instead. The protocol has no
+    /// working directory request, so that is always a `cd`.
+    fn exec(&self, channel: &mut Channel, command: &str) -> Result<()> {
+        let env = &self.options.env;
+        let sent = !env.is_empty()
+            && !self.setenv_rejected.load(Ordering::Relaxed)
+            && env.iter().all(|(key, value)| channel.setenv(key, value).is_ok());
+        if !env.is_empty() && !sent {
+            self.setenv_rejected.store(true, Ordering::Relaxed);
+        }
+
+        let prefix = raw_command::env_prefix(self.options.cwd.as_deref(), if sent { &[] } else { env.as_slice() });
+        let command = format!("{}{}", prefix, command);
+        verbose::command("ssh2", &self.target, &command);
+        channel.exec(&command)?;
+        Ok(())
+    }
+
+    /// Pipes `script` into a remote `sh -s` with `args` as its positional parameters, handing
+    /// stdout and stderr to `on_output` as they arrive. Returns the script's exit status.
+    pub async fn run_script(&self, script: &[u8], args: &[String], mut on_output: impl FnMut(&[u8])) -> Result<i32> {
+        let session = self.session.lock().await;
+
+        let mut channel = session.channel_session()?;
+        channel.handle_extended_data(ExtendedData::Merge)?;
+        let mut command = "sh -s --".to_string();
+        for arg in args {
+            command.push(' ');
+            command.push_str(&raw_command::quote(arg));
+        }
+        self.exec(&mut channel, &command)?;
+
+        // Non-blocking like the interactive shell: a script that prints a lot while it is still
+        // being sent would otherwise fill the channel window with both sides waiting on the other.
+        // Scripts may also stay quiet for longer than the command timeout.
+        session.set_blocking(false);
+        let result = Self::pump_script(&session, &mut channel, script, &mut on_output);
+        session.set_blocking(true);
+        result?;
+
+        channel.wait_close()?;
+        Ok(channel.exit_status()?)
+    }
+
+    /// Sends `script` as stdin, then EOF, while passing output on as it arrives, until the remote
+    /// side closes. The session is non-blocking here.
+    fn pump_script(session: &Session, channel: &mut Channel, mut script: &[u8], on_output: &mut impl FnMut(&[u8])) -> Result<()> {
+        let mut eof_sent = false;
+        let mut buffer = [0; 16 * 1024];
+        loop {
+            let mut idle = true;
+
+            if !script.is_empty() {
+                match channel.write(script) {
+                    Ok(n) => {
+                        idle = n == 0;
+                        script = &script[n..];
+                    }
+                    Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {}
+                    Err(e) => return Err(anyhow::anyhow!("Error sending script: {}", e)),
+                }
+            } else if !eof_sent {
+                eof_sent = true;
+                session.set_blocking(true);
+                let sent = channel.send_eof();
+                session.set_blocking(false);
+                sent?;
+            }
+
+            match channel.read(&mut buffer) {
+                Ok(0) => return Ok(()),
+                Ok(n) => {
+                    idle = false;
+                    on_output(&buffer[..n]);
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {}
+                Err(e) => return Err(anyhow::anyhow!("Error reading script output: {}", e)),
+            }
+
+            if idle {
+                std::thread::sleep(Duration::from_millis(5));
+            }
+        }
+    }
+
+    /// Interactive login shell on a PTY sized like the local terminal. Proxies the terminal in raw
+    /// mode until the remote shell exits and returns its exit status.
+    pub async fn interactive_shell(&self) -> Result<i32> {
+        let session = self.session.lock().await;
+
+        let mut channel = session.channel_session()?;
+        let (columns, rows) = interactive::size();
+        channel.request_pty(&interactive::term_name(), None, Some((columns as u32, rows as u32, 0, 0)))?;
+        verbose::command("ssh2", &self.target, "<interactive shell>");
+        channel.shell()?;
+
+        let raw_mode = interactive::RawMode::enable()?;
+        session.set_blocking(false);
+        let result = Self::pump_shell(&session, &mut channel, (columns, rows));
+        session.set_blocking(true);
+        drop(raw_mode);
+        result?;
+
+        channel.wait_close()?;
+        Ok(channel.exit_status()?)
+    }
+
+    /// Copies keystrokes to the shell and its output to stdout until the shell closes its side,
+    /// passing local terminal resizes on. The session is non-blocking here.
+    fn pump_shell(session: &Session, channel: &mut Channel, mut size: (u16, u16)) -> Result<()> {
+        let input = interactive::spawn_stdin_reader();
+        let mut stdin_open = true;
+        let mut stdout = std::io::stdout();
+        let mut buffer = [0; 16 * 1024];
+        loop {
+            let mut idle = true;
+
+            while stdin_open {
+                match input.try_recv() {
+                    Ok(keys) => {
+                        idle = false;
+                        Self::write_all_nonblocking(channel, &keys)?;
+                    }
+                    Err(std::sync::mpsc::TryRecvError::Empty) => break,
+                    Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                        // Local EOF, e.g. input piped in: let the remote shell see it too
+                        stdin_open = false;
+                        session.set_blocking(true);
+                        let sent = channel.send_eof();
+                        session.set_blocking(false);
+                        sent?;
+                    }
+                }
+            }
+
+            match channel.read(&mut buffer) {
+                Ok(0) => return Ok(()),
+                Ok(n) => {
+                    idle = false;
+                    stdout.write_all(&buffer[..n])?;
+                    stdout.flush()?;
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {}
+                Err(e) => return Err(anyhow::anyhow!("Error reading shell output: {}", e)),
+            }
+
+            let current = interactive::size();
+            if current != size {
+                size = current;
+                session.set_blocking(true);
+                let resized = channel.request_pty_size(size.0 as u32, size.1 as u32, None, None);
+                session.set_blocking(false);
+                resized?;
+            }
+
+            if idle {
+                std::thread::sleep(Duration::from_millis(5));
+            }
+        }
+    }
+
+    /// Runs all commands in a single `exec`, separated by marker lines, so a batch costs one
+    /// round trip instead of one per command. Each command runs in its own subshell; results
+    /// match `execute_multiple_commands` (trimmed stdout, or "Error: ..." with the command's stderr
+    /// for a nonzero exit).
+    /// Fails when the markers don't come back intact, e.g. a non-POSIX login shell.
+    pub async fn execute_batched(&self, commands: &[&str]) -> Result<Vec<String>> {
+        // Unique per call so command output can't fake a marker
+        let nonce = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_nanos())
+            .unwrap_or(0);
+        let marker = format!("---SBCTOOL-SEP-{:x}", nonce);
+
+        let script: String = commands
+            .iter()
+            .enumerate()
+            // Newlines rather than `;` so a trailing comment in a command can't swallow the `)`.
+            // The markers go to stderr as well, so each command's error output can be split out too.
+            .map(|(index, command)| {
+                format!(
+                    "echo {marker}-{index}-BEGIN---\necho {marker}-{index}-BEGIN--- >&2\n( {command}\n)\necho {marker}-{index}-END-$?---\necho {marker}-{index}-END--- >&2\n"
+                )
+            })
+            .collect();
+
+        let started = std::time::Instant::now();
+        let output = self.execute_command_full(&script).await?;
+        timing::record(&format!("batch of {} commands", commands.len()), started.elapsed());
+
+        let mut results = Vec::new();
+        for index in 0..commands.len() {
+            let begin = format!("{}-{}-BEGIN---", marker, index);
+            let end = format!("{}-{}-END-", marker, index);
+            let (Some(begin_at), Some(end_at)) = (output.stdout.find(&begin), output.stdout.find(&end)) else {
+                return Err(anyhow::anyhow!("Batched output is missing the markers of command {}", index));
+            };
+            let body = output.stdout.get(begin_at + begin.len()..end_at).unwrap_or("").trim();
+            let exit_status: i32 = output.stdout[end_at + end.len()..]
+                .split("---")
+                .next()
+                .and_then(|status| status.parse().ok())
+                .unwrap_or(-1);
+
+            verbose::output("ssh2", &self.target, body);
+            results.push(if exit_status == 0 {
+                body.to_string()
+            } else {
+                let stderr = match (output.stderr.find(&begin), output.stderr.find(&end)) {
+                    (Some(begin_at), Some(end_at)) => output.stderr.get(begin_at + begin.len()..end_at).unwrap_or("").trim().to_string(),
+                    _ => String::new(),
+                };
+                format!("{}{}", FAILED_COMMAND_PREFIX, SbcError::CommandFailed { exit_status, output: stderr })
+            });
+        }
+
+        Ok(results)
+    }
+
     pub async fn execute_multiple_commands(&self, commands: &[&str]) -> Result<Vec<String>> {
+        self.execute_multiple_commands_with_progress(commands, |_, _| {}).await
+    }
+
+    /// Like `execute_multiple_commands`, calling `on_step(index, command)` before each command runs
+    pub async fn execute_multiple_commands_with_progress(&self, commands: &[&str], mut on_step: impl FnMut(usize, &str)) -> Result<Vec<String>> {
         let mut results = Vec::new();
         
-        for command in commands {
-            match self.execute_command(command).await {
-                Ok(output) => results.push(output),
+        for (index, command) in commands.iter().enumerate() {
+            on_step(index, command);
+            let started = std::time::Instant::now();
+            let result = self.execute_command(command).await;
+            timing::record(command, started.elapsed());
+            match result {
+                Ok(output) => {
+                    verbose::output("ssh2", &self.target, &output);
+                    results.push(output);
+                }
                 Err(e) => {
                     // Log error but continue with other commands
                     eprintln!("Command '{}' failed: {}", command, e);
-                    results.push(format!("Error: {}", e));
+                    results.push(format!("{}{}", FAILED_COMMAND_PREFIX, e));
                 }
             }
         }
@@ -114,38 +993,176 @@ impl SSHSession {
         Ok(results)
     }
     
-    pub async fn start_log_stream(&self, log_sender: Arc<Mutex<Vec<crate::tui::LogEntry>>>) -> Result<()> {
+    pub async fn upload_file<F>(&self, local_path: &Path, remote_path: &str, mut progress: F) -> Result<u64>
+    where
+        F: FnMut(u64, u64),
+    {
+        let mut file = std::fs::File::open(local_path)?;
+        let metadata = file.metadata()?;
+        let size = metadata.len();
+        let mode = Self::local_file_mode(&metadata);
+
         let session = self.session.lock().await;
-        
-        // Start journalctl -f for real-time logs
-        let mut channel = session.channel_session()?;
-        channel.exec("journalctl -f --no-hostname --output=short-iso")?;
-        
-        // Stream logs in a loop
+        let mut channel = session.scp_send(Path::new(remote_path), mode, size, None)?;
+
+        // Copy in chunks so the caller can report progress
+        let mut buffer = [0; 32 * 1024];
+        let mut transferred = 0u64;
+        loop {
+            let n = file.read(&mut buffer)?;
+            if n == 0 {
+                break;
+            }
+            channel.write_all(&buffer[..n])?;
+            transferred += n as u64;
+            progress(transferred, size);
+        }
+
+        // Signal EOF and wait for the remote scp to finish
+        channel.send_eof()?;
+        channel.wait_eof()?;
+        channel.close()?;
+        channel.wait_close()?;
+
+        Ok(transferred)
+    }
+
+    pub async fn download_file<F>(&self, remote_path: &str, local_path: &Path, mut progress: F) -> Result<u64>
+    where
+        F: FnMut(u64, u64),
+    {
+        let session = self.session.lock().await;
+        let (mut channel, stat) = session.scp_recv(Path::new(remote_path))?;
+        let size = stat.size();
+
+        let mut file = std::fs::File::create(local_path)?;
+
+        // Only read the announced size, scp appends a trailing status byte
+        let mut buffer = [0; 32 * 1024];
+        let mut transferred = 0u64;
+        while transferred < size {
+            let want = std::cmp::min(buffer.len() as u64, size - transferred) as usize;
+            let n = channel.read(&mut buffer[..want])?;
+            if n == 0 {
+                break;
+            }
+            file.write_all(&buffer[..n])?;
+            transferred += n as u64;
+            progress(transferred, size);
+        }
+
+        channel.send_eof()?;
+        channel.wait_eof()?;
+        channel.close()?;
+        channel.wait_close()?;
+
+        Self::set_local_file_mode(local_path, stat.mode())?;
+
+        if transferred < size {
+            return Err(anyhow::anyhow!("Transfer incomplete: received {} of {} bytes", transferred, size));
+        }
+
+        Ok(transferred)
+    }
+
+    #[cfg(unix)]
+    fn local_file_mode(metadata: &std::fs::Metadata) -> i32 {
+        use std::os::unix::fs::PermissionsExt;
+        (metadata.permissions().mode() & 0o777) as i32
+    }
+
+    #[cfg(not(unix))]
+    fn local_file_mode(_metadata: &std::fs::Metadata) -> i32 {
+        0o644
+    }
+
+    #[cfg(unix)]
+    fn set_local_file_mode(path: &Path, mode: i32) -> Result<()> {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode((mode & 0o777) as u32))?;
+        Ok(())
+    }
+
+    #[cfg(not(unix))]
+    fn set_local_file_mode(_path: &Path, _mode: i32) -> Result<()> {
+        Ok(())
+    }
+
+    /// Streams `journalctl -f` into `log_sender` until EOF or until `stop` returns true.
+    /// Holds the session for the whole stream, so use a dedicated `SSHSession` for it.
+    pub async fn start_log_stream(
+        &self,
+        log_sender: std::sync::Arc<std::sync::Mutex<Vec<crate::tui::LogEntry>>>,
+        stop: impl Fn() -> bool + Send + 'static,
+        log_lines: usize,
+    ) -> Result<()> {
+        if self.execute_command("command -v journalctl").await.is_err() {
+            return Err(anyhow::anyhow!("journalctl is not available on the target"));
+        }
+
+        // Background jobs get /dev/null as stdin, so the watcher reads the channel through fd 3
+        let command = format!("exec 3<&0; {} {} & {}", LOG_STREAM_COMMAND, log_lines, LOG_STREAM_WATCHER);
+        verbose::command("ssh2", &self.target, &command);
+
+        // The reads block for up to 500 ms at a time, so keep them off the runtime's worker threads
+        let session = self.session.clone();
+        let timeout_ms = self.timeout_ms;
+        tokio::task::spawn_blocking(move || {
+            let session = session.blocking_lock();
+            let mut channel = session.channel_session()?;
+            channel.exec(&command)?;
+
+            // Short blocking timeout so the loop can notice a shutdown request
+            session.set_timeout(500);
+            let result = Self::read_log_stream(&mut channel, &log_sender, &stop);
+
+            // EOF stops the remote journalctl through the watcher; the 500 ms timeout still bounds the close
+            let _ = channel.send_eof();
+            let _ = channel.close();
+            let _ = channel.wait_close();
+            session.set_timeout(timeout_ms);
+
+            result
+        })
+        .await?
+    }
+
+    /// Parses complete lines from the stream into `log_sender` until EOF, an error or `stop`
+    fn read_log_stream(channel: &mut Channel, log_sender: &std::sync::Mutex<Vec<crate::tui::LogEntry>>, stop: &impl Fn() -> bool) -> Result<()> {
         let mut buffer = [0; 1024];
+        let mut pending: Vec<u8> = Vec::new();
         loop {
+            if stop() {
+                return Ok(());
+            }
+
             match channel.read(&mut buffer) {
-                Ok(0) => break, // EOF
+                Ok(0) => return Ok(()), // EOF
                 Ok(n) => {
-                    let data = String::from_utf8_lossy(&buffer[..n]);
-                    for line in data.lines() {
-                        if let Some(entry) = self.parse_journald_log_line(line) {
-                            let mut logs = log_sender.lock().await;
-                            logs.push(entry);
+                    pending.extend_from_slice(&buffer[..n]);
+                    while let Some(pos) = pending.iter().position(|&b| b == b'\n') {
+                        let raw: Vec<u8> = pending.drain(..=pos).collect();
+                        let line = String::from_utf8_lossy(&raw);
+                        if let Some(entry) = Self::parse_journald_log_line(line.trim_end()) {
+                            if let Ok(mut logs) = log_sender.lock() {
+                                crate::tui::push_log_dedup(&mut logs, entry);
+                            }
                         }
                     }
                 }
-                Err(e) => {
-                    eprintln!("Error reading log stream: {}", e);
-                    break;
-                }
+                Err(e) if e.kind() == std::io::ErrorKind::TimedOut => continue,
+                Err(e) => return Err(anyhow::anyhow!("Error reading log stream: {}", e)),
             }
         }
-        
-        Ok(())
     }
     
-    fn parse_journald_log_line(&self, line: &str) -> Option<crate::tui::LogEntry> {
+    fn parse_journald_log_line(line: &str) -> Option<crate::tui::LogEntry> {
+        // Prefer the real syslog priority from JSON output
+        if let Some(entry) = crate::log_collector::parse_journald_json_line(line) {
+            return Some(entry);
+        }
+
+        // Fallback for plain text output: guess the level from the message
         // Example: "2025-09-05T18:49:25+0000 hostname systemd[1]: Started Session 1 of User stulluk."
         let parts: Vec<&str> = line.splitn(3, ' ').collect();
         if parts.len() < 3 { return None; }
@@ -168,3 +1185,32 @@ impl SSHSession {
         Some(crate::tui::LogEntry { timestamp, level, message })
     }
 }
+
+/// Whether a private key file needs a passphrase: PEM keys say so in their header, OpenSSH
+/// format keys name their cipher right after the "openssh-key-v1" magic ("none" when plain)
+pub fn key_is_encrypted(path: &Path) -> bool {
+    use base64::Engine;
+
+    let Ok(content) = std::fs::read_to_string(path) else {
+        return false;
+    };
+    if content.contains("ENCRYPTED") {
+        return true;
+    }
+    if !content.contains("BEGIN OPENSSH PRIVATE KEY") {
+        return false;
+    }
+
+    let body: String = content.lines().filter(|line| !line.starts_with("-----")).collect();
+    let Ok(decoded) = base64::engine::general_purpose::STANDARD.decode(body.trim()) else {
+        return false;
+    };
+    const MAGIC: &[u8] = b"openssh-key-v1\0";
+    let Some(rest) = decoded.strip_prefix(MAGIC) else {
+        return false;
+    };
+    let Some(length) = rest.get(..4).map(|bytes| u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize) else {
+        return false;
+    };
+    rest.get(4..4 + length).is_some_and(|cipher| cipher != b"none")
+}
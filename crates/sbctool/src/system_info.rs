@@ -1,12 +1,138 @@
 use anyhow::Result;
-use crate::tui::SystemInfo;
-use crate::ssh_session::SSHSession;
+use crate::tui::{self, BatteryStatus, BootInfo, CpuFreq, FanReading, WifiLink, ExtraValue, GpioLine, FailedUnit, KernelAlert, KernelModule, MountInfo, NetIface, PendingUpdates, ProcessInfo, StorageDevice, SystemInfo, ThermalZone};
+use crate::error::SbcError;
+use crate::extra_collectors;
+use crate::snapshot;
+use crate::ssh_session::{FAILED_COMMAND_PREFIX, SSHSession};
+use crate::ssh_resolver;
+use crate::timing;
+use crate::verbose;
+use crate::adb_session::ADBSession;
+use crate::soc_db;
+use crate::userland::{self, Userland};
+use std::io::{IsTerminal, Write};
 use std::sync::Arc;
 
+/// Kernel name, release and machine on separate lines, instead of index-slicing `uname -a`
+const UNAME_COMMAND: &str = "uname -s && uname -r && uname -m";
+
+/// Block device sizes plus eMMC/SD name, type and life-time estimate, as "path:value" lines
+const STORAGE_COMMAND: &str = "grep . /sys/block/*/size /sys/block/mmcblk*/device/name /sys/block/mmcblk*/device/type /sys/block/mmcblk*/device/life_time 2>/dev/null; true";
+
+/// "N temp type" per thermal zone; the temperature goes first since a few drivers put spaces in
+/// the type, and zones whose sensor can't be read (EIO, ENODATA) come out without one
+const THERMAL_COMMAND: &str = "for z in /sys/class/thermal/thermal_zone*; do [ -f $z/temp ] && echo \"${z##*thermal_zone} $(cat $z/temp 2>/dev/null) $(cat $z/type 2>/dev/null)\"; done; true";
+
+/// Longest command prefix shown in the batch progress line
+const PROGRESS_LABEL_WIDTH: usize = 60;
+
+/// Raspberry Pi firmware report: throttling bitmask, SoC temperature and core voltage
+const POWER_COMMAND: &str = "vcgencmd get_throttled && vcgencmd measure_temp && vcgencmd measure_volts";
+
+/// Exported sysfs GPIOs as "sysfs <gpioN> <direction> <value>", then libgpiod's view of all lines
+const GPIO_COMMAND: &str = "for g in /sys/class/gpio/gpio[0-9]*; do [ -d $g ] && echo sysfs ${g##*/} $(cat $g/direction 2>/dev/null) $(cat $g/value 2>/dev/null); done; echo __GPIOINFO__; gpioinfo 2>/dev/null; true";
+
+/// PID 1's name and binary, the service managers' CLIs on PATH and Android's release prop
+const INIT_COMMAND: &str = "cat /proc/1/comm 2>/dev/null; echo __INIT__; readlink -f /sbin/init 2>/dev/null; echo __TOOLS__; command -v systemctl rc-status service 2>/dev/null; echo __ANDROID__; getprop ro.build.version.release 2>/dev/null; true";
+
+/// Kernel cmdline, device-tree bootargs and bootloader version, then the device mounted on /
+const BOOT_COMMAND: &str = "cat /proc/cmdline 2>/dev/null; echo __BOOTARGS__; cat /proc/device-tree/chosen/bootargs 2>/dev/null; echo; echo __BOOTLOADER__; cat /proc/device-tree/chosen/u-boot,version 2>/dev/null; echo; echo __ROOTFS__; findmnt -n -o SOURCE / 2>/dev/null; true";
+
+/// systemd's verdict, container markers, PID 1's cgroups, the DMI product name and Android's emulator props
+const VIRT_COMMAND: &str = "systemd-detect-virt 2>/dev/null; echo __CONTAINER__; cat /run/systemd/container 2>/dev/null; [ -f /.dockerenv ] && echo docker; echo __CGROUP__; cat /proc/1/cgroup 2>/dev/null; echo __DMI__; cat /sys/class/dmi/id/product_name 2>/dev/null; echo __QEMU__; getprop ro.kernel.qemu 2>/dev/null; getprop ro.boot.qemu 2>/dev/null; true";
+
+/// hwmon chip names, fan tachometers and PWM duty, plus cooling device types and states, as "path:value" lines
+const FAN_COMMAND: &str = "grep . /sys/class/hwmon/hwmon*/name /sys/class/hwmon/hwmon*/fan*_input /sys/class/hwmon/hwmon*/pwm[0-9] /sys/class/thermal/cooling_device*/type /sys/class/thermal/cooling_device*/cur_state /sys/class/thermal/cooling_device*/max_state 2>/dev/null; true";
+
+/// `iw dev <iface> link` for every wireless interface, then /proc/net/wireless as a fallback for the signal level
+const WIFI_COMMAND: &str = "for i in /sys/class/net/*; do [ -d $i/wireless ] && echo __IFACE__ ${i##*/} && iw dev ${i##*/} link 2>/dev/null; done; echo __PROCWIRELESS__; cat /proc/net/wireless 2>/dev/null; true";
+
+/// `lspci` with domains, or the raw class and vendor/device IDs from sysfs on images without pciutils
+const PCI_COMMAND: &str = "lspci -D 2>/dev/null || for d in /sys/bus/pci/devices/*; do [ -d $d ] && echo ${d##*/} $(cat $d/class) $(cat $d/vendor) $(cat $d/device); done; true";
+
+/// Loaded modules as "name size refcount deps state address", readable where `lsmod` isn't installed
+const MODULES_COMMAND: &str = "cat /proc/modules 2>/dev/null; true";
+
+/// Kernel log lines about OOM kills, panics and oopses; journald's kernel log when dmesg is restricted to root
+const ALERTS_COMMAND: &str = "(dmesg -T 2>/dev/null || dmesg 2>/dev/null || journalctl -k --no-pager -o short-iso 2>/dev/null) | grep -iE \"out of memory|oom-kill|killed process|kernel panic|call trace\" | tail -n 20; true";
+
+/// Serial sources in order of preference: device tree (both paths), cpuinfo's "Serial" line, Android's prop
+const SERIAL_COMMAND: &str = "cat /proc/device-tree/serial-number 2>/dev/null; echo; echo __SYSFS__; cat /sys/firmware/devicetree/base/serial-number 2>/dev/null; echo; echo __CPUINFO__; grep -i ^serial /proc/cpuinfo 2>/dev/null; echo __ANDROID__; getprop ro.serialno 2>/dev/null; true";
+
+/// Mount table, then sizes and usage in 1K blocks; POSIX `df` output keeps one line per filesystem
+const MOUNTS_COMMAND: &str = "cat /proc/mounts 2>/dev/null; echo __DF__; df -Pk 2>/dev/null; true";
+
+/// Active swap areas, then every zram device with its size and compression algorithm list
+const SWAP_COMMAND: &str = "cat /proc/swaps 2>/dev/null; echo __ZRAM__; for d in /sys/block/zram*; do [ -e \"$d/disksize\" ] && echo \"${d##*/} $(cat $d/disksize) $(cat $d/comp_algorithm 2>/dev/null)\"; done; true";
+
+/// Board identity from the firmware's DMI tables, as "name=value" lines; x86 boards have no device tree
+const DMI_COMMAND: &str = "for f in sys_vendor product_name board_vendor board_name; do echo \"$f=$(cat /sys/class/dmi/id/$f 2>/dev/null)\"; done";
+
+/// DMI values firmware vendors leave in when they didn't fill the field in
+const DMI_PLACEHOLDERS: [&str; 7] = [
+    "To be filled by O.E.M.", "Default string", "System Product Name", "System manufacturer", "Not Specified",
+    "Not Applicable", "Type2 - Board Product Name",
+];
+
+/// Kernel and virtual filesystems left out of the mounts list
+const PSEUDO_FILESYSTEMS: [&str; 17] = [
+    "proc", "sysfs", "devpts", "devtmpfs", "cgroup", "cgroup2", "securityfs", "debugfs", "tracefs", "pstore", "bpf",
+    "configfs", "mqueue", "hugetlbfs", "fusectl", "binfmt_misc", "autofs",
+];
+
+/// Alerts kept, newest last
+const MAX_ALERTS: usize = 5;
+
+/// Failed units as "__UNIT__ name load active sub description", each followed by its `systemctl status`
+const FAILED_UNITS_COMMAND: &str = "systemctl --failed --no-legend --plain 2>/dev/null | while read -r unit rest; do echo __UNIT__ $unit $rest; systemctl status --no-pager -n 5 \"$unit\" 2>/dev/null; done; true";
+
+/// Failed units kept, in systemctl's order
+const MAX_FAILED_UNITS: usize = 5;
+
+/// Full process list from procps `ps`; busybox and toybox `ps` reject the flags, so fall back to `top`
+const TOP_COMMAND: &str = "ps -eo pid,pcpu,pmem,comm --sort=-pcpu 2>/dev/null || top -bn1 2>/dev/null; true";
+
+/// TOP_COMMAND without the procps attempt, when `ps` is known to be an applet
+const APPLET_TOP_COMMAND: &str = "top -bn1 2>/dev/null; true";
+
+/// `ip` link and address lines, or `ifconfig` on images without iproute2 / the `ip` applet
+const NETWORK_COMMAND: &str = "(ip -o link && ip -o addr) 2>/dev/null || ifconfig -a 2>/dev/null || true";
+const IFCONFIG_COMMAND: &str = "ifconfig -a 2>/dev/null || true";
+
+/// Each DRM connector's status and preferred mode, then the framebuffer mode for its refresh rate
+const DISPLAY_COMMAND: &str = "for c in /sys/class/drm/card*-*; do [ -f $c/status ] && echo \"__CONNECTOR__ ${c##*/} $(cat $c/status) $(head -n 1 $c/modes 2>/dev/null)\"; done; echo __FBSET__; fbset 2>/dev/null; true";
+
+/// Android keeps /sys/class/drm to itself; `wm size` has the resolution and dumpsys the refresh rate
+const ANDROID_DISPLAY_COMMAND: &str = "wm size 2>/dev/null; dumpsys display 2>/dev/null | grep -o -m 1 \"fps=[0-9.]*\"; true";
+
+/// The first package manager on PATH; apt-get stands for apt
+const PACKAGE_MANAGER_COMMAND: &str = "for p in apt-get dnf yum pacman apk opkg; do command -v $p >/dev/null 2>&1 && echo $p && break; done; true";
+
+/// Two samples of the /proc/stat cpu lines half a second apart; BusyBox builds without
+/// fractional sleep wait a whole second instead
+const CPU_USAGE_COMMAND: &str = "grep ^cpu /proc/stat; sleep 0.5 2>/dev/null || sleep 1; echo __SAMPLE__; grep ^cpu /proc/stat";
+
+/// Runtime overlays in configfs, then the overlays the bootloader is told to apply: Raspberry Pi
+/// config.txt, Armbian's armbianEnv.txt and BeagleBone's uEnv.txt
+const OVERLAYS_COMMAND: &str = "ls /sys/kernel/config/device-tree/overlays 2>/dev/null; echo __CONFIGTXT__; grep -h \"^dtoverlay=\" /boot/firmware/config.txt /boot/config.txt 2>/dev/null; echo __ARMBIANENV__; grep -hE \"^(user_)?overlays=\" /boot/armbianEnv.txt 2>/dev/null; echo __UENV__; grep -hE \"^(uboot_overlay_[a-z0-9_]+|dtb_overlay)=\" /boot/uEnv.txt /boot/firmware/uEnv.txt 2>/dev/null; true";
+
+/// Rows kept from the process list: busiest by CPU, then largest by memory not already among them
+const TOP_BY_CPU: usize = 5;
+const TOP_BY_MEMORY: usize = 3;
+
+/// Collects DRM uevents, loaded modules, registered platform drivers and the GL renderer in one go
+const GPU_COMMAND: &str = "cat /sys/class/drm/card*/device/uevent 2>/dev/null; echo __LSMOD__; lsmod 2>/dev/null; echo __DRIVERS__; ls /sys/bus/platform/drivers 2>/dev/null; echo __GLX__; glxinfo -B 2>/dev/null | grep renderer; true";
+
 pub struct SystemInfoCollector {
     connection_type: String,
     target: String,
     ssh_session: Option<Arc<SSHSession>>,
+    show_progress: bool,
+    collect_gpio: bool,
+    /// Count upgradable packages (`--updates`)
+    check_updates: bool,
+    /// BusyBox / toybox probe, run on the first collection
+    userland: tokio::sync::OnceCell<Userland>,
 }
 
 impl SystemInfoCollector {
@@ -15,136 +141,506 @@ impl SystemInfoCollector {
             connection_type: connection_type.to_string(),
             target: target.to_string(),
             ssh_session: None,
+            show_progress: false,
+            collect_gpio: false,
+            check_updates: false,
+            userland: tokio::sync::OnceCell::new(),
         }
     }
     
-    pub async fn new_with_ssh_session(connection_type: &str, target: &str) -> Result<Self> {
-        let mut collector = Self::new(connection_type, target);
-        
-        if connection_type == "ssh" {
-            let ssh_session = SSHSession::new(target).await?;
-            collector.ssh_session = Some(Arc::new(ssh_session));
+    pub fn new_with_ssh_session(connection_type: &str, target: &str, ssh_session: Arc<SSHSession>) -> Self {
+        Self {
+            connection_type: connection_type.to_string(),
+            target: target.to_string(),
+            ssh_session: Some(ssh_session),
+            show_progress: false,
+            collect_gpio: false,
+            check_updates: false,
+            userland: tokio::sync::OnceCell::new(),
         }
-        
-        Ok(collector)
+    }
+
+    /// Prints a step counter to stderr during batch collection; only for non-TUI output
+    pub fn with_progress(mut self, show_progress: bool) -> Self {
+        self.show_progress = show_progress && std::io::stderr().is_terminal();
+        self
+    }
+
+    /// Also reads GPIO line states (`--gpio`)
+    pub fn with_gpio(mut self, collect_gpio: bool) -> Self {
+        self.collect_gpio = collect_gpio;
+        self
+    }
+
+    /// Also counts upgradable packages (`--updates`); the package manager is always detected
+    pub fn with_updates(mut self, check_updates: bool) -> Self {
+        self.check_updates = check_updates;
+        self
     }
 
     pub async fn collect_system_info(&self) -> Result<SystemInfo> {
         // If we have a persistent SSH session, use batch commands for better performance
-        if let Some(ssh_session) = &self.ssh_session {
-            self.collect_system_info_batch(ssh_session).await
+        let info = if let Some(ssh_session) = &self.ssh_session {
+            self.collect_system_info_batch(ssh_session).await?
         } else {
-            self.collect_system_info_sequential().await
-        }
+            self.collect_system_info_sequential().await?
+        };
+        snapshot::record(&self.target, &info);
+        Ok(info)
     }
     
     async fn collect_system_info_batch(&self, ssh_session: &SSHSession) -> Result<SystemInfo> {
+        let userland = self.userland().await;
+
         // Execute multiple commands in batch for better performance
-        let commands = vec![
-            "uname -a",
+        let mut commands = vec![
+            UNAME_COMMAND,
             "hostname", 
             "cat /proc/device-tree/model 2>/dev/null || echo 'No model'",
             "cat /proc/device-tree/compatible 2>/dev/null || echo 'No compatible'",
             "cat /proc/cpuinfo",
             "cat /proc/meminfo",
             "cat /proc/uptime",
-            "cat /etc/os-release 2>/dev/null || echo 'No os-release'"
+            "cat /etc/os-release 2>/dev/null || echo 'No os-release'",
+            "grep . /sys/devices/system/cpu/cpu*/cpufreq/scaling_cur_freq /sys/devices/system/cpu/cpu*/cpufreq/cpuinfo_max_freq 2>/dev/null || true",
+            THERMAL_COMMAND,
+            "cat /proc/loadavg 2>/dev/null || true",
+            self.network_command(userland),
+            GPU_COMMAND,
+            STORAGE_COMMAND,
+            BOOT_COMMAND,
+            VIRT_COMMAND,
+            FAN_COMMAND,
+            WIFI_COMMAND,
+            self.top_command(userland),
+            MODULES_COMMAND,
+            INIT_COMMAND,
+            ALERTS_COMMAND,
+            PCI_COMMAND,
+            SERIAL_COMMAND,
+            MOUNTS_COMMAND,
+            SWAP_COMMAND,
+            DMI_COMMAND,
+            DISPLAY_COMMAND,
+            PACKAGE_MANAGER_COMMAND,
+            CPU_USAGE_COMMAND,
+            OVERLAYS_COMMAND,
         ];
+        // User probes from collectors.toml run in the same batch, after the built-in commands
+        let builtin_count = commands.len();
+        commands.extend(extra_collectors::configured().iter().map(|collector| collector.command.as_str()));
         
-        let results = ssh_session.execute_multiple_commands(&commands).await?;
+        let total = commands.len();
+        let show_progress = self.show_progress;
+        let progress = |index: usize, command: &str| {
+            if show_progress {
+                let label: String = command.chars().take(PROGRESS_LABEL_WIDTH).collect();
+                eprint!("\r\x1b[2K[{}/{}] {}", index + 1, total, label);
+                let _ = std::io::stderr().flush();
+            }
+        };
+
+        // One exec for the whole batch; one channel per command only if the server won't start the
+        // combined script. Once it ran, missing markers are an error rather than a reason to run
+        // every probe again.
+        if show_progress {
+            eprint!("\r\x1b[2K[batch] {} commands in one round trip", total);
+            let _ = std::io::stderr().flush();
+        }
+        let results = match ssh_session.execute_batched(&commands).await {
+            Err(e) if matches!(e.downcast_ref::<SbcError>(), Some(SbcError::ExecRefused(_))) => {
+                ssh_session.execute_multiple_commands_with_progress(&commands, progress).await
+            }
+            results => results,
+        };
+        if show_progress {
+            // Clear the progress line before anything else is printed
+            eprint!("\r\x1b[2K");
+            let _ = std::io::stderr().flush();
+        }
+        let results = results?;
         
         // Parse results
-        let uname_output = &results[0];
         let hostname = results[1].trim().to_string();
         
         // Parse uname output
-        let parts: Vec<&str> = uname_output.split_whitespace().collect();
-        let kernel = if parts.len() > 2 {
-            format!("{} {}", parts[0], parts[2])
-        } else {
-            parts[0].to_string()
-        };
-        
-        let architecture = if parts.len() > 12 {
-            parts[12].to_string()
+        let (kernel, architecture) = self.parse_uname(&results[0]);
+
+        // Parse chip info from device tree, or DMI on x86 boards
+        let chip = if is_x86(&architecture) {
+            self.parse_x86_chip(&results[26], &results[4])
         } else {
-            "unknown".to_string()
-        };
+            None
+        }
+        .or_else(|| self.parse_chip_from_batch_results(&results[2], &results[3], &results[4]));
 
-        // Parse chip info from device tree
-        let chip = self.parse_chip_from_batch_results(&results[2], &results[3], &results[4]);
+        // Parse the board serial number
+        let serial_number = self.parse_serial_number(&results[23]);
         
         // Parse CPU info
         let cpu_info = self.parse_cpu_from_cpuinfo(&results[4]);
+        let cpu_cores = parse_cpu_cores(&results[4]);
+        let (cpu_usage_percent, cpu_core_usage) = parse_cpu_usage(&results[29]);
         
         // Parse memory info
         let memory = self.parse_memory_from_meminfo(&results[5]);
+        let memory_used_percent = self.parse_memory_used_percent(&results[5]);
+        let memory_total_bytes = meminfo_kib(&results[5], "MemTotal:").map(|kb| kb * 1024);
+        let mem_available = self.parse_mem_available(&results[5]);
+        let mem_cached = parse_mem_cached(&results[5]);
+        let swap = self.parse_swap(&results[5], &results[25]);
         
         // Parse uptime
         let uptime = self.parse_uptime_from_proc(&results[6]);
+        let uptime_seconds = parse_uptime_seconds(&results[6]);
         
         // Parse OS info
         let os_info = self.parse_os_from_release(&results[7]);
 
+        // Parse per-core CPU frequencies
+        let cpu_freqs = self.parse_cpu_freqs(&results[8]);
+
+        // Parse thermal zones and the SoC temperature
+        let thermal_zones = self.parse_thermal_zones(&results[9]);
+        let temperature = tui::selected_thermal_zone(&thermal_zones).map(ThermalZone::celsius);
+
+        // Parse load average
+        let load_average = self.parse_loadavg(&results[10]);
+
+        // Parse network interfaces
+        let mut network = self.parse_network(&results[11]);
+        self.attach_wifi(&mut network, &results[17]);
+
+        // Parse GPU / VPU from drivers, falling back to the SoC's typical GPU
+        let (gpu, vpu) = self.parse_gpu_vpu(&results[12], &format!("{} {}", results[3], chip.as_deref().unwrap_or("")));
+
+        // Parse block devices and SD/eMMC wear
+        let storage_devices = self.parse_storage(&results[13]);
+
+        // Parse mounted filesystems and their usage
+        let mounts = self.parse_mounts(&results[24]);
+
+        // Parse connected displays
+        let display = self.parse_display(&results[27]);
+
+        // Parse kernel cmdline, root device and bootloader
+        let boot_info = self.parse_boot_info(&results[14]);
+
+        // Parse configured device-tree overlays
+        let overlays = parse_overlays(&results[30]);
+
+        // Parse container / VM detection
+        let virtualization = self.parse_virtualization(&results[15]);
+
+        // Parse fan speeds and cooling states
+        let fans = self.parse_fans(&results[16]);
+
+        // Parse the init system
+        let init_system = self.parse_init_system(&results[20]);
+        let userland_applets = userland.describe();
+
+        // Parse OOM / panic / call trace events
+        let alerts = self.parse_alerts(&results[21]);
+
+        // Failed units need systemctl, so only ask systemd boards
+        let failed_units = if init_system.as_deref() == Some("systemd") {
+            match ssh_session.execute_command(FAILED_UNITS_COMMAND).await {
+                Ok(output) => self.parse_failed_units(&output),
+                Err(_) => Vec::new(),
+            }
+        } else {
+            Vec::new()
+        };
+
+        // Parse PCIe devices
+        let pci_devices = self.parse_pci_devices(&results[22]);
+
+        // Parse the busiest processes
+        let top_processes = self.parse_top_processes(&results[18]);
+
+        // Parse loaded kernel modules
+        let modules = self.parse_modules(&results[19]);
+
+        // Extract the user probe values
+        let extra = extra_collectors::configured()
+            .iter()
+            .zip(&results[builtin_count..])
+            .map(|(collector, output)| ExtraValue {
+                label: collector.label.clone(),
+                // A failed probe has no value; its error text must not pass for one
+                value: if output.starts_with(FAILED_COMMAND_PREFIX) { None } else { collector.extract(output) },
+            })
+            .collect();
+
+        // Only Raspberry Pi firmware has vcgencmd, so skip the round trip elsewhere
+        let power_status = if self.parse_chip_from_output(&results[2]).as_deref() == Some("Raspberry Pi") {
+            match ssh_session.execute_command(POWER_COMMAND).await {
+                Ok(output) => self.parse_power_status(&output),
+                Err(_) => None,
+            }
+        } else {
+            None
+        };
+
+        // The batch only runs over SSH, and Linux boards have no dumpsys battery
+        let battery = None;
+
+        // The update count reads the package lists, which can take seconds, so it runs separately
+        let pending_updates = self.get_pending_updates(&results[28]).await;
+
+        // GPIO is opt-in, so it isn't part of the batch
+        let gpio = if self.collect_gpio {
+            match ssh_session.execute_command(GPIO_COMMAND).await {
+                Ok(output) => self.parse_gpio(&output),
+                Err(_) => Vec::new(),
+            }
+        } else {
+            Vec::new()
+        };
+
         Ok(SystemInfo {
             hostname,
             kernel,
             architecture,
             chip,
+            serial_number,
+            gpu,
+            vpu,
+            display,
             cpu_info,
+            cpu_freqs,
+            cpu_cores,
+            cpu_usage_percent,
+            cpu_core_usage,
             memory,
+            memory_used_percent,
+            memory_total_bytes,
+            mem_available,
+            mem_cached,
+            swap,
             uptime,
+            uptime_seconds,
+            load_average,
             os_info,
+            init_system,
+            userland: userland_applets,
+            pending_updates,
+            virtualization,
+            temperature,
+            thermal_zones,
+            fans,
+            network,
+            power_status,
+            battery,
+            storage_devices,
+            mounts,
+            pci_devices,
+            modules,
+            alerts,
+            failed_units,
+            top_processes,
+            gpio,
+            extra,
+            boot_info,
+            overlays,
         })
     }
     
     async fn collect_system_info_sequential(&self) -> Result<SystemInfo> {
-        let uname_output = self.execute_command("uname -a").await?;
+        let uname_output = match self.execute_command(UNAME_COMMAND).await {
+            Ok(output) => output,
+            Err(_) => self.execute_command("uname -a").await?,
+        };
         let hostname = self.execute_command("hostname").await?.trim().to_string();
         
         // Parse uname output
-        let parts: Vec<&str> = uname_output.split_whitespace().collect();
-        let kernel = if parts.len() > 2 {
-            format!("{} {}", parts[0], parts[2])
-        } else {
-            parts[0].to_string()
-        };
-        
-        let architecture = if parts.len() > 12 {
-            parts[12].to_string()
-        } else {
-            "unknown".to_string()
+        let (kernel, architecture) = self.parse_uname(&uname_output);
+
+        // Get chip information from device tree, or DMI on x86 boards
+        let chip = match self.get_x86_chip_info(&architecture).await {
+            Some(chip) => Some(chip),
+            None => self.get_chip_info().await.ok(),
         };
 
-        // Get chip information from device tree
-        let chip = self.get_chip_info().await.ok();
+        // Get the board serial number
+        let serial_number = self.get_serial_number().await;
 
         // Get CPU information
         let cpu_info = self.get_cpu_info().await.unwrap_or_else(|_| "Unknown".to_string());
 
+        // Get per-core CPU frequencies (empty when cpufreq isn't exposed)
+        let cpu_freqs = self.get_cpu_freqs().await.unwrap_or_default();
+
+        // Count cores for metrics
+        let cpu_cores = self.execute_command("cat /proc/cpuinfo").await.ok().and_then(|cpuinfo| parse_cpu_cores(&cpuinfo));
+
+        // Sample CPU utilization
+        let (cpu_usage_percent, cpu_core_usage) = match self.execute_command(CPU_USAGE_COMMAND).await {
+            Ok(output) => parse_cpu_usage(&output),
+            Err(_) => (None, Vec::new()),
+        };
+
         // Get memory information
         let memory = self.get_memory_info().await.unwrap_or_else(|_| "Unknown".to_string());
+        let memory_used_percent = self.get_memory_used_percent().await;
+        let meminfo = self.execute_command("cat /proc/meminfo").await.unwrap_or_default();
+        let memory_total_bytes = meminfo_kib(&meminfo, "MemTotal:").map(|kb| kb * 1024);
+        let mem_cached = parse_mem_cached(&meminfo);
+        let (mem_available, swap) = self.get_swap_info().await;
 
         // Get uptime
         let uptime = self.get_uptime().await.unwrap_or_else(|_| "Unknown".to_string());
+        let uptime_seconds = self.execute_command("cat /proc/uptime").await.ok().and_then(|uptime| parse_uptime_seconds(&uptime));
+
+        // Get load average
+        let load_average = self.get_load_average().await;
 
         // Get OS information
         let os_info = self.get_os_info().await.unwrap_or_else(|_| "Unknown".to_string());
 
+        // Get thermal zones and the SoC temperature
+        let thermal_zones = self.get_thermal_zones().await.unwrap_or_default();
+        let temperature = tui::selected_thermal_zone(&thermal_zones).map(ThermalZone::celsius);
+
+        // Get network interfaces
+        let mut network = self.get_network_info().await.unwrap_or_default();
+        if let Ok(output) = self.execute_command(WIFI_COMMAND).await {
+            self.attach_wifi(&mut network, &output);
+        }
+
+        // Get GPU / VPU information
+        let (gpu, vpu) = self.get_gpu_info(chip.as_deref()).await;
+
+        // Get Raspberry Pi power / throttling status
+        let power_status = self.get_power_status().await;
+
+        // Get the battery state (Android only; Linux boards have no dumpsys)
+        let battery = if self.connection_type == "adb" {
+            self.get_battery().await
+        } else {
+            None
+        };
+
+        // Get block devices and SD/eMMC wear
+        let storage_devices = self.get_storage_devices().await.unwrap_or_default();
+
+        // Get mounted filesystems and their usage
+        let mounts = self.get_mounts().await.unwrap_or_default();
+
+        // Get connected displays
+        let display = self.get_display().await;
+
+        // Get kernel cmdline, root device and bootloader
+        let boot_info = self.get_boot_info().await.unwrap_or_default();
+
+        // Get configured device-tree overlays
+        let overlays = self.get_overlays().await.unwrap_or_default();
+
+        // Detect containers and VMs
+        let virtualization = self.get_virtualization().await;
+
+        // Get fan speeds and cooling states
+        let fans = self.get_fans().await.unwrap_or_default();
+
+        // Detect the init system
+        let init_system = self.get_init_system().await;
+
+        // Note BusyBox / toybox applets standing in for the usual tools
+        let userland_applets = self.userland().await.describe();
+
+        // Get OOM / panic / call trace events
+        let alerts = self.get_alerts().await.unwrap_or_default();
+
+        // Get failed units on systemd boards
+        let failed_units = if init_system.as_deref() == Some("systemd") {
+            self.get_failed_units().await.unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+
+        // Get PCIe devices
+        let pci_devices = self.get_pci_devices().await.unwrap_or_default();
+
+        // Get the busiest processes
+        let top_processes = self.get_top_processes().await.unwrap_or_default();
+
+        // Get loaded kernel modules
+        let modules = self.get_modules().await.unwrap_or_default();
+
+        // Run the user probes from collectors.toml
+        let mut extra = Vec::new();
+        for collector in extra_collectors::configured() {
+            let output = self.execute_command(&collector.command).await;
+            extra.push(ExtraValue {
+                label: collector.label.clone(),
+                value: output.ok().and_then(|output| collector.extract(&output)),
+            });
+        }
+
+        // Detect the package manager, and count updates when requested
+        let package_manager = self.execute_command(PACKAGE_MANAGER_COMMAND).await.unwrap_or_default();
+        let pending_updates = self.get_pending_updates(&package_manager).await;
+
+        // Get GPIO line states when requested
+        let gpio = if self.collect_gpio {
+            self.get_gpio().await.unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+
         Ok(SystemInfo {
             hostname,
             kernel,
             architecture,
             chip,
+            serial_number,
+            gpu,
+            vpu,
+            display,
             cpu_info,
+            cpu_freqs,
+            cpu_cores,
+            cpu_usage_percent,
+            cpu_core_usage,
             memory,
+            memory_used_percent,
+            memory_total_bytes,
+            mem_available,
+            mem_cached,
+            swap,
             uptime,
+            uptime_seconds,
+            load_average,
             os_info,
+            init_system,
+            userland: userland_applets,
+            pending_updates,
+            virtualization,
+            temperature,
+            thermal_zones,
+            fans,
+            network,
+            power_status,
+            battery,
+            storage_devices,
+            mounts,
+            pci_devices,
+            modules,
+            alerts,
+            failed_units,
+            top_processes,
+            gpio,
+            extra,
+            boot_info,
+            overlays,
         })
     }
 
     async fn execute_command(&self, command: &str) -> Result<String> {
-        match self.connection_type.as_str() {
+        verbose::command(&self.connection_type, &self.target, command);
+
+        let started = std::time::Instant::now();
+        let result = match self.connection_type.as_str() {
             "ssh" => {
                 if let Some(ssh_session) = &self.ssh_session {
                     // Use persistent SSH session
@@ -155,7 +651,41 @@ impl SystemInfoCollector {
                 }
             },
             "adb" => self.execute_adb_command(command).await,
-            _ => Err(anyhow::anyhow!("Unknown connection type: {}", self.connection_type)),
+            _ => Err(SbcError::UnknownConnectionType(self.connection_type.clone()).into()),
+        };
+        timing::record(command, started.elapsed());
+
+        if let Ok(output) = &result {
+            verbose::output(&self.connection_type, &self.target, output);
+        }
+        result
+    }
+
+    /// What the target's `ps`, `ip`, `free` ... are, probed once per collector. A failed probe
+    /// leaves everything unknown, and the commands keep their `||` fallbacks for that case.
+    async fn userland(&self) -> &Userland {
+        self.userland
+            .get_or_init(|| async {
+                let output = self.execute_command(userland::PROBE_COMMAND).await.unwrap_or_default();
+                Userland::parse(&output)
+            })
+            .await
+    }
+
+    /// BusyBox and toybox `ps` reject the procps flags, so skip straight to `top`
+    fn top_command(&self, userland: &Userland) -> &'static str {
+        if userland.applet_of("ps").is_some() {
+            APPLET_TOP_COMMAND
+        } else {
+            TOP_COMMAND
+        }
+    }
+
+    fn network_command(&self, userland: &Userland) -> &'static str {
+        if userland.lacks("ip") && !userland.lacks("ifconfig") {
+            IFCONFIG_COMMAND
+        } else {
+            NETWORK_COMMAND
         }
     }
 
@@ -163,37 +693,8 @@ impl SystemInfoCollector {
         use std::process::Command;
         
         
-        // Parse target to get user@host
-        let (user, host) = if let Some((u, h)) = self.target.split_once('@') {
-            (u.to_string(), h.to_string())
-        } else {
-            // Try to resolve alias using ssh -G
-            if let Ok(output) = Command::new("ssh").arg("-G").arg(&self.target).output() {
-                if output.status.success() {
-                    let stdout = String::from_utf8_lossy(&output.stdout);
-                    let mut resolved_host = None;
-                    let mut resolved_user = None;
-                    
-                    for line in stdout.lines() {
-                        if let Some(rest) = line.strip_prefix("hostname ") {
-                            resolved_host = Some(rest.trim().to_string());
-                        } else if let Some(rest) = line.strip_prefix("user ") {
-                            resolved_user = Some(rest.trim().to_string());
-                        }
-                    }
-                    
-                    let host = resolved_host.unwrap_or(self.target.clone());
-                    let user = resolved_user.unwrap_or_else(|| {
-                        std::env::var("USER").unwrap_or_else(|_| "root".to_string())
-                    });
-                    (user, host)
-                } else {
-                    return Err(anyhow::anyhow!("Failed to resolve SSH alias: {}", self.target));
-                }
-            } else {
-                return Err(anyhow::anyhow!("SSH command not available"));
-            }
-        };
+        // Resolve user, host and port once per target (cached across collectors)
+        let resolved = ssh_resolver::resolve(&self.target)?;
 
         // Execute command via SSH with timeout and terminal reset
         let output = Command::new("ssh")
@@ -211,58 +712,27 @@ impl SystemInfoCollector {
             .arg("StrictHostKeyChecking=no")
             .arg("-o")
             .arg("UserKnownHostsFile=/dev/null")
-            .arg(&format!("{}@{}", user, host))
+            .args(ssh_resolver::external_args())
+            .arg("-p")
+            .arg(resolved.port.to_string())
+            .arg(&format!("{}@{}", resolved.user, resolved.host))
             .arg(&format!("timeout 30 bash -c '{}'", command))
             .output()?;
 
         if output.status.success() {
             Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
         } else {
-            Err(anyhow::anyhow!("SSH command failed: {}", String::from_utf8_lossy(&output.stderr)))
-        }
-    }
-
-    async fn execute_adb_command(&self, command: &str) -> Result<String> {
-        use std::process::Command;
-        
-        if self.target == "auto" {
-            // Try to find a device automatically
-            if let Ok(output) = Command::new("adb").arg("devices").output() {
-                let stdout = String::from_utf8_lossy(&output.stdout);
-                let lines: Vec<&str> = stdout.lines().collect();
-                
-                // Find first device that's not "List of devices attached"
-                for line in lines {
-                    if line.contains("\tdevice") {
-                        if let Some(serial) = line.split('\t').next() {
-                            if !serial.is_empty() {
-                                return self.execute_adb_command_with_serial(serial, command).await;
-                            }
-                        }
-                    }
-                }
+            Err(SbcError::CommandFailed {
+                exit_status: output.status.code().unwrap_or(-1),
+                output: String::from_utf8_lossy(&output.stderr).into_owned(),
             }
-            Err(anyhow::anyhow!("No ADB devices found"))
-        } else {
-            self.execute_adb_command_with_serial(&self.target, command).await
+            .into())
         }
     }
 
-    async fn execute_adb_command_with_serial(&self, serial: &str, command: &str) -> Result<String> {
-        use std::process::Command;
-        
-        let output = Command::new("adb")
-            .arg("-s")
-            .arg(serial)
-            .arg("shell")
-            .arg(command)
-            .output()?;
-
-        if output.status.success() {
-            Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
-        } else {
-            Err(anyhow::anyhow!("ADB command failed: {}", String::from_utf8_lossy(&output.stderr)))
-        }
+    async fn execute_adb_command(&self, command: &str) -> Result<String> {
+        // Talk to adbd natively through adb_client, no external adb binary needed
+        ADBSession::new(&self.target).execute_command(command).await
     }
 
     async fn get_chip_info(&self) -> Result<String> {
@@ -306,7 +776,14 @@ impl SystemInfoCollector {
                 return Ok(board.trim().to_string());
             }
         } else {
-            // For Linux systems, try to read from device tree first
+            // For Linux systems, a known SoC in the device tree wins
+            if let Ok(compatible) = self.execute_command("cat /proc/device-tree/compatible 2>/dev/null").await {
+                if let Some(chip) = soc_db::lookup(&compatible) {
+                    return Ok(chip);
+                }
+            }
+
+            // Then the device tree model
             if let Ok(model) = self.execute_command("cat /proc/device-tree/model 2>/dev/null").await {
                 let model_trimmed = model.trim();
                 if !model_trimmed.is_empty() && model_trimmed != "No model" {
@@ -335,6 +812,48 @@ impl SystemInfoCollector {
         Err(anyhow::anyhow!("Could not determine chip information"))
     }
 
+    async fn get_x86_chip_info(&self, architecture: &str) -> Option<String> {
+        if self.connection_type == "adb" || !is_x86(architecture) {
+            return None;
+        }
+        let dmi = self.execute_command(DMI_COMMAND).await.ok()?;
+        let cpuinfo = self.execute_command("cat /proc/cpuinfo").await.unwrap_or_default();
+        self.parse_x86_chip(&dmi, &cpuinfo)
+    }
+
+    /// "vendor product (CPU model)" from DMI and cpuinfo, e.g. "HARDKERNEL ODROID-H3 (Intel(R) Celeron(R) N5105 @ 2.00GHz)".
+    /// Many boards only fill in the baseboard fields, so those stand in for placeholder system fields.
+    fn parse_x86_chip(&self, dmi: &str, cpuinfo: &str) -> Option<String> {
+        let field = |name: &str| {
+            dmi.lines()
+                .find_map(|line| line.strip_prefix(name)?.strip_prefix('='))
+                .map(str::trim)
+                .filter(|value| !value.is_empty() && !DMI_PLACEHOLDERS.iter().any(|placeholder| placeholder.eq_ignore_ascii_case(value)))
+        };
+        let vendor = field("sys_vendor").or_else(|| field("board_vendor"));
+        let product = field("product_name").or_else(|| field("board_name"));
+        let cpu = cpuinfo
+            .lines()
+            .find(|line| line.starts_with("model name"))
+            .and_then(|line| line.split_once(':'))
+            .map(|(_, model)| model.trim())
+            .filter(|model| !model.is_empty());
+
+        let board = match (vendor, product) {
+            // Product names often repeat the vendor, e.g. "Intel Corporation" / "Intel NUC..."
+            (Some(vendor), Some(product)) if product.starts_with(vendor.split_whitespace().next().unwrap_or(vendor)) => {
+                Some(product.to_string())
+            }
+            (Some(vendor), Some(product)) => Some(format!("{} {}", vendor, product)),
+            (vendor, product) => vendor.or(product).map(str::to_string),
+        };
+        match (board, cpu) {
+            (Some(board), Some(cpu)) => Some(format!("{} ({})", board, cpu)),
+            (Some(board), None) => Some(board),
+            (None, cpu) => cpu.map(str::to_string),
+        }
+    }
+
     fn parse_chip_from_output(&self, output: &str) -> Option<String> {
         let output = output.trim();
         
@@ -373,6 +892,10 @@ impl SystemInfoCollector {
     fn parse_chip_from_compatible(&self, compatible: &str) -> Option<String> {
         // Parse device tree compatible string
         // Example: "rockchip,rk3399-rockpro64\0rockchip,rk3399"
+        if let Some(chip) = soc_db::lookup(compatible) {
+            return Some(chip);
+        }
+
         let compatible = compatible.replace('\0', " ");
         
         // Common SBC patterns
@@ -512,28 +1035,19 @@ impl SystemInfoCollector {
         Ok(cpu_desc)
     }
 
+    async fn get_cpu_freqs(&self) -> Result<Vec<CpuFreq>> {
+        let output = self.execute_command("grep . /sys/devices/system/cpu/cpu*/cpufreq/scaling_cur_freq /sys/devices/system/cpu/cpu*/cpufreq/cpuinfo_max_freq 2>/dev/null").await?;
+        Ok(self.parse_cpu_freqs(&output))
+    }
+
     async fn get_memory_info(&self) -> Result<String> {
         if self.connection_type == "adb" {
             // For Android, try to use the free command first
             if let Ok(free_output) = self.execute_command("free").await {
-                // Parse free output: "total        used        free      shared     buffers"
-                // "Mem:       2005991424  1791692800   214298624     2093056    36106240"
-                for line in free_output.lines() {
-                    if line.starts_with("Mem:") {
-                        let parts: Vec<&str> = line.split_whitespace().collect();
-                        if parts.len() > 1 {
-                            if let Ok(total_kb) = parts[1].parse::<u64>() {
-                                let mb = total_kb / 1024;
-                                let gb = mb / 1024;
-                                
-                                if gb > 0 {
-                                    return Ok(format!("{} GB", gb));
-                                } else {
-                                    return Ok(format!("{} MB", mb));
-                                }
-                            }
-                        }
-                    }
+                // toybox `free` counts bytes, BusyBox and procps `free` (without -h) KiB
+                let unit = if self.userland().await.applet_of("free") == Some("toybox") { 1024 } else { 1 };
+                if let Some(memory) = memory_from_free(&free_output, unit) {
+                    return Ok(memory);
                 }
             }
             
@@ -645,63 +1159,252 @@ impl SystemInfoCollector {
         }
     }
 
-    async fn get_os_info(&self) -> Result<String> {
-        if self.connection_type == "adb" {
-            // For Android, get build info
-            let mut os_info = String::new();
-            
-            // Get Android version
-            if let Ok(version) = self.execute_command("getprop ro.build.version.release").await {
-                os_info.push_str(&format!("Android {}", version.trim()));
-            }
-            
-            // Get build number
-            if let Ok(build) = self.execute_command("getprop ro.build.display.id").await {
-                if !build.trim().is_empty() {
-                    if !os_info.is_empty() {
-                        os_info.push_str(" ");
-                    }
-                    os_info.push_str(&format!("({})", build.trim()));
-                }
-            }
-            
-            if !os_info.is_empty() {
-                return Ok(os_info);
-            }
-        } else {
-            // For Linux systems, try to get OS info from various sources
-            let sources = vec![
-                ("cat /etc/os-release", "PRETTY_NAME"),
-                ("cat /etc/lsb-release", "DISTRIB_DESCRIPTION"),
-                ("uname -o", ""),
-            ];
+    async fn get_thermal_zones(&self) -> Result<Vec<ThermalZone>> {
+        let output = self.execute_command(THERMAL_COMMAND).await?;
+        Ok(self.parse_thermal_zones(&output))
+    }
 
-            for (command, key) in sources {
-                if let Ok(output) = self.execute_command(command).await {
-                    if key.is_empty() {
-                        return Ok(output.trim().to_string());
-                    } else {
-                        for line in output.lines() {
-                            if line.starts_with(key) {
-                                let parts: Vec<&str> = line.split('=').collect();
-                                if parts.len() > 1 {
-                                    let value = parts[1].trim_matches('"');
-                                    return Ok(value.to_string());
-                                }
-                            }
-                        }
-                    }
-                }
-            }
+    async fn get_power_status(&self) -> Option<String> {
+        let model = self.execute_command("cat /proc/device-tree/model 2>/dev/null").await.ok()?;
+        if self.parse_chip_from_output(&model).as_deref() != Some("Raspberry Pi") {
+            return None;
         }
 
-        Ok("Unknown".to_string())
+        let output = self.execute_command(POWER_COMMAND).await.ok()?;
+        self.parse_power_status(&output)
     }
-    
-    // Batch parsing methods for better performance
-    fn parse_chip_from_batch_results(&self, model: &str, compatible: &str, cpuinfo: &str) -> Option<String> {
-        // Try device tree model first
-        if !model.trim().is_empty() && model.trim() != "No model" {
+
+    async fn get_battery(&self) -> Option<BatteryStatus> {
+        let output = self.execute_command("dumpsys battery").await.ok()?;
+        self.parse_battery(&output)
+    }
+
+    async fn get_memory_used_percent(&self) -> Option<f64> {
+        let meminfo = self.execute_command("cat /proc/meminfo").await.ok()?;
+        self.parse_memory_used_percent(&meminfo)
+    }
+
+    /// MemAvailable and the swap summary
+    async fn get_swap_info(&self) -> (Option<String>, String) {
+        let meminfo = self.execute_command("cat /proc/meminfo").await.unwrap_or_default();
+        let swaps = self.execute_command(SWAP_COMMAND).await.unwrap_or_default();
+        (self.parse_mem_available(&meminfo), self.parse_swap(&meminfo, &swaps))
+    }
+
+    async fn get_load_average(&self) -> Option<(f64, f64, f64)> {
+        if let Ok(loadavg) = self.execute_command("cat /proc/loadavg").await {
+            if let Some(load) = self.parse_loadavg(&loadavg) {
+                return Some(load);
+            }
+        }
+
+        // Fallback for Android shells where /proc/loadavg isn't readable
+        let uptime_output = self.execute_command("uptime").await.ok()?;
+        self.parse_load_from_uptime(&uptime_output)
+    }
+
+    async fn get_gpu_info(&self, chip: Option<&str>) -> (Option<String>, Option<String>) {
+        let output = self.execute_command(GPU_COMMAND).await.unwrap_or_default();
+        let compatible = self
+            .execute_command("cat /proc/device-tree/compatible 2>/dev/null")
+            .await
+            .unwrap_or_default();
+        self.parse_gpu_vpu(&output, &format!("{} {}", compatible, chip.unwrap_or("")))
+    }
+
+    async fn get_network_info(&self) -> Result<Vec<NetIface>> {
+        let command = if self.connection_type == "adb" {
+            "ip addr"
+        } else {
+            self.network_command(self.userland().await)
+        };
+        let output = self.execute_command(command).await?;
+        Ok(self.parse_network(&output))
+    }
+
+    async fn get_storage_devices(&self) -> Result<Vec<StorageDevice>> {
+        let output = self.execute_command(STORAGE_COMMAND).await?;
+        Ok(self.parse_storage(&output))
+    }
+
+    async fn get_mounts(&self) -> Result<Vec<MountInfo>> {
+        let output = self.execute_command(MOUNTS_COMMAND).await?;
+        Ok(self.parse_mounts(&output))
+    }
+
+    /// Package manager from `PACKAGE_MANAGER_COMMAND` output, with the update count when `--updates` is on
+    async fn get_pending_updates(&self, detection: &str) -> Option<PendingUpdates> {
+        let manager = match detection.trim() {
+            "" => return None,
+            "apt-get" => "apt",
+            manager => manager,
+        };
+        let count = match updates_command(manager).filter(|_| self.check_updates) {
+            Some(command) => self.execute_command(command).await.ok().map(|output| count_pending_updates(manager, &output)),
+            None => None,
+        };
+        Some(PendingUpdates { manager: manager.to_string(), count })
+    }
+
+    async fn get_display(&self) -> Option<String> {
+        let command = if self.connection_type == "adb" { ANDROID_DISPLAY_COMMAND } else { DISPLAY_COMMAND };
+        let output = self.execute_command(command).await.ok()?;
+        self.parse_display(&output)
+    }
+
+    async fn get_serial_number(&self) -> Option<String> {
+        let output = self.execute_command(SERIAL_COMMAND).await.ok()?;
+        self.parse_serial_number(&output)
+    }
+
+    async fn get_pci_devices(&self) -> Result<Vec<String>> {
+        let output = self.execute_command(PCI_COMMAND).await?;
+        Ok(self.parse_pci_devices(&output))
+    }
+
+    async fn get_alerts(&self) -> Result<Vec<KernelAlert>> {
+        let output = self.execute_command(ALERTS_COMMAND).await?;
+        Ok(self.parse_alerts(&output))
+    }
+
+    async fn get_failed_units(&self) -> Result<Vec<FailedUnit>> {
+        let output = self.execute_command(FAILED_UNITS_COMMAND).await?;
+        Ok(self.parse_failed_units(&output))
+    }
+
+    async fn get_modules(&self) -> Result<Vec<KernelModule>> {
+        let output = self.execute_command(MODULES_COMMAND).await?;
+        Ok(self.parse_modules(&output))
+    }
+
+    async fn get_top_processes(&self) -> Result<Vec<ProcessInfo>> {
+        let output = self.execute_command(self.top_command(self.userland().await)).await?;
+        Ok(self.parse_top_processes(&output))
+    }
+
+    async fn get_fans(&self) -> Result<Vec<FanReading>> {
+        let output = self.execute_command(FAN_COMMAND).await?;
+        Ok(self.parse_fans(&output))
+    }
+
+    async fn get_virtualization(&self) -> Option<String> {
+        let output = self.execute_command(VIRT_COMMAND).await.ok()?;
+        self.parse_virtualization(&output)
+    }
+
+    /// Also used by the log collector to pick a log source
+    pub async fn get_init_system(&self) -> Option<String> {
+        let output = self.execute_command(INIT_COMMAND).await.ok()?;
+        self.parse_init_system(&output)
+    }
+
+    async fn get_boot_info(&self) -> Result<BootInfo> {
+        let output = self.execute_command(BOOT_COMMAND).await?;
+        Ok(self.parse_boot_info(&output))
+    }
+
+    async fn get_overlays(&self) -> Result<Vec<String>> {
+        let output = self.execute_command(OVERLAYS_COMMAND).await?;
+        Ok(parse_overlays(&output))
+    }
+
+    async fn get_gpio(&self) -> Result<Vec<GpioLine>> {
+        let output = self.execute_command(GPIO_COMMAND).await?;
+        Ok(self.parse_gpio(&output))
+    }
+
+    async fn get_os_info(&self) -> Result<String> {
+        if self.connection_type == "adb" {
+            // For Android, get build info
+            let mut os_info = String::new();
+            
+            // Get Android version
+            if let Ok(version) = self.execute_command("getprop ro.build.version.release").await {
+                os_info.push_str(&format!("Android {}", version.trim()));
+            }
+            
+            // Get build number
+            if let Ok(build) = self.execute_command("getprop ro.build.display.id").await {
+                if !build.trim().is_empty() {
+                    if !os_info.is_empty() {
+                        os_info.push_str(" ");
+                    }
+                    os_info.push_str(&format!("({})", build.trim()));
+                }
+            }
+            
+            if !os_info.is_empty() {
+                return Ok(os_info);
+            }
+        } else {
+            // For Linux systems, try to get OS info from various sources
+            let sources = vec![
+                ("cat /etc/os-release", "PRETTY_NAME"),
+                ("cat /etc/lsb-release", "DISTRIB_DESCRIPTION"),
+                ("uname -o", ""),
+            ];
+
+            for (command, key) in sources {
+                if let Ok(output) = self.execute_command(command).await {
+                    if key.is_empty() {
+                        return Ok(output.trim().to_string());
+                    } else {
+                        for line in output.lines() {
+                            if line.starts_with(key) {
+                                let parts: Vec<&str> = line.split('=').collect();
+                                if parts.len() > 1 {
+                                    let value = parts[1].trim_matches('"');
+                                    return Ok(value.to_string());
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok("Unknown".to_string())
+    }
+    
+    fn parse_uname(&self, output: &str) -> (String, String) {
+        let lines: Vec<&str> = output.lines().map(|line| line.trim()).filter(|line| !line.is_empty()).collect();
+
+        // Expected: "Linux\n6.1.0-rockchip\naarch64" from UNAME_COMMAND
+        if lines.len() == 3 && lines.iter().all(|line| !line.contains(' ')) {
+            return (format!("{} {}", lines[0], lines[1]), lines[2].to_string());
+        }
+
+        // Fallback for a plain `uname -a` line: look for a known machine name instead of a fixed index
+        let parts: Vec<&str> = output.split_whitespace().collect();
+        let kernel = match parts.len() {
+            0 => "Unknown".to_string(),
+            1 | 2 => parts[0].to_string(),
+            _ => format!("{} {}", parts[0], parts[2]),
+        };
+        let known_arches = [
+            "aarch64", "arm64", "armv8l", "armv7l", "armv6l", "armv5tel", "x86_64", "i686", "i386",
+            "riscv64", "mips", "mipsel", "mips64", "ppc64le", "loongarch64",
+        ];
+        let architecture = parts
+            .iter()
+            .skip(3)
+            .rev()
+            .find(|part| known_arches.contains(part))
+            .map(|part| part.to_string())
+            .unwrap_or_else(|| "unknown".to_string());
+
+        (kernel, architecture)
+    }
+
+    // Batch parsing methods for better performance
+    fn parse_chip_from_batch_results(&self, model: &str, compatible: &str, cpuinfo: &str) -> Option<String> {
+        // Known SoCs get their marketing name and core layout
+        if let Some(chip) = soc_db::lookup(compatible) {
+            return Some(chip);
+        }
+
+        // Try device tree model
+        if !model.trim().is_empty() && model.trim() != "No model" {
             return Some(model.trim().to_string());
         }
         
@@ -780,6 +1483,33 @@ impl SystemInfoCollector {
         cpu_desc
     }
     
+    fn parse_cpu_freqs(&self, output: &str) -> Vec<CpuFreq> {
+        // grep output: /sys/devices/system/cpu/cpu0/cpufreq/scaling_cur_freq:1416000 (values in kHz)
+        let mut freqs: std::collections::BTreeMap<usize, (u64, u64)> = std::collections::BTreeMap::new();
+
+        for line in output.lines() {
+            let Some((path, value)) = line.rsplit_once(':') else { continue };
+            let Ok(khz) = value.trim().parse::<u64>() else { continue };
+
+            let core = path
+                .split('/')
+                .find_map(|part| part.strip_prefix("cpu").and_then(|n| n.parse::<usize>().ok()));
+            let Some(core) = core else { continue };
+
+            let entry = freqs.entry(core).or_insert((0, 0));
+            if path.ends_with("scaling_cur_freq") {
+                entry.0 = khz / 1000;
+            } else if path.ends_with("cpuinfo_max_freq") {
+                entry.1 = khz / 1000;
+            }
+        }
+
+        freqs
+            .into_iter()
+            .map(|(core, (cur_mhz, max_mhz))| CpuFreq { core, cur_mhz, max_mhz })
+            .collect()
+    }
+    
     fn parse_memory_from_meminfo(&self, meminfo: &str) -> String {
         for line in meminfo.lines() {
             if line.starts_with("MemTotal") {
@@ -800,6 +1530,71 @@ impl SystemInfoCollector {
         "Unknown".to_string()
     }
     
+    fn parse_memory_used_percent(&self, meminfo: &str) -> Option<f64> {
+        // MemAvailable (kernel 3.14+) counts reclaimable cache as free, unlike MemFree
+        let field = |name: &str| {
+            meminfo
+                .lines()
+                .find(|line| line.starts_with(name))
+                .and_then(|line| line.split_whitespace().nth(1))
+                .and_then(|kb| kb.parse::<f64>().ok())
+        };
+        let total = field("MemTotal:").filter(|total| *total > 0.0)?;
+        let available = field("MemAvailable:")?;
+        Some(((total - available) / total * 100.0).clamp(0.0, 100.0))
+    }
+
+    fn parse_mem_available(&self, meminfo: &str) -> Option<String> {
+        meminfo_kib(meminfo, "MemAvailable:").map(format_kib)
+    }
+
+    /// Totals from SwapTotal / SwapFree, devices from /proc/swaps; zram devices are named with
+    /// their active compression algorithm (the bracketed one in `comp_algorithm`)
+    fn parse_swap(&self, meminfo: &str, output: &str) -> String {
+        let total = meminfo_kib(meminfo, "SwapTotal:").unwrap_or(0);
+        if total == 0 {
+            return "none".to_string();
+        }
+        let free = meminfo_kib(meminfo, "SwapFree:").unwrap_or(total).min(total);
+        let summary = format!("{} ({:.0}% used)", format_kib(total), (total - free) as f64 / total as f64 * 100.0);
+
+        let (swaps, zram) = output.split_once("__ZRAM__").unwrap_or((output, ""));
+        // "zram0 2147483648 lzo lzo-rle [lz4] zstd"
+        let zram_algorithm = |name: &str| {
+            zram.lines().find_map(|line| {
+                let mut fields = line.split_whitespace();
+                (fields.next() == Some(name)).then(|| {
+                    fields
+                        .skip(1)
+                        .find(|algorithm| algorithm.starts_with('['))
+                        .map(|algorithm| algorithm.trim_matches(['[', ']']).to_string())
+                })
+            })
+        };
+
+        // "Filename Type Size Used Priority"
+        let devices: Vec<String> = swaps
+            .lines()
+            .skip(1)
+            .filter_map(|line| {
+                let fields: Vec<&str> = line.split_whitespace().collect();
+                let [path, kind, ..] = fields.as_slice() else { return None };
+                let name = path.trim_start_matches("/dev/");
+                Some(match zram_algorithm(name) {
+                    Some(Some(algorithm)) => format!("{} (zram, {})", name, algorithm),
+                    Some(None) => format!("{} (zram)", name),
+                    None => format!("{} ({})", path, kind),
+                })
+            })
+            .collect();
+
+        if devices.is_empty() {
+            summary
+        } else {
+            format!("{}: {}", summary, devices.join(", "))
+        }
+    }
+
     fn parse_uptime_from_proc(&self, uptime: &str) -> String {
         let parts: Vec<&str> = uptime.split_whitespace().collect();
         
@@ -821,6 +1616,1079 @@ impl SystemInfoCollector {
         "Unknown".to_string()
     }
     
+    fn parse_loadavg(&self, loadavg: &str) -> Option<(f64, f64, f64)> {
+        // /proc/loadavg: "0.52 0.41 0.30 1/234 5678"
+        let mut values = loadavg.split_whitespace().map(|v| v.parse::<f64>());
+        match (values.next(), values.next(), values.next()) {
+            (Some(Ok(one)), Some(Ok(five)), Some(Ok(fifteen))) => Some((one, five, fifteen)),
+            _ => None,
+        }
+    }
+
+    fn parse_load_from_uptime(&self, uptime_output: &str) -> Option<(f64, f64, f64)> {
+        // uptime: "18:57:16 up  1:42,  0 users,  load average: 1.09, 1.06, 1.02"
+        let (_, load_part) = uptime_output.split_once("load average:")?;
+        let values: Vec<f64> = load_part
+            .split(',')
+            .filter_map(|v| v.trim().parse::<f64>().ok())
+            .collect();
+        if values.len() >= 3 {
+            Some((values[0], values[1], values[2]))
+        } else {
+            None
+        }
+    }
+
+    fn parse_gpu_vpu(&self, output: &str, soc_hint: &str) -> (Option<String>, Option<String>) {
+        // Sections are separated by the markers echoed in GPU_COMMAND
+        let (uevent, rest) = output.split_once("__LSMOD__").unwrap_or((output, ""));
+        let (lsmod, rest) = rest.split_once("__DRIVERS__").unwrap_or((rest, ""));
+        let (drivers, glx) = rest.split_once("__GLX__").unwrap_or((rest, ""));
+
+        // Loaded modules and platform drivers (built-in drivers only show up in the latter)
+        let loaded: Vec<&str> = lsmod
+            .lines()
+            .filter_map(|line| line.split_whitespace().next())
+            .chain(drivers.split_whitespace())
+            .collect();
+        let has = |name: &str| loaded.iter().any(|driver| driver.replace('-', "_") == name);
+
+        // 1. The GL renderer string is the most precise, e.g. "Mali-G610 (Panfrost)"
+        let mut gpu = glx
+            .lines()
+            .find_map(|line| line.split_once("renderer string:"))
+            .map(|(_, renderer)| renderer.trim().to_string())
+            .filter(|renderer| !renderer.is_empty() && !renderer.contains("llvmpipe"));
+
+        // 2. DRM device uevent: DRIVER=panfrost / OF_COMPATIBLE_0=arm,mali-bifrost
+        if gpu.is_none() {
+            let mut driver = None;
+            let mut model = None;
+            for line in uevent.lines() {
+                if let Some(value) = line.strip_prefix("DRIVER=") {
+                    driver = Some(value.trim());
+                } else if let Some(value) = line.strip_prefix("OF_COMPATIBLE_0=") {
+                    model = value.trim().split(',').nth(1).map(|m| m.to_string());
+                }
+            }
+            gpu = match (driver.and_then(Self::gpu_from_driver), model) {
+                (Some(name), Some(model)) if model.starts_with("mali") => Some(format!("{} ({})", name, model)),
+                (Some(name), _) => Some(name.to_string()),
+                _ => None,
+            };
+        }
+
+        // 3. Known GPU kernel drivers
+        if gpu.is_none() {
+            gpu = ["panfrost", "lima", "mali_kbase", "mali", "v3d", "vc4", "etnaviv", "msm", "tegra", "nouveau", "i915", "amdgpu"]
+                .iter()
+                .find(|driver| has(driver))
+                .and_then(|driver| Self::gpu_from_driver(driver))
+                .map(|name| name.to_string());
+        }
+
+        // 4. Best effort: the GPU the SoC family usually ships with
+        if gpu.is_none() {
+            gpu = Self::gpu_from_soc(soc_hint).map(|name| format!("{} (typical for SoC)", name));
+        }
+
+        let vpu = [
+            ("rkvdec", "Rockchip RKVDEC"),
+            ("rkvenc", "Rockchip RKVENC"),
+            ("hantro_vpu", "Hantro VPU"),
+            ("rockchip_vpu", "Hantro VPU"),
+            ("meson_vdec", "Amlogic VDEC"),
+            ("sunxi_cedrus", "Allwinner Cedrus"),
+            ("cedrus", "Allwinner Cedrus"),
+            ("bcm2835_codec", "VideoCore codec"),
+            ("rpivid", "Raspberry Pi HEVC decoder"),
+            ("qcom_venus", "Qualcomm Venus"),
+            ("venus_core", "Qualcomm Venus"),
+            ("nvhost_nvdec", "NVIDIA NVDEC"),
+        ]
+        .iter()
+        .filter(|(driver, _)| has(driver))
+        .map(|(_, name)| *name)
+        .fold(Vec::<&str>::new(), |mut names, name| {
+            if !names.contains(&name) {
+                names.push(name);
+            }
+            names
+        });
+        let vpu = if vpu.is_empty() { None } else { Some(vpu.join(", ")) };
+
+        (gpu, vpu)
+    }
+
+    fn gpu_from_driver(driver: &str) -> Option<&'static str> {
+        match driver {
+            "panfrost" => Some("Mali (Panfrost)"),
+            "lima" => Some("Mali-400/450 (Lima)"),
+            "mali" | "mali_kbase" => Some("Mali (vendor driver)"),
+            "v3d" => Some("VideoCore VI/VII (v3d)"),
+            "vc4" => Some("VideoCore IV (vc4)"),
+            "etnaviv" => Some("Vivante (etnaviv)"),
+            "msm" => Some("Adreno (msm)"),
+            "tegra" | "nouveau" => Some("NVIDIA"),
+            "i915" => Some("Intel Graphics"),
+            "amdgpu" => Some("AMD Radeon"),
+            _ => None,
+        }
+    }
+
+    /// Keys are whole tokens of the compatible strings and chip name ("rockchip,rk3588",
+    /// "allwinner,sun8i-h3", "Raspberry Pi 4 Model B"), so "h3" doesn't match inside another word
+    fn gpu_from_soc(soc_hint: &str) -> Option<&'static str> {
+        let soc = soc_hint.to_lowercase();
+        let tokens: Vec<&str> = soc.split([',', '\0', '-', '_', ' ', '\n']).filter(|token| !token.is_empty()).collect();
+        let table = [
+            ("rk3588", "Mali-G610 MP4"),
+            ("rk3576", "Mali-G52 MC3"),
+            ("rk3568", "Mali-G52 2EE"),
+            ("rk3566", "Mali-G52 2EE"),
+            ("rk3399", "Mali-T860 MP4"),
+            ("rk3328", "Mali-450 MP2"),
+            ("rk3288", "Mali-T764"),
+            ("s922x", "Mali-G52 MP4"),
+            ("a311d", "Mali-G52 MP4"),
+            ("g12b", "Mali-G52 MP4"),
+            ("s905x3", "Mali-G31 MP2"),
+            ("sm1", "Mali-G31 MP2"),
+            ("s905x2", "Mali-G31 MP2"),
+            ("g12a", "Mali-G31 MP2"),
+            ("s905x", "Mali-450 MP3"),
+            ("s905d", "Mali-450 MP3"),
+            ("gxl", "Mali-450 MP3"),
+            ("s905", "Mali-450 MP3"),
+            ("gxbb", "Mali-450 MP3"),
+            ("h618", "Mali-G31 MP2"),
+            ("h616", "Mali-G31 MP2"),
+            ("h6", "Mali-T720 MP2"),
+            ("h5", "Mali-450 MP4"),
+            ("h3", "Mali-400 MP2"),
+            ("bcm2712", "VideoCore VII"),
+            ("bcm2711", "VideoCore VI"),
+            ("bcm2837", "VideoCore IV"),
+            ("bcm2835", "VideoCore IV"),
+            ("raspberry pi 5", "VideoCore VII"),
+            ("raspberry pi 4", "VideoCore VI"),
+            ("tegra210", "NVIDIA Maxwell"),
+            ("tegra194", "NVIDIA Volta"),
+            ("tegra234", "NVIDIA Ampere"),
+        ];
+
+        // Multi-word keys match consecutive tokens
+        let matches = |key: &str| {
+            let words: Vec<&str> = key.split(' ').collect();
+            tokens.windows(words.len()).any(|window| window == words.as_slice())
+        };
+        table.iter().find(|(key, _)| matches(key)).map(|(_, gpu)| *gpu)
+    }
+
+    fn parse_storage(&self, output: &str) -> Vec<StorageDevice> {
+        let mut devices: Vec<StorageDevice> = Vec::new();
+
+        // Lines look like "/sys/block/mmcblk0/size:62333952" or "/sys/block/mmcblk0/device/life_time:0x01 0x02"
+        for line in output.lines() {
+            let Some((path, value)) = line.split_once(':') else { continue };
+            let Some(rest) = path.strip_prefix("/sys/block/") else { continue };
+            let Some((name, attribute)) = rest.split_once('/') else { continue };
+
+            // Virtual devices say nothing about the board's storage
+            if name.starts_with("loop") || name.starts_with("ram") || name.starts_with("zram") {
+                continue;
+            }
+
+            let index = match devices.iter().position(|device| device.name == name) {
+                Some(index) => index,
+                None => {
+                    devices.push(StorageDevice { name: name.to_string(), ..Default::default() });
+                    devices.len() - 1
+                }
+            };
+            let device = &mut devices[index];
+            let value = value.trim();
+
+            match attribute {
+                // Always counted in 512-byte sectors
+                "size" => device.size_bytes = value.parse::<u64>().unwrap_or(0) * 512,
+                "device/name" => device.model = Some(value.to_string()),
+                "device/type" => device.media = Some(value.to_string()),
+                "device/life_time" => {
+                    // Type A and type B estimates, 0x01 = 0-10% used ... 0x0A = 90-100%, 0x0B = exceeded
+                    device.wear_level = value
+                        .split_whitespace()
+                        .filter_map(|level| u8::from_str_radix(level.trim_start_matches("0x"), 16).ok())
+                        .filter(|level| (1..=11).contains(level))
+                        .max();
+                }
+                _ => {}
+            }
+        }
+
+        devices.retain(|device| device.size_bytes > 0);
+        devices
+    }
+
+    fn parse_pci_devices(&self, output: &str) -> Vec<String> {
+        output
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(|line| {
+                // sysfs fallback: "0000:01:00.0 0x028000 0x8086 0x2723"
+                let fields: Vec<&str> = line.split_whitespace().collect();
+                match fields.as_slice() {
+                    [address, class, vendor, device] if class.starts_with("0x") => {
+                        let class = class.trim_start_matches("0x");
+                        let class_name = match class.get(..2) {
+                            Some("01") => "Mass storage controller",
+                            Some("02") => "Network controller",
+                            Some("03") => "Display controller",
+                            Some("04") => "Multimedia controller",
+                            Some("06") => "Bridge",
+                            Some("0c") => "Serial bus controller",
+                            _ => "Device",
+                        };
+                        format!(
+                            "{} {} [{}]: {}:{}",
+                            address,
+                            class_name,
+                            class.get(..4).unwrap_or(class),
+                            vendor.trim_start_matches("0x"),
+                            device.trim_start_matches("0x")
+                        )
+                    }
+                    // lspci already reads "0000:00:00.0 PCI bridge: Rockchip Electronics Co., Ltd RK3588 (rev 01)"
+                    _ => line.to_string(),
+                }
+            })
+            .collect()
+    }
+
+    fn parse_alerts(&self, output: &str) -> Vec<KernelAlert> {
+        let mut alerts: Vec<KernelAlert> = output
+            .lines()
+            .filter_map(|line| {
+                let line = line.trim();
+                // "[Tue Oct 15 10:22:01 2026] msg" / "[12345.678901] msg" / "2026-10-15T10:22:01+0000 host kernel: msg"
+                let (timestamp, message) = if let Some(rest) = line.strip_prefix('[') {
+                    let (timestamp, message) = rest.split_once(']')?;
+                    (timestamp.trim().to_string(), message.trim())
+                } else {
+                    let (timestamp, rest) = line.split_once(' ')?;
+                    let message = rest.split_once("kernel: ").map_or(rest, |(_, message)| message);
+                    (timestamp.to_string(), message.trim())
+                };
+
+                let lower = message.to_lowercase();
+                let kind = if lower.contains("kernel panic") {
+                    "panic"
+                } else if lower.contains("call trace") {
+                    "call trace"
+                } else {
+                    "OOM"
+                };
+                Some(KernelAlert {
+                    timestamp,
+                    kind: kind.to_string(),
+                    message: message.to_string(),
+                })
+            })
+            .collect();
+
+        let len = alerts.len();
+        alerts.drain(..len.saturating_sub(MAX_ALERTS));
+        alerts
+    }
+
+    fn parse_mounts(&self, output: &str) -> Vec<MountInfo> {
+        let (mount_table, df) = output.split_once("__DF__").unwrap_or((output, ""));
+
+        // "Filesystem 1024-blocks Used Available Capacity Mounted on"; mount points may contain spaces
+        let usage: std::collections::HashMap<String, (u64, Option<f64>)> = df
+            .lines()
+            .skip_while(|line| !line.starts_with("Filesystem"))
+            .skip(1)
+            .filter_map(|line| {
+                let fields: Vec<&str> = line.split_whitespace().collect();
+                if fields.len() < 6 {
+                    return None;
+                }
+                let blocks: u64 = fields[1].parse().ok()?;
+                let used_percent = fields[4].trim_end_matches('%').parse().ok();
+                Some((fields[5..].join(" "), (blocks * 1024, used_percent)))
+            })
+            .collect();
+
+        let mut mounts: Vec<MountInfo> = Vec::new();
+        for line in mount_table.lines() {
+            // "/dev/mmcblk0p2 / ext4 rw,noatime 0 0", spaces in paths escaped as \040
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let [device, mountpoint, fstype, options, ..] = fields.as_slice() else { continue };
+            if PSEUDO_FILESYSTEMS.contains(fstype) {
+                continue;
+            }
+            let mountpoint = mountpoint.replace("\\040", " ");
+            let Some(&(size_bytes, used_percent)) = usage.get(&mountpoint).filter(|(size, _)| *size > 0) else { continue };
+
+            // A later line for the same mount point is an overmount hiding the earlier one
+            mounts.retain(|mount| mount.mountpoint != mountpoint);
+            mounts.push(MountInfo {
+                device: device.to_string(),
+                mountpoint,
+                fstype: fstype.to_string(),
+                read_only: options.split(',').any(|option| option == "ro"),
+                size_bytes,
+                used_percent,
+            });
+        }
+        mounts
+    }
+
+    /// Connected DRM connectors from `DISPLAY_COMMAND`, else fbset's mode, else Android's `wm size`
+    fn parse_display(&self, output: &str) -> Option<String> {
+        let mut connectors: Vec<(String, String)> = Vec::new();
+        let mut framebuffer = None;
+        let mut android_size = None;
+        let mut android_rate = None;
+
+        for line in output.lines().map(str::trim) {
+            if let Some(rest) = line.strip_prefix("__CONNECTOR__ ") {
+                // "card0-HDMI-A-1 connected 1920x1080"; writeback connectors never report "connected"
+                let mut fields = rest.split_whitespace();
+                let (Some(name), Some("connected")) = (fields.next(), fields.next()) else { continue };
+                let name = name.split_once('-').map_or(name, |(_, connector)| connector);
+                connectors.push((name.to_string(), fields.next().unwrap_or("").to_string()));
+            } else if let Some(mode) = line.strip_prefix("mode \"") {
+                // fbset's mode "1920x1080-60"; some drivers leave out the rate
+                let mode = mode.trim_end_matches('"');
+                framebuffer = Some(match mode.split_once('-') {
+                    Some((size, rate)) => (size.to_string(), Some(rate.to_string())),
+                    None => (mode.to_string(), None),
+                });
+            } else if let Some(size) = line.strip_prefix("Physical size: ").or_else(|| line.strip_prefix("Override size: ")) {
+                // The override, listed second, is what apps actually render at
+                android_size = Some(size.to_string());
+            } else if let Some(rate) = line.strip_prefix("fps=") {
+                android_rate = rate.parse::<f64>().ok().map(|rate| rate.round().to_string());
+            }
+        }
+
+        let with_rate = |size: &str, rate: Option<&String>| match rate {
+            Some(rate) => format!("{}@{}", size, rate),
+            None => size.to_string(),
+        };
+        if !connectors.is_empty() {
+            // sysfs only knows the mode list, so the framebuffer's rate is used when its size matches
+            let described: Vec<String> = connectors
+                .iter()
+                .map(|(name, mode)| {
+                    let rate = framebuffer.as_ref().filter(|(size, _)| size == mode).and_then(|(_, rate)| rate.as_ref());
+                    if mode.is_empty() { name.clone() } else { format!("{}: {}", name, with_rate(mode, rate)) }
+                })
+                .collect();
+            return Some(described.join(", "));
+        }
+        if let Some(size) = android_size {
+            return Some(with_rate(&size, android_rate.as_ref()));
+        }
+        framebuffer.map(|(size, rate)| format!("fb0: {}", with_rate(&size, rate.as_ref())))
+    }
+
+    fn parse_serial_number(&self, output: &str) -> Option<String> {
+        output
+            .lines()
+            .filter(|line| !(line.starts_with("__") && line.ends_with("__")))
+            .map(|line| {
+                // cpuinfo's "Serial\t\t: 10000000abcdef01"; device-tree strings end in a NUL
+                let value = match line.split_once(':') {
+                    Some((key, value)) if key.trim().eq_ignore_ascii_case("serial") => value,
+                    _ => line,
+                };
+                value.trim_matches(|c: char| c == '\0' || c.is_whitespace())
+            })
+            // Placeholders seen on boards and emulators without a programmed ID
+            .find(|value| !value.is_empty() && !value.chars().all(|c| c == '0') && !value.eq_ignore_ascii_case("unknown"))
+            .map(str::to_string)
+    }
+
+    fn parse_failed_units(&self, output: &str) -> Vec<FailedUnit> {
+        let mut units: Vec<FailedUnit> = Vec::new();
+        let mut in_journal = false;
+
+        for line in output.lines() {
+            // "__UNIT__ nginx.service loaded failed failed A high performance web server"
+            if let Some(rest) = line.strip_prefix("__UNIT__ ") {
+                let mut fields = rest.split_whitespace();
+                let Some(name) = fields.next() else { continue };
+                let description: Vec<&str> = fields.skip(3).collect();
+                units.push(FailedUnit {
+                    name: name.to_string(),
+                    description: description.join(" "),
+                    result: None,
+                    last_log: None,
+                });
+                in_journal = false;
+                continue;
+            }
+            let Some(unit) = units.last_mut() else { continue };
+
+            let trimmed = line.trim();
+            // The journal tail follows the first blank line of `systemctl status`
+            if trimmed.is_empty() {
+                in_journal = true;
+            } else if in_journal {
+                // "Oct 15 10:22:01 host nginx[812]: message"; skip systemd's own "Failed with result" lines
+                if let Some((_, message)) = trimmed.split_once("]: ").filter(|(source, _)| !source.ends_with("systemd[1")) {
+                    unit.last_log = Some(message.trim().to_string());
+                }
+            } else if let Some(active) = trimmed.strip_prefix("Active:") {
+                // "Active: failed (Result: exit-code) since Tue 2026-10-15 10:22:01 UTC; 3min ago"
+                unit.result = active
+                    .split_once("(Result: ")
+                    .and_then(|(_, rest)| rest.split_once(')'))
+                    .map(|(result, _)| result.trim().to_string());
+            }
+        }
+
+        units.truncate(MAX_FAILED_UNITS);
+        units
+    }
+
+    fn parse_modules(&self, output: &str) -> Vec<KernelModule> {
+        // /proc/modules: "panfrost 69632 2 - Live 0x0000000000000000"
+        let mut modules: Vec<KernelModule> = output
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.split_whitespace();
+                let name = fields.next()?.to_string();
+                let use_count = fields.nth(1)?.parse().ok()?;
+                Some(KernelModule { name, use_count })
+            })
+            .collect();
+        modules.sort_by(|a, b| a.name.cmp(&b.name));
+        modules
+    }
+
+    fn parse_top_processes(&self, output: &str) -> Vec<ProcessInfo> {
+        // Columns are located by header since ps, procps top, busybox top and toybox top all differ:
+        //   "PID %CPU %MEM COMMAND", "PID USER PR NI VIRT RES SHR S %CPU %MEM TIME+ COMMAND",
+        //   "PID PPID USER STAT VSZ %VSZ CPU %CPU COMMAND", "PID USER PR NI VIRT RES SHR S[%CPU] %MEM TIME+ ARGS"
+        let mut lines = output.lines();
+        let Some(header) = lines.by_ref().find(|line| line.split_whitespace().next() == Some("PID")) else {
+            return Vec::new();
+        };
+        let columns: Vec<String> = header.replace("S[%CPU]", "S %CPU").split_whitespace().map(|c| c.trim_matches(['[', ']']).to_string()).collect();
+        let column = |names: &[&str]| columns.iter().position(|c| names.contains(&c.as_str()));
+        let (Some(pid_col), Some(cmd_col)) = (column(&["PID"]), column(&["COMMAND", "CMD", "COMM", "ARGS", "NAME"])) else {
+            return Vec::new();
+        };
+        let cpu_col = column(&["%CPU", "PCPU"]);
+        let mem_col = column(&["%MEM", "PMEM"]);
+
+        let mut processes: Vec<ProcessInfo> = lines
+            .filter_map(|line| {
+                let fields: Vec<&str> = line.split_whitespace().collect();
+                // Command names and args may contain spaces, so the last column takes the rest of the line
+                let command = fields.get(cmd_col..).filter(|rest| !rest.is_empty())?.join(" ");
+                let percent = |col: Option<usize>| col.and_then(|c| fields.get(c)).and_then(|v| v.trim_end_matches('%').parse::<f64>().ok());
+                Some(ProcessInfo {
+                    pid: fields.get(pid_col)?.parse().ok()?,
+                    cpu_percent: percent(cpu_col),
+                    mem_percent: percent(mem_col),
+                    command,
+                })
+            })
+            // The collection's own ps / top is always near the top of its output
+            .filter(|process| !matches!(process.command.as_str(), "ps" | "top" | "top -bn1"))
+            .collect();
+
+        let by_desc = |a: Option<f64>, b: Option<f64>| b.unwrap_or(0.0).total_cmp(&a.unwrap_or(0.0));
+        processes.sort_by(|a, b| by_desc(a.cpu_percent, b.cpu_percent));
+        let mut rest = processes.split_off(TOP_BY_CPU.min(processes.len()));
+        rest.sort_by(|a, b| by_desc(a.mem_percent, b.mem_percent));
+        processes.extend(rest.into_iter().filter(|process| process.mem_percent.is_some_and(|mem| mem > 0.0)).take(TOP_BY_MEMORY));
+        processes
+    }
+
+    fn parse_fans(&self, output: &str) -> Vec<FanReading> {
+        // "path:value" per file, grouped by the hwmon / cooling device directory
+        let mut values: Vec<(&str, &str, &str)> = Vec::new();
+        for line in output.lines() {
+            let Some((path, value)) = line.split_once(':') else { continue };
+            let Some((dir, file)) = path.rsplit_once('/') else { continue };
+            values.push((dir, file, value.trim()));
+        }
+        let value_of = |dir: &str, file: &str| {
+            values.iter().find(|(d, f, _)| *d == dir && *f == file).map(|(_, _, value)| *value)
+        };
+
+        let mut fans: Vec<FanReading> = Vec::new();
+        let mut seen_dirs: Vec<&str> = Vec::new();
+        for (dir, _, _) in &values {
+            if seen_dirs.contains(dir) {
+                continue;
+            }
+            seen_dirs.push(dir);
+
+            if dir.contains("/hwmon/") {
+                let chip = value_of(dir, "name").unwrap_or("hwmon");
+                // fanN_input and pwmN with the same N describe one fan
+                let mut indices: Vec<&str> = values
+                    .iter()
+                    .filter(|(d, _, _)| d == dir)
+                    .filter_map(|(_, file, _)| {
+                        file.strip_prefix("fan")
+                            .and_then(|rest| rest.strip_suffix("_input"))
+                            .or_else(|| file.strip_prefix("pwm"))
+                    })
+                    .collect();
+                indices.sort();
+                indices.dedup();
+
+                for index in indices {
+                    let rpm = value_of(dir, &format!("fan{}_input", index)).and_then(|value| value.parse().ok());
+                    let duty_percent = value_of(dir, &format!("pwm{}", index))
+                        .and_then(|value| value.parse::<u32>().ok())
+                        .map(|pwm| (pwm.min(255) * 100 / 255) as u8);
+                    fans.push(FanReading {
+                        label: format!("{} fan{}", chip, index),
+                        rpm,
+                        duty_percent,
+                    });
+                }
+            } else if let Some(kind) = value_of(dir, "type").filter(|kind| kind.contains("fan")) {
+                // Cooling devices also cover cpufreq throttling; only fans are of interest here
+                let state = value_of(dir, "cur_state").and_then(|value| value.parse::<u32>().ok());
+                let max_state = value_of(dir, "max_state").and_then(|value| value.parse::<u32>().ok());
+                let duty_percent = match (state, max_state) {
+                    (Some(state), Some(max)) if max > 0 => Some((state.min(max) * 100 / max) as u8),
+                    _ => None,
+                };
+                fans.push(FanReading {
+                    label: kind.to_string(),
+                    rpm: None,
+                    duty_percent,
+                });
+            }
+        }
+
+        fans
+    }
+
+    fn parse_virtualization(&self, output: &str) -> Option<String> {
+        let mut section = "";
+        let mut hint: Option<String> = None;
+        let mut systemd_verdict: Option<String> = None;
+
+        for line in output.lines() {
+            let line = line.trim();
+            if line.starts_with("__") && line.ends_with("__") {
+                section = line;
+                continue;
+            }
+            if line.is_empty() {
+                continue;
+            }
+
+            let found = match section {
+                // systemd-detect-virt knows best, including "none"
+                "" => {
+                    systemd_verdict.get_or_insert_with(|| line.to_string());
+                    None
+                }
+                "__CONTAINER__" => Some(line.to_string()),
+                "__CGROUP__" => {
+                    let cgroup = line.to_lowercase();
+                    if cgroup.contains("docker") {
+                        Some("docker".to_string())
+                    } else if cgroup.contains("kubepods") {
+                        Some("kubernetes".to_string())
+                    } else if cgroup.contains("lxc") {
+                        Some("lxc".to_string())
+                    } else {
+                        None
+                    }
+                }
+                "__DMI__" => {
+                    let product = line.to_lowercase();
+                    [
+                        ("kvm", "kvm"),
+                        ("qemu", "qemu"),
+                        ("standard pc", "qemu"),
+                        ("virtualbox", "oracle"),
+                        ("vmware", "vmware"),
+                        ("virtual machine", "microsoft"),
+                    ]
+                    .iter()
+                    .find(|(key, _)| product.contains(key))
+                    .map(|(_, name)| name.to_string())
+                }
+                "__QEMU__" if line == "1" => Some("android-emulator".to_string()),
+                _ => None,
+            };
+            if hint.is_none() {
+                hint = found;
+            }
+        }
+
+        match systemd_verdict {
+            Some(verdict) if verdict != "none" || hint.is_none() => Some(verdict),
+            // Without systemd (e.g. Android) go by the other hints; no hint at all means real hardware
+            _ => Some(hint.unwrap_or_else(|| "none".to_string())),
+        }
+    }
+
+    /// Names the init system from `INIT_COMMAND` output, e.g. "systemd", "OpenRC", "BusyBox init", "Android init"
+    fn parse_init_system(&self, output: &str) -> Option<String> {
+        let mut section = "";
+        let mut comm: Option<&str> = None;
+        let mut init_path = "";
+        let mut tools: Vec<&str> = Vec::new();
+        let mut android = false;
+
+        for line in output.lines() {
+            let line = line.trim();
+            if line.starts_with("__") && line.ends_with("__") {
+                section = line;
+                continue;
+            }
+            if line.is_empty() {
+                continue;
+            }
+            match section {
+                "" => comm = comm.or(Some(line)),
+                "__INIT__" => init_path = line,
+                "__TOOLS__" => tools.push(line.rsplit('/').next().unwrap_or(line)),
+                "__ANDROID__" => android = true,
+                _ => {}
+            }
+        }
+
+        let name = match comm {
+            Some("systemd") => "systemd",
+            Some("openrc-init") => "OpenRC",
+            Some("runit") | Some("runit-init") => "runit",
+            Some("s6-svscan") => "s6",
+            Some("procd") => "procd",
+            // A generic "init" (or unreadable /proc/1/comm) needs the other hints
+            Some("init") | None => {
+                if android {
+                    "Android init"
+                } else if init_path.ends_with("systemd") {
+                    "systemd"
+                } else if tools.contains(&"rc-status") {
+                    "OpenRC"
+                } else if init_path.ends_with("busybox") {
+                    "BusyBox init"
+                } else if comm.is_some() || tools.contains(&"service") {
+                    "SysV init"
+                } else if tools.contains(&"systemctl") {
+                    "systemd"
+                } else {
+                    return None;
+                }
+            }
+            Some(other) => other,
+        };
+        Some(name.to_string())
+    }
+
+    fn parse_boot_info(&self, output: &str) -> BootInfo {
+        // Device-tree strings end in NUL, which would otherwise end up in the value
+        let section = |start: &str, end: Option<&str>| -> Option<String> {
+            let rest = if start.is_empty() { output } else { output.split_once(start)?.1 };
+            let body = match end {
+                Some(end) => rest.split_once(end).map(|(body, _)| body).unwrap_or(rest),
+                None => rest,
+            };
+            let value = body.replace('\0', " ").trim().to_string();
+            (!value.is_empty()).then_some(value)
+        };
+
+        let cmdline = section("", Some("__BOOTARGS__"));
+        let bootargs = section("__BOOTARGS__", Some("__BOOTLOADER__"));
+        let bootloader = section("__BOOTLOADER__", Some("__ROOTFS__"));
+        let root_mount = section("__ROOTFS__", None);
+
+        let root = cmdline
+            .as_deref()
+            .or(bootargs.as_deref())
+            .and_then(|args| args.split_whitespace().find_map(|arg| arg.strip_prefix("root=")))
+            .map(|root| root.to_string());
+
+        BootInfo {
+            bootargs: bootargs.filter(|bootargs| Some(bootargs) != cmdline.as_ref()),
+            cmdline,
+            root,
+            root_mount,
+            bootloader,
+        }
+    }
+
+    fn parse_gpio(&self, output: &str) -> Vec<GpioLine> {
+        // Empty sections just mean sysfs GPIO / libgpiod isn't there or needs root
+        let (sysfs, gpioinfo) = output.split_once("__GPIOINFO__").unwrap_or((output, ""));
+        let mut lines = Vec::new();
+
+        // "sysfs gpio17 out 1"
+        for line in sysfs.lines() {
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            if parts.len() < 2 || parts[0] != "sysfs" {
+                continue;
+            }
+            let Some(number) = parts[1].strip_prefix("gpio").and_then(|n| n.parse().ok()) else { continue };
+            lines.push(GpioLine {
+                chip: "sysfs".to_string(),
+                line: number,
+                name: None,
+                direction: parts.get(2).map(|d| d.to_string()).unwrap_or_default(),
+                value: parts.get(3).and_then(|v| v.parse().ok()),
+                consumer: None,
+            });
+        }
+
+        // libgpiod v1: `line  17:   "GPIO17"  "sysfs"  output  active-high [used]`
+        // libgpiod v2: `line  17:	"GPIO17"	output consumer="sysfs"`
+        let mut chip = String::new();
+        for line in gpioinfo.lines() {
+            let trimmed = line.trim();
+            if trimmed.starts_with("gpiochip") {
+                chip = trimmed.split(|c: char| c.is_whitespace() || c == ':').next().unwrap_or("").to_string();
+                continue;
+            }
+            let Some(rest) = trimmed.strip_prefix("line") else { continue };
+            let Some((number, rest)) = rest.split_once(':') else { continue };
+            let Ok(number) = number.trim().parse::<u32>() else { continue };
+
+            let tokens = Self::split_gpioinfo_tokens(rest);
+            let name = tokens.first().filter(|(token, _)| token != "unnamed").map(|(token, _)| token.clone());
+            let direction = tokens
+                .iter()
+                .find(|(token, quoted)| !quoted && (token == "input" || token == "output"))
+                .map(|(token, _)| token.clone())
+                .unwrap_or_default();
+            let consumer = tokens
+                .iter()
+                .find_map(|(token, _)| token.strip_prefix("consumer=").map(|c| c.trim_matches('"').to_string()))
+                .or_else(|| tokens.get(1).filter(|(_, quoted)| *quoted).map(|(token, _)| token.clone()));
+
+            // Only lines a driver or user has claimed; the rest would flood the panel
+            if consumer.is_none() {
+                continue;
+            }
+            lines.push(GpioLine {
+                chip: chip.clone(),
+                line: number,
+                name,
+                direction,
+                value: None,
+                consumer,
+            });
+        }
+
+        lines
+    }
+
+    /// Splits a gpioinfo line into tokens, keeping "quoted strings" whole and marking them
+    fn split_gpioinfo_tokens(rest: &str) -> Vec<(String, bool)> {
+        let mut tokens = Vec::new();
+        let mut chars = rest.chars().peekable();
+        while let Some(&c) = chars.peek() {
+            if c.is_whitespace() {
+                chars.next();
+            } else if c == '"' {
+                chars.next();
+                let token: String = chars.by_ref().take_while(|&c| c != '"').collect();
+                tokens.push((token, true));
+            } else {
+                let mut token = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c.is_whitespace() {
+                        break;
+                    }
+                    token.push(c);
+                    chars.next();
+                }
+                tokens.push((token, false));
+            }
+        }
+        tokens
+    }
+
+    /// Adds SSID, signal and bitrate to the wireless entries of `network`
+    fn attach_wifi(&self, network: &mut [NetIface], output: &str) {
+        let (iw_output, proc_wireless) = output.split_once("__PROCWIRELESS__").unwrap_or((output, ""));
+
+        for block in iw_output.split("__IFACE__").skip(1) {
+            let mut lines = block.lines();
+            let Some(name) = lines.next().map(str::trim) else { continue };
+            let Some(iface) = network.iter_mut().find(|iface| iface.name == name) else { continue };
+
+            // "Not connected." or no iw at all leaves the interface without Wi-Fi details
+            let mut wifi = WifiLink::default();
+            let mut connected = false;
+            for line in lines {
+                let line = line.trim();
+                if line.starts_with("Connected to") {
+                    connected = true;
+                } else if let Some(ssid) = line.strip_prefix("SSID: ") {
+                    wifi.ssid = Some(ssid.to_string());
+                } else if let Some(signal) = line.strip_prefix("signal: ") {
+                    wifi.signal_dbm = signal.split_whitespace().next().and_then(|dbm| dbm.parse().ok());
+                } else if let Some(bitrate) = line.strip_prefix("tx bitrate: ") {
+                    // Keep "433.3 MBit/s", drop the MCS / channel width details
+                    wifi.tx_bitrate = Some(bitrate.split_whitespace().take(2).collect::<Vec<_>>().join(" "));
+                }
+            }
+            if connected {
+                iface.wifi = Some(wifi);
+            }
+        }
+
+        // "wlan0: 0000   54.  -56.  -256 ..." — the level column is the signal in dBm
+        for line in proc_wireless.lines() {
+            let Some((name, rest)) = line.trim().split_once(':') else { continue };
+            let Some(iface) = network.iter_mut().find(|iface| iface.name == name) else { continue };
+            // 0 (or a positive quality-style value) means no usable dBm reading
+            let Some(level) = rest
+                .split_whitespace()
+                .nth(2)
+                .and_then(|level| level.trim_end_matches('.').parse::<i32>().ok())
+                .filter(|level| *level < 0)
+            else {
+                continue;
+            };
+            let wifi = iface.wifi.get_or_insert_with(WifiLink::default);
+            wifi.signal_dbm.get_or_insert(level);
+        }
+    }
+
+    fn parse_network(&self, output: &str) -> Vec<NetIface> {
+        // iproute2 headers look like "2: eth0: <BROADCAST,...>" or "2: eth0    inet ..."
+        let is_ip_output = output.lines().any(|line| {
+            line.split_once(':')
+                .is_some_and(|(index, _)| !index.is_empty() && index.chars().all(|c| c.is_ascii_digit()))
+        });
+
+        if is_ip_output {
+            self.parse_ip_output(output)
+        } else {
+            self.parse_ifconfig_output(output)
+        }
+    }
+
+    fn parse_ip_output(&self, output: &str) -> Vec<NetIface> {
+        // Handles both `ip addr` and `ip -o link`/`ip -o addr` (where '\' joins continuation lines)
+        let mut interfaces: Vec<NetIface> = Vec::new();
+        let mut current: Option<usize> = None;
+
+        for line in output.lines() {
+            let line = line.replace('\\', " ");
+            let mut tokens: &str = &line;
+
+            if let Some((index, rest)) = line.split_once(':') {
+                if !index.is_empty() && index.chars().all(|c| c.is_ascii_digit()) {
+                    let rest = rest.trim_start();
+                    let name_end = rest.find(|c: char| c == ':' || c.is_whitespace()).unwrap_or(rest.len());
+                    // VLANs and veths show up as "eth0.100@eth0"
+                    let name = rest[..name_end].split('@').next().unwrap_or("").to_string();
+                    let existing = interfaces.iter().position(|iface| iface.name == name);
+                    current = Some(match existing {
+                        Some(pos) => pos,
+                        None => {
+                            interfaces.push(NetIface {
+                                name,
+                                state: "UNKNOWN".to_string(),
+                                ..Default::default()
+                            });
+                            interfaces.len() - 1
+                        }
+                    });
+                    tokens = &rest[name_end..];
+                }
+            }
+
+            let Some(pos) = current else { continue };
+            let iface = &mut interfaces[pos];
+            let words: Vec<&str> = tokens.split_whitespace().collect();
+            for pair in words.windows(2) {
+                match pair[0] {
+                    "state" => iface.state = pair[1].to_string(),
+                    "link/ether" => iface.mac = Some(pair[1].to_string()),
+                    "inet" if !iface.ipv4.iter().any(|a| a == pair[1]) => iface.ipv4.push(pair[1].to_string()),
+                    "inet6" if !iface.ipv6.iter().any(|a| a == pair[1]) => iface.ipv6.push(pair[1].to_string()),
+                    _ => {}
+                }
+            }
+        }
+
+        interfaces
+    }
+
+    fn parse_ifconfig_output(&self, output: &str) -> Vec<NetIface> {
+        // Handles net-tools ("eth0: flags=4163<UP,...>") and busybox ("eth0  Link encap:Ethernet  HWaddr ...")
+        let mut interfaces: Vec<NetIface> = Vec::new();
+
+        for line in output.lines() {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            if !line.starts_with(char::is_whitespace) {
+                let name = line.split_whitespace().next().unwrap_or("").trim_end_matches(':').to_string();
+                interfaces.push(NetIface {
+                    name,
+                    state: "DOWN".to_string(),
+                    ..Default::default()
+                });
+            }
+
+            let Some(iface) = interfaces.last_mut() else { continue };
+            if line.contains("<UP") || line.contains(",UP") || line.trim_start().starts_with("UP ") {
+                iface.state = "UP".to_string();
+            }
+
+            let words: Vec<&str> = line.split_whitespace().collect();
+            for (i, word) in words.iter().enumerate() {
+                let next = words.get(i + 1).copied();
+                match *word {
+                    "inet" => {
+                        if let Some(addr) = next {
+                            iface.ipv4.push(addr.trim_start_matches("addr:").to_string());
+                        }
+                    }
+                    "inet6" => {
+                        // busybox prints "inet6 addr: fe80::1/64"
+                        let addr = if next == Some("addr:") { words.get(i + 2).copied() } else { next };
+                        if let Some(addr) = addr {
+                            iface.ipv6.push(addr.to_string());
+                        }
+                    }
+                    "ether" | "HWaddr" => iface.mac = next.map(|mac| mac.to_lowercase()),
+                    _ => {}
+                }
+            }
+        }
+
+        interfaces
+    }
+
+    fn parse_thermal_zones(&self, output: &str) -> Vec<ThermalZone> {
+        // "0 45277 cpu-thermal"
+        let mut zones: Vec<ThermalZone> = output
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.trim().splitn(3, ' ');
+                let index: u32 = fields.next()?.parse().ok()?;
+                let value: i64 = fields.next()?.trim().parse().ok()?;
+                let kind = fields.next().unwrap_or("").trim();
+                // temp is usually millidegrees Celsius, a few drivers report whole degrees
+                let millidegrees = if value.abs() >= 1000 { value } else { value * 1000 };
+                let label = if kind.is_empty() { format!("thermal_zone{}", index) } else { kind.to_string() };
+                Some(ThermalZone { index, label, millidegrees })
+            })
+            .collect();
+        // The glob sorts thermal_zone10 before thermal_zone2
+        zones.sort_by_key(|zone| zone.index);
+
+        // Boards with one zone per core often repeat the type
+        let labels: Vec<String> = zones.iter().map(|zone| zone.label.clone()).collect();
+        for zone in &mut zones {
+            if labels.iter().filter(|label| **label == zone.label).count() > 1 {
+                zone.label = format!("{} #{}", zone.label, zone.index);
+            }
+        }
+        zones
+    }
+    
+    fn parse_battery(&self, output: &str) -> Option<BatteryStatus> {
+        // "  AC powered: false", "  status: 2", "  level: 85", "  temperature: 285", "  voltage: 4123";
+        // keys vary in case and spacing between Android versions, and some builds append extra sections
+        let values: std::collections::HashMap<String, String> = output
+            .lines()
+            .filter_map(|line| line.split_once(':'))
+            .map(|(key, value)| (key.trim().to_lowercase(), value.trim().to_string()))
+            .collect();
+        let number = |key: &str| values.get(key).and_then(|value| value.parse::<f64>().ok());
+
+        // Boards without a battery still run the service, reporting it as absent
+        if values.get("present").is_some_and(|present| present == "false") || !values.contains_key("level") {
+            return None;
+        }
+
+        let scale = number("scale").filter(|scale| *scale > 0.0).unwrap_or(100.0);
+        let level_percent = number("level").map(|level| (level / scale * 100.0).round().clamp(0.0, 100.0) as u8);
+        let status = match values.get("status").map(String::as_str) {
+            Some("2") => "charging",
+            Some("3") => "discharging",
+            Some("4") => "not charging",
+            Some("5") => "full",
+            _ => "unknown",
+        };
+        let plugged = [("ac powered", "AC"), ("usb powered", "USB"), ("wireless powered", "Wireless"), ("dock powered", "Dock")]
+            .iter()
+            .filter(|(key, _)| values.get(*key).is_some_and(|value| value == "true"))
+            .map(|(_, name)| name.to_string())
+            .collect();
+        // Tenths of a degree; voltage is millivolts except on a few old builds that report volts
+        let temperature_c = number("temperature").map(|tenths| tenths / 10.0);
+        let voltage_mv = number("voltage").map(|voltage| (if voltage < 100.0 { voltage * 1000.0 } else { voltage }) as u32);
+
+        Some(BatteryStatus {
+            level_percent,
+            status: status.to_string(),
+            plugged,
+            temperature_c,
+            voltage_mv,
+        })
+    }
+
+    fn parse_power_status(&self, output: &str) -> Option<String> {
+        // Output lines: "throttled=0x50005", "temp=48.3'C", "volt=0.8500V"
+        let mut throttled = None;
+        let mut readings = Vec::new();
+        for line in output.lines() {
+            let line = line.trim();
+            if let Some(value) = line.strip_prefix("throttled=") {
+                throttled = u32::from_str_radix(value.trim_start_matches("0x"), 16).ok();
+            } else if let Some(value) = line.strip_prefix("volt=") {
+                readings.push(value.to_string());
+            } else if let Some(value) = line.strip_prefix("temp=") {
+                readings.push(value.replace('\'', "°"));
+            }
+        }
+        let throttled = throttled?;
+
+        // Bits 0-3 describe the current state, bits 16-19 whether it happened since boot
+        let flags = [
+            (0, "under-voltage now"),
+            (1, "freq-capped now"),
+            (2, "throttled now"),
+            (3, "soft temp limit now"),
+            (16, "under-voltage past"),
+            (17, "freq-capped past"),
+            (18, "throttled past"),
+            (19, "soft temp limit past"),
+        ];
+        let active: Vec<&str> = flags
+            .iter()
+            .filter(|(bit, _)| throttled & (1u32 << *bit) != 0)
+            .map(|(_, name)| *name)
+            .collect();
+
+        let mut status = if active.is_empty() {
+            "OK".to_string()
+        } else {
+            active.join(", ")
+        };
+        if !readings.is_empty() {
+            status.push_str(&format!(" ({})", readings.join(", ")));
+        }
+        Some(status)
+    }
+
     fn parse_os_from_release(&self, os_release: &str) -> String {
         for line in os_release.lines() {
             if line.starts_with("PRETTY_NAME") {
@@ -834,3 +2702,284 @@ impl SystemInfoCollector {
         "Unknown".to_string()
     }
 }
+
+fn is_x86(architecture: &str) -> bool {
+    matches!(architecture, "x86_64" | "amd64" | "i386" | "i486" | "i586" | "i686")
+}
+
+/// Lists upgradable packages from the cached package lists, without refreshing them (no root needed).
+/// dnf, yum and pacman exit non-zero for "updates available" / "none", hence the `true`.
+fn updates_command(manager: &str) -> Option<&'static str> {
+    match manager {
+        "apt" => Some("apt-get -s -o Debug::NoLocking=1 upgrade 2>/dev/null"),
+        "dnf" => Some("dnf -q --cacheonly check-update 2>/dev/null; true"),
+        "yum" => Some("yum -q -C check-update 2>/dev/null; true"),
+        "pacman" => Some("pacman -Qu 2>/dev/null; true"),
+        "apk" => Some("apk version -l \"<\" 2>/dev/null"),
+        "opkg" => Some("opkg list-upgradable 2>/dev/null"),
+        _ => None,
+    }
+}
+
+/// Counts the package lines in `updates_command` output
+fn count_pending_updates(manager: &str, output: &str) -> u32 {
+    let lines = output.lines().map(str::trim).filter(|line| !line.is_empty());
+    let count = match manager {
+        // "Inst libc6 [2.36-9] (2.36-9+deb12u4 Debian:12.5/stable [arm64])"
+        "apt" => lines.filter(|line| line.starts_with("Inst ")).count(),
+        // "kernel.x86_64  6.8.9-300.fc40  updates"; obsoleted packages follow in a section of their own
+        "dnf" | "yum" => lines
+            .take_while(|line| !line.starts_with("Obsoleting"))
+            .filter(|line| {
+                let fields: Vec<&str> = line.split_whitespace().collect();
+                fields.len() == 3 && fields[0].contains('.')
+            })
+            .count(),
+        // "busybox-1.36.1-r5 < 1.36.1-r6"
+        "apk" => lines.filter(|line| line.contains(" < ")).count(),
+        // pacman "linux 6.9.1-1 -> 6.9.2-1", opkg "busybox - 1.36.1-1 - 1.36.1-2"
+        _ => lines.count(),
+    };
+    count as u32
+}
+
+/// Overall and per-core busy percent from the two `CPU_USAGE_COMMAND` samples. Busy is the
+/// total minus idle and iowait; guest time is already part of user time, so it is left out.
+fn parse_cpu_usage(output: &str) -> (Option<f64>, Vec<f64>) {
+    let Some((first, second)) = output.split_once("__SAMPLE__") else {
+        return (None, Vec::new());
+    };
+    // "cpu0 4705 150 1120 16250 520 30 25 0 0 0" -> (busy, total) jiffies
+    let sample = |text: &str| -> Vec<(String, u64, u64)> {
+        text.lines()
+            .filter_map(|line| {
+                let mut fields = line.split_whitespace();
+                let name = fields.next()?.to_string();
+                let values: Vec<u64> = fields.take(8).filter_map(|value| value.parse().ok()).collect();
+                if values.len() < 4 {
+                    return None;
+                }
+                let total: u64 = values.iter().sum();
+                let idle = values[3] + values.get(4).copied().unwrap_or(0);
+                Some((name, total - idle, total))
+            })
+            .collect()
+    };
+    let before = sample(first);
+    let after = sample(second);
+
+    let usage = |name: &str| -> Option<f64> {
+        let (_, busy_before, total_before) = before.iter().find(|(cpu, _, _)| cpu == name)?;
+        let (_, busy_after, total_after) = after.iter().find(|(cpu, _, _)| cpu == name)?;
+        let total = total_after.checked_sub(*total_before).filter(|total| *total > 0)?;
+        let busy = busy_after.saturating_sub(*busy_before);
+        Some((busy as f64 / total as f64 * 100.0).clamp(0.0, 100.0))
+    };
+    // Cores that went offline between the samples are skipped
+    let cores = after
+        .iter()
+        .filter(|(name, _, _)| name != "cpu")
+        .filter_map(|(name, _, _)| usage(name))
+        .collect();
+    (usage("cpu"), cores)
+}
+
+/// "name (source)" per overlay from `OVERLAYS_COMMAND`, in the order they are applied. Overlay
+/// parameters are dropped, and a name listed twice (config.txt in both /boot and /boot/firmware)
+/// is kept once per source.
+fn parse_overlays(output: &str) -> Vec<String> {
+    fn section<'a>(output: &'a str, start: &str, end: &str) -> &'a str {
+        let rest = output.split_once(start).map(|(_, rest)| rest).unwrap_or("");
+        rest.split_once(end).map(|(body, _)| body).unwrap_or(rest)
+    }
+    let configfs = output.split_once("__CONFIGTXT__").map(|(body, _)| body).unwrap_or("");
+
+    let mut found: Vec<(String, &str)> = Vec::new();
+    // configfs directories are named by whoever applied the overlay, e.g. Pi's `dtoverlay` tool
+    found.extend(configfs.split_whitespace().map(|name| (name.to_string(), "configfs")));
+    // "dtoverlay=vc4-kms-v3d,cma-512"; a bare "dtoverlay=" only resets parameters
+    for line in section(output, "__CONFIGTXT__", "__ARMBIANENV__").lines() {
+        let Some(value) = line.trim().strip_prefix("dtoverlay=") else { continue };
+        let name = value.split(',').next().unwrap_or("").trim();
+        if !name.is_empty() {
+            found.push((name.to_string(), "config.txt"));
+        }
+    }
+    // "overlays=i2c1 uart3 usbhost2"; user_overlays= names the .dtbo files under /boot/overlay-user
+    for line in section(output, "__ARMBIANENV__", "__UENV__").lines() {
+        let Some((_, value)) = line.trim().split_once('=') else { continue };
+        found.extend(value.split_whitespace().map(|name| (name.to_string(), "armbianEnv.txt")));
+    }
+    // "uboot_overlay_addr4=/lib/firmware/BB-UART1-00A0.dtbo", "dtb_overlay=/lib/firmware/BB-SPIDEV0-00A0.dtbo"
+    for line in output.split_once("__UENV__").map(|(_, rest)| rest).unwrap_or("").lines() {
+        let Some((_, value)) = line.trim().split_once('=') else { continue };
+        let file = value.trim().rsplit('/').next().unwrap_or("");
+        let name = file.strip_suffix(".dtbo").unwrap_or(file);
+        if !name.is_empty() {
+            found.push((name.to_string(), "uEnv.txt"));
+        }
+    }
+
+    let mut overlays: Vec<String> = Vec::new();
+    for (name, source) in found {
+        let entry = format!("{} ({})", name, source);
+        if !overlays.contains(&entry) {
+            overlays.push(entry);
+        }
+    }
+    overlays
+}
+
+/// Total from the "Mem:" line of `free`, whose numbers are divided by `unit` to get KiB:
+/// "              total        used        free      shared     buffers"
+/// "Mem:       2005991424  1791692800   214298624     2093056    36106240"
+fn memory_from_free(output: &str, unit: u64) -> Option<String> {
+    let line = output.lines().find(|line| line.starts_with("Mem:"))?;
+    let total_kb = line.split_whitespace().nth(1)?.parse::<u64>().ok()? / unit;
+    let mb = total_kb / 1024;
+    let gb = mb / 1024;
+    if gb > 0 {
+        Some(format!("{} GB", gb))
+    } else {
+        Some(format!("{} MB", mb))
+    }
+}
+
+/// Cached plus Buffers from /proc/meminfo
+fn parse_mem_cached(meminfo: &str) -> Option<String> {
+    let cached = meminfo_kib(meminfo, "Cached:")?;
+    Some(format_kib(cached + meminfo_kib(meminfo, "Buffers:").unwrap_or(0)))
+}
+
+/// Number of "processor : N" entries; None when cpuinfo lists none (some Android kernels)
+fn parse_cpu_cores(cpuinfo: &str) -> Option<usize> {
+    let cores = cpuinfo
+        .lines()
+        .filter(|line| line.split(':').next().is_some_and(|key| key.trim() == "processor"))
+        .count();
+    (cores > 0).then_some(cores)
+}
+
+/// Whole seconds from the first field of /proc/uptime
+fn parse_uptime_seconds(uptime: &str) -> Option<u64> {
+    uptime.split_whitespace().next()?.parse::<f64>().ok().map(|seconds| seconds as u64)
+}
+
+/// Value in kB of a /proc/meminfo line such as "SwapTotal:  1048572 kB"
+fn meminfo_kib(meminfo: &str, name: &str) -> Option<u64> {
+    meminfo
+        .lines()
+        .find(|line| line.starts_with(name))
+        .and_then(|line| line.split_whitespace().nth(1))
+        .and_then(|kb| kb.parse().ok())
+}
+
+fn format_kib(kb: u64) -> String {
+    if kb >= 1024 * 1024 {
+        format!("{:.1} GB", kb as f64 / (1024.0 * 1024.0))
+    } else {
+        format!("{} MB", kb / 1024)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// BusyBox `top -bn1` has no %MEM column and puts STAT and VSZ before %CPU
+    const BUSYBOX_TOP: &str = "\
+Mem: 168252K used, 1862924K free, 1116K shrd, 8328K buff, 79872K cached
+CPU:   7% usr   1% sys   0% nic  91% idle   0% io   0% irq   0% sirq
+Load average: 0.31 0.12 0.04 2/79 1262
+  PID  PPID USER     STAT   VSZ %VSZ CPU %CPU COMMAND
+ 1262  1255 root     R     1600   0%   1   0% top -bn1
+ 1180     1 root     S    38044   2%   2   7% /usr/bin/python3 /opt/app/main.py
+  502     1 root     S    12064   1%   0   1% /usr/sbin/sshd -D
+    1     0 root     S     1632   0%   3   0% /sbin/init
+";
+
+    #[test]
+    fn busybox_top_processes() {
+        let collector = SystemInfoCollector::new("ssh", "board");
+        let processes = collector.parse_top_processes(BUSYBOX_TOP);
+        let commands: Vec<&str> = processes.iter().map(|process| process.command.as_str()).collect();
+        assert_eq!(commands, ["/usr/bin/python3 /opt/app/main.py", "/usr/sbin/sshd -D", "/sbin/init"]);
+        assert_eq!(processes[0].pid, 1180);
+        assert_eq!(processes[0].cpu_percent, Some(7.0));
+        assert_eq!(processes[0].mem_percent, None);
+    }
+
+    #[test]
+    fn toybox_free_counts_bytes() {
+        let output = "\
+                total        used        free      shared     buffers
+Mem:       3899379712  3722211328   177168384    15380480     2179072
+-/+ buffers/cache:  3720032256   179347456
+Swap:      2147479552   633393152  1514086400
+";
+        assert_eq!(memory_from_free(output, 1024).as_deref(), Some("3 GB"));
+    }
+
+    #[test]
+    fn busybox_free_counts_kib() {
+        let output = "\
+              total        used        free      shared  buff/cache   available
+Mem:         949580      153012      611956        1108      184612      781536
+Swap:        102396           0      102396
+";
+        assert_eq!(memory_from_free(output, 1).as_deref(), Some("927 MB"));
+    }
+
+    #[test]
+    fn free_without_mem_line() {
+        assert_eq!(memory_from_free("free: not found", 1), None);
+    }
+
+    #[test]
+    fn uname_three_lines() {
+        let collector = SystemInfoCollector::new("ssh", "board");
+        let (kernel, architecture) = collector.parse_uname("Linux\n6.1.0-rockchip\naarch64\n");
+        assert_eq!(kernel, "Linux 6.1.0-rockchip");
+        assert_eq!(architecture, "aarch64");
+    }
+
+    #[test]
+    fn uname_a_raspberry_pi() {
+        let collector = SystemInfoCollector::new("ssh", "board");
+        let (kernel, architecture) = collector.parse_uname(
+            "Linux raspberrypi 6.6.31+rpt-rpi-v8 #1 SMP PREEMPT Debian 1:6.6.31-1+rpt1 (2024-05-29) aarch64 GNU/Linux",
+        );
+        assert_eq!(kernel, "Linux 6.6.31+rpt-rpi-v8");
+        assert_eq!(architecture, "aarch64");
+    }
+
+    #[test]
+    fn uname_a_rockchip() {
+        let collector = SystemInfoCollector::new("ssh", "board");
+        let (kernel, architecture) = collector.parse_uname(
+            "Linux orangepi5 5.10.110-rockchip-rk3588 #1.1.6 SMP Thu Jun 29 10:30:40 CST 2023 aarch64 aarch64 aarch64 GNU/Linux",
+        );
+        assert_eq!(kernel, "Linux 5.10.110-rockchip-rk3588");
+        assert_eq!(architecture, "aarch64");
+    }
+
+    #[test]
+    fn uname_a_amlogic() {
+        let collector = SystemInfoCollector::new("ssh", "board");
+        let (kernel, architecture) = collector.parse_uname(
+            "Linux odroid 3.10.107-13 #1 SMP PREEMPT Fri Aug 3 09:34:41 UTC 2018 armv7l armv7l armv7l GNU/Linux",
+        );
+        assert_eq!(kernel, "Linux 3.10.107-13");
+        assert_eq!(architecture, "armv7l");
+    }
+
+    #[test]
+    fn gpu_from_soc_matches_whole_tokens() {
+        let gpu = |hint| SystemInfoCollector::gpu_from_soc(hint);
+        assert_eq!(gpu("xunlong,orangepi-pc\0allwinner,sun8i-h3 Allwinner H3"), Some("Mali-400 MP2"));
+        assert_eq!(gpu("hardkernel,odroid-n2\0amlogic,s922x\0amlogic,g12b Amlogic S922X"), Some("Mali-G52 MP4"));
+        assert_eq!(gpu("raspberrypi,4-model-b\0brcm,bcm2711 Raspberry Pi 4 Model B Rev 1.4"), Some("VideoCore VI"));
+        // "h3" inside another token, "sm1" inside "xsm1000"
+        assert_eq!(gpu("vendor,board-h3x\0vendor,xsm1000 Unknown"), None);
+    }
+}
@@ -0,0 +1,63 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Set by `--time`
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Label, number of runs and total wall-clock time, in first-seen order
+static RECORDS: Mutex<Vec<(String, u32, Duration)>> = Mutex::new(Vec::new());
+
+/// Longest label shown in the report; commands are cut to this many characters
+const LABEL_WIDTH: usize = 50;
+
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Adds one run of a step (a remote command or a connection phase); repeated labels are summed
+pub fn record(label: &str, elapsed: Duration) {
+    if !enabled() {
+        return;
+    }
+    let label: String = label.chars().take(LABEL_WIDTH).collect();
+    let Ok(mut records) = RECORDS.lock() else { return };
+    match records.iter_mut().find(|(existing, _, _)| *existing == label) {
+        Some((_, runs, total)) => {
+            *runs += 1;
+            *total += elapsed;
+        }
+        None => records.push((label, 1, elapsed)),
+    }
+}
+
+/// Prints the timing table to stderr, slowest step first
+pub fn report() {
+    if !enabled() {
+        return;
+    }
+    let Ok(mut records) = RECORDS.lock() else { return };
+    if records.is_empty() {
+        return;
+    }
+    records.sort_by(|a, b| b.2.cmp(&a.2));
+
+    let width = records.iter().map(|(label, _, _)| label.chars().count()).max().unwrap_or(0);
+    let grand_total: Duration = records.iter().map(|(_, _, total)| *total).sum();
+    eprintln!("{:<width$}  {:>5}  {:>10}  {:>10}", "step", "runs", "total ms", "avg ms", width = width);
+    for (label, runs, total) in records.iter() {
+        eprintln!(
+            "{:<width$}  {:>5}  {:>10.1}  {:>10.1}",
+            label,
+            runs,
+            total.as_secs_f64() * 1000.0,
+            total.as_secs_f64() * 1000.0 / *runs as f64,
+            width = width
+        );
+    }
+    eprintln!("{:<width$}  {:>5}  {:>10.1}", "total", "", grand_total.as_secs_f64() * 1000.0, width = width);
+}
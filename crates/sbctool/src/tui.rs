@@ -1,6 +1,6 @@
 use anyhow::Result;
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
+    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind, MouseButton, MouseEvent, MouseEventKind},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
@@ -9,25 +9,461 @@ use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Sparkline, Tabs, Wrap},
     Frame, Terminal,
 };
+use serde::Serialize;
+use base64::Engine;
 use std::{
-    io,
-    sync::{Arc, Mutex},
+    cell::Cell,
+    collections::VecDeque,
+    io::{self, Write},
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, Mutex, OnceLock,
+    },
     time::Duration,
 };
+use tokio::sync::mpsc::UnboundedSender;
 
-#[derive(Debug, Clone)]
+/// Serialized by `sbctool info`; unknown (None) fields are omitted in every format,
+/// since TOML has no null
+#[derive(Debug, Clone, Serialize)]
 pub struct SystemInfo {
     pub hostname: String,
     pub kernel: String,
     pub architecture: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub chip: Option<String>,
+    /// Per-board ID: device-tree serial-number, the Pi's cpuinfo Serial or Android's ro.serialno
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub serial_number: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gpu: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vpu: Option<String>,
+    /// Connected outputs and their mode, e.g. "HDMI-A-1: 1920x1080@60"; None on headless boards
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display: Option<String>,
     pub cpu_info: String,
+    pub cpu_freqs: Vec<CpuFreq>,
+    /// "processor" entries in /proc/cpuinfo
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpu_cores: Option<usize>,
+    /// Busy share of all cores between two /proc/stat samples half a second apart; iowait counts as idle
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpu_usage_percent: Option<f64>,
+    /// The same per core, in /proc/stat order (cpu0, cpu1, ...)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub cpu_core_usage: Vec<f64>,
     pub memory: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory_used_percent: Option<f64>,
+    /// MemTotal in bytes, for metrics; `memory` is the rounded display value
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory_total_bytes: Option<u64>,
+    /// MemAvailable, i.e. free memory plus reclaimable cache, e.g. "1.4 GB"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mem_available: Option<String>,
+    /// Page cache plus buffers, e.g. "812.4 MB"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mem_cached: Option<String>,
+    /// Swap size and use with its devices, e.g. "1.9 GB (12% used): zram0 (zram, lz4)"; "none" without swap
+    pub swap: String,
     pub uptime: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uptime_seconds: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub load_average: Option<(f64, f64, f64)>,
     pub os_info: String,
+    /// PID 1 / service manager, e.g. "systemd", "OpenRC", "BusyBox init", "Android init"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub init_system: Option<String>,
+    /// BusyBox / toybox with the applets standing in for the usual tools, e.g. "BusyBox v1.36.1 (sh ps top)"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub userland: Option<String>,
+    /// Package manager, with the upgradable package count when `--updates` was given
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pending_updates: Option<PendingUpdates>,
+    /// Container / hypervisor the system runs in, e.g. "lxc", "docker", "kvm"; "none" on real hardware
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub virtualization: Option<String>,
+    /// The `--thermal-zone` zone, else thermal_zone0
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f64>,
+    /// Every thermal zone, serialized as a map of label to millidegrees Celsius
+    #[serde(serialize_with = "serialize_thermal_zones")]
+    pub thermal_zones: Vec<ThermalZone>,
+    /// hwmon fans and fan cooling devices; empty on passively cooled boards
+    pub fans: Vec<FanReading>,
+    pub network: Vec<NetIface>,
+    /// Decoded `vcgencmd get_throttled` flags on Raspberry Pi, "OK ..." when nothing is set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub power_status: Option<String>,
+    /// `dumpsys battery` on Android targets that report a battery as present
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub battery: Option<BatteryStatus>,
+    pub storage_devices: Vec<StorageDevice>,
+    /// Mounted filesystems with a size, from /proc/mounts and `df`
+    pub mounts: Vec<MountInfo>,
+    /// "address class: device" per PCI function, e.g. "0000:01:00.0 Network controller: Intel ... Wi-Fi 6 AX200"
+    pub pci_devices: Vec<String>,
+    /// Loaded kernel modules from /proc/modules, sorted by name; empty on kernels without module support
+    pub modules: Vec<KernelModule>,
+    /// Most recent OOM kills, panics and call traces from the kernel log
+    pub alerts: Vec<KernelAlert>,
+    /// Units in systemd's failed state, only collected when PID 1 is systemd
+    pub failed_units: Vec<FailedUnit>,
+    /// Busiest processes by CPU, then the largest by memory not already listed
+    pub top_processes: Vec<ProcessInfo>,
+    /// Exported / claimed GPIO lines, only collected with `--gpio`
+    pub gpio: Vec<GpioLine>,
+    /// Results of the user's collectors.toml probes
+    pub extra: Vec<ExtraValue>,
+    pub boot_info: BootInfo,
+    /// Device-tree overlays the board is configured with, e.g. "i2c-rtc (config.txt)"
+    pub overlays: Vec<String>,
+}
+
+impl SystemInfo {
+    /// Serialized field names in declaration order, for `info --fields`; keep in sync with the struct
+    pub const FIELD_NAMES: &[&str] = &[
+        "hostname", "kernel", "architecture", "chip", "serial_number", "gpu", "vpu", "display", "cpu_info", "cpu_freqs", "cpu_cores", "cpu_usage_percent", "cpu_core_usage",
+        "memory", "memory_used_percent", "memory_total_bytes", "mem_available", "mem_cached", "swap", "uptime", "uptime_seconds", "load_average", "os_info", "init_system", "userland", "pending_updates",
+        "virtualization", "temperature", "thermal_zones", "fans", "network", "power_status", "battery", "storage_devices", "mounts",
+        "pci_devices", "modules", "alerts", "failed_units", "top_processes", "gpio", "extra", "boot_info", "overlays",
+    ];
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CpuFreq {
+    pub core: usize,
+    pub cur_mhz: u64,
+    pub max_mhz: u64,
+}
+
+/// One `/sys/class/thermal/thermal_zone*`
+#[derive(Debug, Clone)]
+pub struct ThermalZone {
+    /// N of thermal_zoneN
+    pub index: u32,
+    /// The zone's `type`, e.g. "cpu-thermal" or "gpu-thermal", made unique with " #N" when zones share one
+    pub label: String,
+    pub millidegrees: i64,
+}
+
+impl ThermalZone {
+    pub fn celsius(&self) -> f64 {
+        self.millidegrees as f64 / 1000.0
+    }
+
+    /// `--thermal-zone` matches the zone number, the label or the bare type, ignoring case
+    fn matches(&self, selector: &str) -> bool {
+        selector.parse::<u32>().is_ok_and(|index| index == self.index)
+            || self.label.eq_ignore_ascii_case(selector)
+            || self.label.split(" #").next().is_some_and(|kind| kind.eq_ignore_ascii_case(selector))
+    }
+}
+
+/// Zones in zone order as `{"cpu-thermal": 45000, ...}`, so tooling can pick one by label
+fn serialize_thermal_zones<S: serde::Serializer>(zones: &[ThermalZone], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_map(zones.iter().map(|zone| (&zone.label, zone.millidegrees)))
+}
+
+/// From `--thermal-zone`; unset means every zone is shown and thermal_zone0 is the temperature
+static THERMAL_ZONE: OnceLock<String> = OnceLock::new();
+
+pub fn set_thermal_zone(selector: &str) {
+    let _ = THERMAL_ZONE.set(selector.to_string());
+}
+
+/// Set once the "no such zone" warning was shown, so refreshes don't repeat it
+static THERMAL_ZONE_WARNED: AtomicBool = AtomicBool::new(false);
+
+/// The `--thermal-zone` zone when one was given, else thermal_zone0 (or the first zone listed)
+pub fn selected_thermal_zone(zones: &[ThermalZone]) -> Option<&ThermalZone> {
+    let Some(selector) = THERMAL_ZONE.get() else {
+        return zones.iter().find(|zone| zone.index == 0).or(zones.first());
+    };
+    let zone = zones.iter().find(|zone| zone.matches(selector));
+    if zone.is_none() && !zones.is_empty() && !THERMAL_ZONE_WARNED.swap(true, Ordering::Relaxed) {
+        let available: Vec<String> = zones.iter().map(|zone| format!("{} ({})", zone.index, zone.label)).collect();
+        crate::verbose::status(&format!("--thermal-zone {} matches no thermal zone; available: {}", selector, available.join(", ")));
+    }
+    zone
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FanReading {
+    /// hwmon chip and fan ("pwmfan fan1") or the cooling device type ("pwm-fan")
+    pub label: String,
+    /// Tachometer reading, when the fan has one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rpm: Option<u32>,
+    /// PWM duty cycle or cooling state as a percentage of the maximum
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duty_percent: Option<u8>,
+}
+
+impl FanReading {
+    pub fn value_label(&self) -> String {
+        match (self.rpm, self.duty_percent) {
+            (Some(rpm), Some(duty)) => format!("{} RPM ({}%)", rpm, duty),
+            (Some(rpm), None) => format!("{} RPM", rpm),
+            (None, Some(duty)) => format!("{}%", duty),
+            (None, None) => "n/a".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BatteryStatus {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub level_percent: Option<u8>,
+    /// "charging", "discharging", "not charging", "full" or "unknown"
+    pub status: String,
+    /// Power sources plugged in: "AC", "USB", "Wireless", "Dock"
+    pub plugged: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature_c: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub voltage_mv: Option<u32>,
+}
+
+impl BatteryStatus {
+    /// "85% charging (USB), 28.5°C, 4.123 V"
+    pub fn summary(&self) -> String {
+        let mut summary = match self.level_percent {
+            Some(level) => format!("{}% {}", level, self.status),
+            None => self.status.clone(),
+        };
+        if !self.plugged.is_empty() {
+            summary.push_str(&format!(" ({})", self.plugged.join("+")));
+        }
+        if let Some(temperature) = self.temperature_c {
+            summary.push_str(&format!(", {:.1}°C", temperature));
+        }
+        if let Some(voltage) = self.voltage_mv {
+            summary.push_str(&format!(", {:.3} V", voltage as f64 / 1000.0));
+        }
+        summary
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct NetIface {
+    pub name: String,
+    pub ipv4: Vec<String>,
+    pub ipv6: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mac: Option<String>,
+    pub state: String,
+    /// Association details for wireless interfaces that are connected
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wifi: Option<WifiLink>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct WifiLink {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ssid: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signal_dbm: Option<i32>,
+    /// As `iw` reports it, e.g. "433.3 MBit/s"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tx_bitrate: Option<String>,
+}
+
+impl WifiLink {
+    /// 0-4 bars, roughly as phones show them
+    pub fn signal_bars(&self) -> Option<usize> {
+        Some(match self.signal_dbm? {
+            -50.. => 4,
+            -60..=-51 => 3,
+            -70..=-61 => 2,
+            -80..=-71 => 1,
+            _ => 0,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct KernelAlert {
+    /// As printed by the source: wall clock from `dmesg -T` / journald, seconds since boot from plain `dmesg`
+    pub timestamp: String,
+    /// "OOM", "panic" or "call trace"
+    pub kind: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FailedUnit {
+    /// e.g. "nginx.service"
+    pub name: String,
+    /// The unit's Description=
+    pub description: String,
+    /// From the "Active: failed (Result: ...)" line, e.g. "exit-code", "timeout", "core-dump"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<String>,
+    /// Newest journal line from the unit itself; needs root or the systemd-journal group
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_log: Option<String>,
+}
+
+impl FailedUnit {
+    /// "Nginx web server (exit-code): bind() to 0.0.0.0:80 failed"
+    pub fn summary(&self) -> String {
+        let mut summary = self.description.clone();
+        if let Some(result) = &self.result {
+            summary.push_str(&format!(" ({})", result));
+        }
+        if let Some(last_log) = &self.last_log {
+            summary.push_str(&format!(": {}", last_log));
+        }
+        summary
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct KernelModule {
+    pub name: String,
+    /// Reference count: dependent modules plus open users, e.g. a mounted filesystem
+    pub use_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProcessInfo {
+    pub pid: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpu_percent: Option<f64>,
+    /// Missing from busybox `top`, which only reports %VSZ
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mem_percent: Option<f64>,
+    pub command: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct StorageDevice {
+    pub name: String,
+    pub size_bytes: u64,
+    /// eMMC/SD product name from the card's CID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// "SD" or "MMC" for mmcblk devices
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub media: Option<String>,
+    /// Worst of the eMMC life-time estimates: 1 = 0-10% used ... 10 = 90-100%, 11 = exceeded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wear_level: Option<u8>,
+}
+
+impl StorageDevice {
+    pub fn wear_label(&self) -> Option<String> {
+        match self.wear_level? {
+            11 => Some("life exceeded".to_string()),
+            level => Some(format!("{}-{}% used", (level - 1) * 10, level * 10)),
+        }
+    }
+
+    pub fn size_label(&self) -> String {
+        format!("{:.1} GB", self.size_bytes as f64 / 1_000_000_000.0)
+    }
+}
+
+/// Usage from which a mount is flagged as nearly full
+pub const MOUNT_NEAR_FULL_PERCENT: f64 = 90.0;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MountInfo {
+    pub device: String,
+    pub mountpoint: String,
+    pub fstype: String,
+    /// Mounted with "ro", e.g. a root filesystem remounted read-only after errors
+    pub read_only: bool,
+    pub size_bytes: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub used_percent: Option<f64>,
+}
+
+impl MountInfo {
+    pub fn size_label(&self) -> String {
+        if self.size_bytes >= 1_000_000_000 {
+            format!("{:.1} GB", self.size_bytes as f64 / 1_000_000_000.0)
+        } else {
+            format!("{:.0} MB", self.size_bytes as f64 / 1_000_000.0)
+        }
+    }
+
+    pub fn is_near_full(&self) -> bool {
+        self.used_percent.is_some_and(|used| used >= MOUNT_NEAR_FULL_PERCENT)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GpioLine {
+    /// "sysfs" for /sys/class/gpio exports, otherwise the gpiochip name
+    pub chip: String,
+    pub line: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub direction: String,
+    /// Only known for sysfs exports
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub consumer: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PendingUpdates {
+    /// "apt", "dnf", "yum", "pacman", "apk" or "opkg"
+    pub manager: String,
+    /// Upgradable packages per the cached package lists; None without `--updates` or when the count failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<u32>,
+}
+
+impl PendingUpdates {
+    /// e.g. "apt (12 upgradable)", or just "apt" without a count
+    pub fn label(&self) -> String {
+        match self.count {
+            Some(count) => format!("{} ({} upgradable)", self.manager, count),
+            None => self.manager.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ExtraValue {
+    pub label: String,
+    /// None when the command failed or the regex didn't match
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+}
+
+/// Kernel command line and what the bootloader passed in through the device tree
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BootInfo {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cmdline: Option<String>,
+    /// `/chosen/bootargs`, only kept when it differs from the running cmdline
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bootargs: Option<String>,
+    /// The `root=` parameter, e.g. "/dev/mmcblk1p2" or "PARTUUID=..."
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub root: Option<String>,
+    /// Device actually mounted on `/`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub root_mount: Option<String>,
+    /// `/chosen/u-boot,version` when the bootloader exposes it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bootloader: Option<String>,
+}
+
+impl BootInfo {
+    pub fn is_empty(&self) -> bool {
+        self.cmdline.is_none() && self.root_mount.is_none() && self.bootloader.is_none()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -37,42 +473,523 @@ pub struct LogEntry {
     pub message: String,
 }
 
-pub struct TuiApp {
+/// Minimum number of log entries kept in memory
+const MAX_LOGS: usize = 100;
+
+/// Log entries kept in memory, raised by `--log-lines` so the fetched history fits
+static LOG_CAPACITY: AtomicUsize = AtomicUsize::new(MAX_LOGS);
+
+pub fn set_log_capacity(log_lines: usize) {
+    LOG_CAPACITY.store(log_lines.max(MAX_LOGS), Ordering::Relaxed);
+}
+
+/// Limits for the health line; a value at or above `*_warn` turns it yellow, at or above `*_crit` red
+#[derive(Debug, Clone, Copy)]
+pub struct HealthThresholds {
+    pub temp_warn: f64,
+    pub temp_crit: f64,
+    pub memory_warn: f64,
+    pub memory_crit: f64,
+}
+
+impl Default for HealthThresholds {
+    fn default() -> Self {
+        Self {
+            temp_warn: 70.0,
+            temp_crit: 85.0,
+            memory_warn: 80.0,
+            memory_crit: 95.0,
+        }
+    }
+}
+
+/// From `--temp-warn` / `--temp-crit` / `--mem-warn` / `--mem-crit`; defaults when unset
+static HEALTH_THRESHOLDS: OnceLock<HealthThresholds> = OnceLock::new();
+
+pub fn set_health_thresholds(thresholds: HealthThresholds) {
+    let _ = HEALTH_THRESHOLDS.set(thresholds);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Health {
+    Ok,
+    Warn,
+    Critical,
+}
+
+/// Worst state over the thresholds, failed units, Pi under-voltage and the connection, with the reasons
+fn assess_health(info: &SystemInfo, connection_lost: bool) -> (Health, Vec<String>) {
+    let thresholds = HEALTH_THRESHOLDS.get().copied().unwrap_or_default();
+    let mut findings: Vec<(Health, String)> = Vec::new();
+
+    if connection_lost {
+        findings.push((Health::Critical, "connection lost".to_string()));
+    }
+    if let Some(temp) = info.temperature {
+        if temp >= thresholds.temp_crit {
+            findings.push((Health::Critical, format!("temp {:.0}°C", temp)));
+        } else if temp >= thresholds.temp_warn {
+            findings.push((Health::Warn, format!("temp {:.0}°C", temp)));
+        }
+    }
+    if let Some(used) = info.memory_used_percent {
+        if used >= thresholds.memory_crit {
+            findings.push((Health::Critical, format!("memory {:.0}%", used)));
+        } else if used >= thresholds.memory_warn {
+            findings.push((Health::Warn, format!("memory {:.0}%", used)));
+        }
+    }
+    if !info.failed_units.is_empty() {
+        findings.push((Health::Warn, format!("{} failed unit(s)", info.failed_units.len())));
+    }
+    if let Some(power) = &info.power_status {
+        if power.contains("under-voltage now") {
+            findings.push((Health::Critical, "under-voltage".to_string()));
+        } else if power.contains("under-voltage past") {
+            findings.push((Health::Warn, "under-voltage since boot".to_string()));
+        }
+    }
+
+    let health = findings.iter().map(|(health, _)| *health).max().unwrap_or(Health::Ok);
+    (health, findings.into_iter().map(|(_, reason)| reason).collect())
+}
+
+/// Bounds for dragging the divider, as a percentage of the space given to system info
+const MIN_INFO_PERCENT: u16 = 20;
+const MAX_INFO_PERCENT: u16 = 80;
+
+/// Terminals less than this many columns per row start stacked; a cell is roughly twice as tall as wide
+const SIDE_BY_SIDE_MIN_ASPECT: u16 = 2;
+
+/// Lines moved per mouse wheel step
+const WHEEL_STEP: isize = 3;
+
+/// Panel that receives the arrow keys
+#[derive(Clone, Copy, PartialEq)]
+enum Panel {
+    Info,
+    Logs,
+}
+
+/// Rows of the boot panel, borders included; long command lines wrap inside it
+const BOOT_PANEL_HEIGHT: u16 = 10;
+
+/// Rows per sparkline (title plus bars), and the trends panel holding three of them with borders
+const TREND_HEIGHT: u16 = 3;
+const TRENDS_PANEL_HEIGHT: u16 = 3 * TREND_HEIGHT + 2;
+
+/// Appends a log entry, keeping only the last LOG_CAPACITY and forwarding it to `--log-file`
+pub fn push_log(logs: &mut Vec<LogEntry>, entry: LogEntry) {
+    crate::log_export::record(&entry);
+
+    let capacity = LOG_CAPACITY.load(Ordering::Relaxed);
+    logs.push(entry);
+    if logs.len() > capacity {
+        let len = logs.len();
+        logs.drain(0..len - capacity);
+    }
+}
+
+/// Appends a streamed log entry unless it repeats the last one displayed (same timestamp and
+/// message), so a line delivered twice in a row shows once
+pub fn push_log_dedup(logs: &mut Vec<LogEntry>, entry: LogEntry) {
+    if logs.last().is_some_and(|log| is_repeat(log, &entry)) {
+        return;
+    }
+
+    push_log(logs, entry);
+}
+
+/// Appends a poll's entries (the last N lines of a log) that an earlier poll didn't: those after
+/// the newest one already among the last N displayed, so only that tail of the buffer is compared
+pub fn push_polled_logs(logs: &mut Vec<LogEntry>, batch: Vec<LogEntry>) {
+    let recent = &logs[logs.len().saturating_sub(batch.len())..];
+    let seen = batch.iter().rposition(|entry| recent.iter().any(|log| is_repeat(log, entry)));
+    for entry in batch.into_iter().skip(seen.map_or(0, |index| index + 1)) {
+        push_log(logs, entry);
+    }
+}
+
+fn is_repeat(log: &LogEntry, entry: &LogEntry) -> bool {
+    log.timestamp == entry.timestamp && log.message == entry.message
+}
+
+/// Logs shown in the panel, newest first, filtered by a case-insensitive substring
+fn matching_logs<'a>(logs: &'a [LogEntry], query: &str) -> Vec<&'a LogEntry> {
+    let query = query.to_lowercase();
+    logs.iter()
+        .rev()
+        .filter(|log| query.is_empty() || log.message.to_lowercase().contains(&query))
+        .collect()
+}
+
+/// Splits a message into spans with every occurrence of `query` highlighted. Matching walks the
+/// message's own chars, since lowercasing can change byte lengths and char boundaries.
+fn highlight_matches<'a>(message: &'a str, query: &str) -> Vec<Span<'a>> {
+    let query = query.to_lowercase();
+    if query.is_empty() {
+        return vec![Span::raw(message)];
+    }
+
+    let highlight = Style::default().fg(Color::Black).bg(Color::Yellow).add_modifier(Modifier::BOLD);
+    let mut spans = Vec::new();
+    let mut last = 0;
+    for (start, _) in message.char_indices() {
+        if start < last {
+            continue;
+        }
+        let Some(len) = match_len(&message[start..], &query) else {
+            continue;
+        };
+        if start > last {
+            spans.push(Span::raw(&message[last..start]));
+        }
+        spans.push(Span::styled(&message[start..start + len], highlight));
+        last = start + len;
+    }
+    if last < message.len() {
+        spans.push(Span::raw(&message[last..]));
+    }
+    spans
+}
+
+/// Byte length of the prefix of `text` that lowercases to `query` (already lowercase), if any
+fn match_len(text: &str, query: &str) -> Option<usize> {
+    let mut rest = query;
+    for (index, c) in text.char_indices() {
+        for lower in c.to_lowercase() {
+            rest = rest.strip_prefix(lower)?;
+        }
+        if rest.is_empty() {
+            return Some(index + c.len_utf8());
+        }
+    }
+    None
+}
+
+/// Samples kept for the trend sparklines, one per collection
+const HISTORY_LEN: usize = 120;
+
+/// Rolling CPU-busy / memory / load samples of one target, appended on every collection
+#[derive(Default)]
+pub struct MetricHistory {
+    /// CPU busy, whole percent
+    cpu_usage: VecDeque<u64>,
+    /// Memory used, whole percent
+    memory_used: VecDeque<u64>,
+    /// 1-minute load average times 100, since sparklines take integers
+    load: VecDeque<u64>,
+}
+
+impl MetricHistory {
+    pub fn push(&mut self, info: &SystemInfo) {
+        if let Some(percent) = info.cpu_usage_percent {
+            push_sample(&mut self.cpu_usage, percent.round() as u64);
+        }
+        if let Some(percent) = info.memory_used_percent {
+            push_sample(&mut self.memory_used, percent.round() as u64);
+        }
+        if let Some((one, _, _)) = info.load_average {
+            push_sample(&mut self.load, (one * 100.0).round() as u64);
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.cpu_usage.is_empty() && self.memory_used.is_empty() && self.load.is_empty()
+    }
+}
+
+fn push_sample(samples: &mut VecDeque<u64>, value: u64) {
+    if samples.len() == HISTORY_LEN {
+        samples.pop_front();
+    }
+    samples.push_back(value);
+}
+
+/// Newest samples that fit in `width` columns; Sparkline draws from the start of its data
+fn latest_samples(samples: &VecDeque<u64>, width: u16) -> Vec<u64> {
+    samples.iter().skip(samples.len().saturating_sub(width as usize)).copied().collect()
+}
+
+/// State shared with the background tasks of one monitored target
+pub struct TargetView {
+    pub name: String,
     pub system_info: Arc<Mutex<Option<SystemInfo>>>,
+    pub history: Arc<Mutex<MetricHistory>>,
     pub logs: Arc<Mutex<Vec<LogEntry>>>,
+    pub last_refresh: Arc<Mutex<Option<String>>>,
+    /// Set by the collector task while collections fail, cleared by the next successful one
+    pub connection_lost: Arc<AtomicBool>,
+    refresh_sender: Option<UnboundedSender<()>>,
+    /// Asks the target's task to rebuild its SSH session (`R`); None for ADB targets
+    reconnect_sender: Option<UnboundedSender<()>>,
+}
+
+impl TargetView {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            system_info: Arc::new(Mutex::new(None)),
+            history: Arc::new(Mutex::new(MetricHistory::default())),
+            logs: Arc::new(Mutex::new(Vec::new())),
+            last_refresh: Arc::new(Mutex::new(None)),
+            connection_lost: Arc::new(AtomicBool::new(false)),
+            refresh_sender: None,
+            reconnect_sender: None,
+        }
+    }
+
+    pub fn set_refresh_sender(&mut self, sender: UnboundedSender<()>) {
+        self.refresh_sender = Some(sender);
+    }
+
+    pub fn set_reconnect_sender(&mut self, sender: UnboundedSender<()>) {
+        self.reconnect_sender = Some(sender);
+    }
+
+    pub fn add_log(&self, entry: LogEntry) {
+        if let Ok(mut logs) = self.logs.lock() {
+            push_log(&mut logs, entry);
+        }
+    }
+}
+
+pub struct TuiApp {
+    /// One entry per target; a tab bar is shown when there is more than one
+    pub targets: Vec<TargetView>,
+    /// Index of the target shown in the panels
+    focus: usize,
     pub should_quit: bool,
+    pub shutdown: Arc<AtomicBool>,
+    /// Number of entries scrolled back from the newest log (0 = live tail)
+    log_scroll: usize,
+    /// Timestamp and message of the newest visible entry while scrolled back
+    scroll_anchor: Option<(String, String)>,
+    /// Rows available in the logs panel during the last draw
+    log_view_height: Cell<usize>,
+    /// Substring filter for the logs panel
+    search: String,
+    /// Whether keystrokes currently go to the search input line
+    search_active: bool,
+    /// Panel focused by clicking it
+    focused_panel: Panel,
+    /// Share of the system info panel in percent, changed by dragging the divider
+    info_percent: u16,
+    /// `Horizontal` puts system info left of the logs, `Vertical` above them; toggled with `v`
+    split: Direction,
+    /// Whether the divider is being dragged with the mouse
+    dragging_divider: bool,
+    /// Lines scrolled down in the system info panel
+    info_scroll: u16,
+    /// Lines in the system info panel during the last draw, to clamp `info_scroll`
+    info_line_count: Cell<u16>,
+    /// Timestamp and message of the log entry selected by clicking it
+    selected_log: Option<(String, String)>,
+    /// Panel areas from the last draw, for hit-testing mouse events
+    content_area: Cell<Rect>,
+    info_area: Cell<Rect>,
+    logs_area: Cell<Rect>,
 }
 
 impl TuiApp {
     pub fn new() -> Self {
+        Self::with_targets(&[String::new()])
+    }
+
+    /// Also takes over status and `-v` output for the logs panels until `restore_terminal`
+    pub fn with_targets(names: &[String]) -> Self {
+        let targets: Vec<TargetView> = names.iter().map(|name| TargetView::new(name)).collect();
+        crate::verbose::attach_tui(targets.iter().map(|target| (target.name.clone(), target.logs.clone())).collect());
         Self {
-            system_info: Arc::new(Mutex::new(None)),
-            logs: Arc::new(Mutex::new(Vec::new())),
+            targets,
+            focus: 0,
             should_quit: false,
+            shutdown: Arc::new(AtomicBool::new(false)),
+            log_scroll: 0,
+            scroll_anchor: None,
+            log_view_height: Cell::new(20),
+            search: String::new(),
+            search_active: false,
+            focused_panel: Panel::Logs,
+            info_percent: 50,
+            split: Direction::Horizontal,
+            dragging_divider: false,
+            info_scroll: 0,
+            info_line_count: Cell::new(0),
+            selected_log: None,
+            content_area: Cell::new(Rect::default()),
+            info_area: Cell::new(Rect::default()),
+            logs_area: Cell::new(Rect::default()),
         }
     }
 
+    /// The target currently shown in the panels
+    fn current(&self) -> &TargetView {
+        &self.targets[self.focus]
+    }
+
+    pub fn set_refresh_sender(&mut self, sender: UnboundedSender<()>) {
+        self.targets[self.focus].set_refresh_sender(sender);
+    }
+
     pub fn update_system_info(&self, info: SystemInfo) {
-        if let Ok(mut system_info) = self.system_info.lock() {
+        if let Ok(mut history) = self.current().history.lock() {
+            history.push(&info);
+        }
+        if let Ok(mut system_info) = self.current().system_info.lock() {
             *system_info = Some(info);
         }
     }
 
     pub fn add_log(&self, entry: LogEntry) {
-        if let Ok(mut logs) = self.logs.lock() {
-            logs.push(entry);
-            // Keep only last 100 logs
-            if logs.len() > 100 {
-                let len = logs.len();
-                logs.drain(0..len - 100);
+        self.current().add_log(entry);
+    }
+
+    fn focus_target(&mut self, index: usize) {
+        if index < self.targets.len() && index != self.focus {
+            self.focus = index;
+            // Scroll position belongs to the previous target's log list
+            self.log_scroll = 0;
+            self.scroll_anchor = None;
+            self.info_scroll = 0;
+            self.selected_log = None;
+        }
+    }
+
+    fn scroll_info(&mut self, delta: isize) {
+        let max = self.info_line_count.get().saturating_sub(1);
+        self.info_scroll = (self.info_scroll as isize + delta).clamp(0, max as isize) as u16;
+    }
+
+    /// Click to focus a panel or select a log line, drag the divider to resize, wheel to scroll
+    fn handle_mouse(&mut self, mouse: MouseEvent) {
+        let (column, row) = (mouse.column, mouse.row);
+        let contains = |area: Rect| {
+            column >= area.x && column < area.right() && row >= area.y && row < area.bottom()
+        };
+        let info_area = self.info_area.get();
+        let logs_area = self.logs_area.get();
+
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                // The divider is the pair of touching borders between the two panels
+                let content = self.content_area.get();
+                let on_divider = match self.split {
+                    Direction::Horizontal => {
+                        (column + 1 == info_area.right() || column == info_area.right())
+                            && row >= content.y
+                            && row < content.bottom()
+                    }
+                    Direction::Vertical => {
+                        (row + 1 == info_area.bottom() || row == info_area.bottom())
+                            && column >= content.x
+                            && column < content.right()
+                    }
+                };
+                if on_divider {
+                    self.dragging_divider = true;
+                } else if contains(logs_area) {
+                    self.focused_panel = Panel::Logs;
+                    let inside_border = row > logs_area.y && row + 1 < logs_area.bottom();
+                    self.select_log_at(row.saturating_sub(logs_area.y + 1) as usize, inside_border);
+                } else if contains(info_area) {
+                    self.focused_panel = Panel::Info;
+                }
             }
+            MouseEventKind::Drag(MouseButton::Left) if self.dragging_divider => {
+                let content = self.content_area.get();
+                let (offset, length) = match self.split {
+                    Direction::Horizontal => (column.saturating_sub(content.x), content.width),
+                    Direction::Vertical => (row.saturating_sub(content.y), content.height),
+                };
+                if length > 0 {
+                    let percent = (offset as u32 * 100 / length as u32) as u16;
+                    self.info_percent = percent.clamp(MIN_INFO_PERCENT, MAX_INFO_PERCENT);
+                }
+            }
+            MouseEventKind::Up(MouseButton::Left) => self.dragging_divider = false,
+            MouseEventKind::ScrollUp if contains(logs_area) => self.scroll_logs(WHEEL_STEP),
+            MouseEventKind::ScrollDown if contains(logs_area) => self.scroll_logs(-WHEEL_STEP),
+            MouseEventKind::ScrollUp if contains(info_area) => self.scroll_info(-WHEEL_STEP),
+            MouseEventKind::ScrollDown if contains(info_area) => self.scroll_info(WHEEL_STEP),
+            _ => {}
         }
     }
 
+    /// Selects the log entry shown on `line` of the list; clicking it again clears the selection
+    fn select_log_at(&mut self, line: usize, inside_border: bool) {
+        let logs_handle = self.current().logs.clone();
+        let Ok(logs) = logs_handle.lock() else { return };
+        let visible = matching_logs(&logs, &self.search);
+        let offset = self.log_scroll.min(visible.len().saturating_sub(1));
+
+        let clicked = visible
+            .get(offset + line)
+            .filter(|_| inside_border)
+            .map(|log| (log.timestamp.clone(), log.message.clone()));
+        self.selected_log = if clicked == self.selected_log { None } else { clicked };
+    }
+
+    /// Copies the selected log line to the clipboard with an OSC 52 escape,
+    /// which most terminals (and tmux with set-clipboard) forward to the system clipboard
+    fn copy_selected_log(&self) -> Result<()> {
+        let Some((timestamp, message)) = &self.selected_log else { return Ok(()) };
+        let text = format!("[{}] {}", timestamp, message);
+        let encoded = base64::engine::general_purpose::STANDARD.encode(text);
+
+        let mut stdout = io::stdout();
+        write!(stdout, "\x1b]52;c;{}\x07", encoded)?;
+        stdout.flush()?;
+        Ok(())
+    }
+
+    fn panel_border_style(&self, panel: Panel) -> Style {
+        if self.focused_panel == panel {
+            Style::default().fg(Color::Yellow)
+        } else {
+            Style::default()
+        }
+    }
+
+    fn scroll_logs(&mut self, delta: isize) {
+        self.log_scroll = self.log_scroll.saturating_add_signed(delta);
+        self.scroll_anchor = None;
+        self.sync_log_scroll();
+    }
+
+    /// Keeps the view pinned to the same entry while new logs arrive and clamps the offset
+    fn sync_log_scroll(&mut self) {
+        let logs_handle = self.current().logs.clone();
+        let Ok(logs) = logs_handle.lock() else { return };
+        let visible = matching_logs(&logs, &self.search);
+
+        if let Some((timestamp, message)) = &self.scroll_anchor {
+            if let Some(pos) = visible.iter().position(|log| &log.timestamp == timestamp && &log.message == message) {
+                self.log_scroll = pos;
+            }
+        }
+
+        self.log_scroll = self.log_scroll.min(visible.len().saturating_sub(1));
+        self.scroll_anchor = if self.log_scroll == 0 {
+            None
+        } else {
+            visible
+                .get(self.log_scroll)
+                .map(|log| (log.timestamp.clone(), log.message.clone()))
+        };
+    }
+
     pub fn run(&mut self, terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, timeout_seconds: u64) -> Result<()> {
+        // Stack the panels on tall, narrow terminals; `v` switches later
+        let size = terminal.size()?;
+        if size.width < size.height * SIDE_BY_SIDE_MIN_ASPECT {
+            self.split = Direction::Vertical;
+        }
+
         let start_time = std::time::Instant::now();
         
         loop {
+            self.sync_log_scroll();
             terminal.draw(|f| self.ui(f))?;
 
             // Check for timeout
@@ -91,8 +1008,43 @@ impl TuiApp {
             }
 
             if event::poll(Duration::from_millis(100))? {
-                            if let Event::Key(key) = event::read()? {
+                let event = event::read()?;
+                if let Event::Mouse(mouse) = event {
+                    self.handle_mouse(mouse);
+                    continue;
+                }
+                            if let Event::Key(key) = event {
+                if self.search_active {
+                    // Search input line owns the keyboard until Enter or Esc
+                    if key.kind == KeyEventKind::Press {
+                        match key.code {
+                            KeyCode::Esc => {
+                                self.search.clear();
+                                self.search_active = false;
+                            }
+                            KeyCode::Enter => self.search_active = false,
+                            KeyCode::Backspace => {
+                                self.search.pop();
+                            }
+                            KeyCode::Char(c) => self.search.push(c),
+                            _ => {}
+                        }
+                        self.log_scroll = 0;
+                        self.scroll_anchor = None;
+                    }
+                    continue;
+                }
+
                 match key.code {
+                    KeyCode::Char('/') => {
+                        self.search_active = true;
+                    }
+                    KeyCode::Esc if !self.search.is_empty() => {
+                        // First Esc clears an active filter instead of quitting
+                        self.search.clear();
+                        self.log_scroll = 0;
+                        self.scroll_anchor = None;
+                    }
                     KeyCode::Char('q') | KeyCode::Esc => {
                         // Add exit log
                         self.add_log(LogEntry {
@@ -110,6 +1062,73 @@ impl TuiApp {
                             level: "INFO".to_string(),
                             message: "Refreshing system information...".to_string(),
                         });
+                        if let Some(sender) = &self.current().refresh_sender {
+                            if sender.send(()).is_err() {
+                                self.add_log(LogEntry {
+                                    timestamp: chrono::Local::now().format("%H:%M:%S").to_string(),
+                                    level: "WARN".to_string(),
+                                    message: "System info collector is no longer running".to_string(),
+                                });
+                            }
+                        }
+                    }
+                    KeyCode::Char('R') => {
+                        let message = match &self.current().reconnect_sender {
+                            Some(sender) if sender.send(()).is_ok() => None,
+                            Some(_) => Some("System info collector is no longer running"),
+                            None => Some("Reconnecting is only available for SSH targets"),
+                        };
+                        if let Some(message) = message {
+                            self.add_log(LogEntry {
+                                timestamp: chrono::Local::now().format("%H:%M:%S").to_string(),
+                                level: "WARN".to_string(),
+                                message: message.to_string(),
+                            });
+                        }
+                    }
+                    KeyCode::Up if self.focused_panel == Panel::Info => self.scroll_info(-1),
+                    KeyCode::Down if self.focused_panel == Panel::Info => self.scroll_info(1),
+                    KeyCode::Up => self.scroll_logs(1),
+                    KeyCode::Down => self.scroll_logs(-1),
+                    KeyCode::Char('v') => {
+                        self.split = match self.split {
+                            Direction::Horizontal => Direction::Vertical,
+                            Direction::Vertical => Direction::Horizontal,
+                        };
+                        self.dragging_divider = false;
+                    }
+                    KeyCode::Char('y') => {
+                        if self.copy_selected_log().is_ok() && self.selected_log.is_some() {
+                            self.add_log(LogEntry {
+                                timestamp: chrono::Local::now().format("%H:%M:%S").to_string(),
+                                level: "INFO".to_string(),
+                                message: "Copied selected log line to the clipboard".to_string(),
+                            });
+                        }
+                    }
+                    KeyCode::PageUp => {
+                        let page = self.log_view_height.get().max(1) as isize;
+                        self.scroll_logs(page);
+                    }
+                    KeyCode::PageDown => {
+                        let page = self.log_view_height.get().max(1) as isize;
+                        self.scroll_logs(-page);
+                    }
+                    KeyCode::Tab => {
+                        let next = (self.focus + 1) % self.targets.len();
+                        self.focus_target(next);
+                    }
+                    KeyCode::BackTab => {
+                        let previous = (self.focus + self.targets.len() - 1) % self.targets.len();
+                        self.focus_target(previous);
+                    }
+                    KeyCode::Char(c @ '1'..='9') => {
+                        self.focus_target(c as usize - '1' as usize);
+                    }
+                    KeyCode::End | KeyCode::Char('G') => {
+                        // Jump back to the live tail
+                        self.log_scroll = 0;
+                        self.scroll_anchor = None;
                     }
                     _ => {}
                 }
@@ -120,29 +1139,146 @@ impl TuiApp {
                 break;
             }
         }
+
+        // Tell background tasks (e.g. the live log stream) to stop
+        self.shutdown.store(true, Ordering::Relaxed);
         Ok(())
     }
 
     fn ui(&self, f: &mut Frame) {
-        // Create main layout with helper bar at bottom
+        // Target tabs on top when monitoring several boards
+        let area = if self.targets.len() > 1 {
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(3), Constraint::Min(0)].as_ref())
+                .split(f.area());
+            self.render_tabs(f, chunks[0]);
+            chunks[1]
+        } else {
+            f.area()
+        };
+
+        // Create main layout with helper bar (and search line when in use) at bottom
+        let show_search = self.search_active || !self.search.is_empty();
+        let constraints = if show_search {
+            vec![Constraint::Min(0), Constraint::Length(3), Constraint::Length(3)]
+        } else {
+            vec![Constraint::Min(0), Constraint::Length(3)]
+        };
         let main_chunks = Layout::default()
             .direction(Direction::Vertical)
-            .constraints([Constraint::Min(0), Constraint::Length(3)].as_ref())
-            .split(f.area());
+            .constraints(constraints)
+            .split(area);
 
-        // Create horizontal layout for system info and logs
+        // System info and logs side by side or stacked
         let content_chunks = Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
+            .direction(self.split)
+            .constraints([Constraint::Percentage(self.info_percent), Constraint::Percentage(100 - self.info_percent)].as_ref())
             .split(main_chunks[0]);
+        self.content_area.set(main_chunks[0]);
+        self.info_area.set(content_chunks[0]);
+
+        // Kernel alerts, failed units and the process table above the logs once something was collected
+        let (alert_count, process_count) = self
+            .current()
+            .system_info
+            .lock()
+            .map(|info| {
+                info.as_ref()
+                    .map_or((0, 0), |info| (info.alerts.len() + info.failed_units.len(), info.top_processes.len()))
+            })
+            .unwrap_or((0, 0));
+        let mut log_constraints = Vec::new();
+        if alert_count > 0 {
+            log_constraints.push(Constraint::Length(alert_count as u16 + 2));
+        }
+        if process_count > 0 {
+            log_constraints.push(Constraint::Length(process_count as u16 + 3));
+        }
+        log_constraints.push(Constraint::Min(0));
+        let log_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(log_constraints)
+            .split(content_chunks[1]);
+        if alert_count > 0 {
+            self.render_alerts(f, log_chunks[0]);
+        }
+        if process_count > 0 {
+            self.render_processes(f, log_chunks[log_chunks.len() - 2]);
+        }
+        let logs_chunk = log_chunks[log_chunks.len() - 1];
+        self.logs_area.set(logs_chunk);
+
+        // Boot panel under the system info once something was collected
+        let has_boot_info = self
+            .current()
+            .system_info
+            .lock()
+            .map(|info| info.as_ref().is_some_and(|info| !info.boot_info.is_empty() || !info.overlays.is_empty()))
+            .unwrap_or(false);
+        let has_history = self.current().history.lock().map(|history| !history.is_empty()).unwrap_or(false);
+        let mut info_constraints = vec![Constraint::Min(0)];
+        if has_history {
+            info_constraints.push(Constraint::Length(TRENDS_PANEL_HEIGHT));
+        }
+        if has_boot_info {
+            info_constraints.push(Constraint::Length(BOOT_PANEL_HEIGHT));
+        }
+        let info_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(info_constraints)
+            .split(content_chunks[0]);
+        self.render_system_info(f, info_chunks[0]);
+        if has_history {
+            self.render_trends(f, info_chunks[1]);
+        }
+        if has_boot_info {
+            self.render_boot_info(f, info_chunks[info_chunks.len() - 1]);
+        }
+        self.render_logs(f, logs_chunk);
+        if show_search {
+            self.render_search_bar(f, main_chunks[1]);
+            self.render_helper_bar(f, main_chunks[2]);
+        } else {
+            self.render_helper_bar(f, main_chunks[1]);
+        }
+    }
+
+    fn render_tabs(&self, f: &mut Frame, area: Rect) {
+        let titles: Vec<Line> = self
+            .targets
+            .iter()
+            .enumerate()
+            .map(|(i, target)| Line::from(format!("{}: {}", i + 1, target.name)))
+            .collect();
 
-        self.render_system_info(f, content_chunks[0]);
-        self.render_logs(f, content_chunks[1]);
-        self.render_helper_bar(f, main_chunks[1]);
+        let tabs = Tabs::new(titles)
+            .select(self.focus)
+            .block(Block::default().borders(Borders::ALL).title("Targets (Tab/1-9: switch)"))
+            .highlight_style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD));
+
+        f.render_widget(tabs, area);
+    }
+
+    fn render_search_bar(&self, f: &mut Frame, area: Rect) {
+        let cursor = if self.search_active { "_" } else { "" };
+        let title = if self.search_active {
+            "Search (Enter: apply, Esc: clear)"
+        } else {
+            "Search (/: edit, Esc: clear)"
+        };
+
+        let paragraph = Paragraph::new(Line::from(vec![
+            Span::styled("/", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+            Span::raw(format!("{}{}", self.search, cursor)),
+        ]))
+        .block(Block::default().borders(Borders::ALL).title(title));
+
+        f.render_widget(paragraph, area);
     }
 
     fn render_system_info(&self, f: &mut Frame, area: Rect) {
-        let system_info = self.system_info.lock().unwrap();
+        let system_info = self.current().system_info.lock().unwrap();
         
         let mut lines = vec![
             Line::from(vec![
@@ -152,6 +1288,19 @@ impl TuiApp {
         ];
 
         if let Some(info) = system_info.as_ref() {
+            // One glanceable verdict above the details
+            let (health, reasons) = assess_health(info, self.current().connection_lost.load(Ordering::Relaxed));
+            let (label, color) = match health {
+                Health::Ok => ("● OK", Color::Green),
+                Health::Warn => ("● WARN", Color::Yellow),
+                Health::Critical => ("● CRITICAL", Color::Red),
+            };
+            let mut health_spans = vec![Span::styled(label, Style::default().fg(color).add_modifier(Modifier::BOLD))];
+            if !reasons.is_empty() {
+                health_spans.push(Span::styled(format!(": {}", reasons.join(", ")), Style::default().fg(color)));
+            }
+            lines.insert(1, Line::from(health_spans));
+
             lines.extend(vec![
                 Line::from(vec![
                     Span::styled("Hostname: ", Style::default().fg(Color::Cyan)),
@@ -168,52 +1317,555 @@ impl TuiApp {
                 Line::from(""),
             ]);
 
+            if let Some(virtualization) = &info.virtualization {
+                // Hardware details from inside a container or VM describe the host or the emulated board
+                let line = if virtualization == "none" {
+                    Line::from(vec![
+                        Span::styled("Virtualization: ", Style::default().fg(Color::Cyan)),
+                        Span::styled("none (bare metal)", Style::default().fg(Color::Green)),
+                    ])
+                } else {
+                    Line::from(vec![
+                        Span::styled("Virtualization: ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+                        Span::styled(
+                            format!("{} (not real hardware)", virtualization),
+                            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+                        ),
+                    ])
+                };
+                lines.push(line);
+                lines.push(Line::from(""));
+            }
+
             if let Some(chip) = &info.chip {
                 lines.push(Line::from(vec![
                     Span::styled("Chip: ", Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)),
                     Span::styled(chip, Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)),
                 ]));
+                if let Some(gpu) = &info.gpu {
+                    lines.push(Line::from(vec![
+                        Span::styled("GPU: ", Style::default().fg(Color::Cyan)),
+                        Span::raw(gpu),
+                    ]));
+                }
+                if let Some(vpu) = &info.vpu {
+                    lines.push(Line::from(vec![
+                        Span::styled("VPU: ", Style::default().fg(Color::Cyan)),
+                        Span::raw(vpu),
+                    ]));
+                }
                 lines.push(Line::from(""));
             }
 
+            if let Some(serial_number) = &info.serial_number {
+                lines.push(Line::from(vec![
+                    Span::styled("Serial: ", Style::default().fg(Color::Cyan)),
+                    Span::raw(serial_number),
+                ]));
+                lines.push(Line::from(""));
+            }
+
+            if let Some(display) = &info.display {
+                lines.push(Line::from(vec![
+                    Span::styled("Display: ", Style::default().fg(Color::Cyan)),
+                    Span::raw(display),
+                ]));
+                lines.push(Line::from(""));
+            }
+
+            if let Some(power) = &info.power_status {
+                let power_color = if power.starts_with("OK") { Color::Green } else { Color::Red };
+                lines.push(Line::from(vec![
+                    Span::styled("Power: ", Style::default().fg(power_color).add_modifier(Modifier::BOLD)),
+                    Span::styled(power.clone(), Style::default().fg(power_color).add_modifier(Modifier::BOLD)),
+                ]));
+                lines.push(Line::from(""));
+            }
+
+            if let Some(battery) = &info.battery {
+                let battery_color = match battery.level_percent {
+                    _ if battery.status == "charging" || battery.status == "full" => Color::Green,
+                    Some(level) if level <= 15 => Color::Red,
+                    Some(level) if level <= 30 => Color::Yellow,
+                    _ => Color::Green,
+                };
+                lines.push(Line::from(vec![
+                    Span::styled("Battery: ", Style::default().fg(Color::Cyan)),
+                    Span::styled(battery.summary(), Style::default().fg(battery_color)),
+                ]));
+                lines.push(Line::from(""));
+            }
+
+            lines.push(Line::from(vec![
+                Span::styled("CPU: ", Style::default().fg(Color::Cyan)),
+                Span::raw(&info.cpu_info),
+            ]));
+
+            if !info.cpu_freqs.is_empty() {
+                let freqs: Vec<String> = info.cpu_freqs
+                    .iter()
+                    .map(|f| format!("{}:{}/{}", f.core, f.cur_mhz, f.max_mhz))
+                    .collect();
+                lines.push(Line::from(vec![
+                    Span::styled("CPU MHz: ", Style::default().fg(Color::Cyan)),
+                    Span::raw(freqs.join(" ")),
+                ]));
+            }
+
+            if let Some(usage) = info.cpu_usage_percent {
+                let cores: Vec<String> = info.cpu_core_usage.iter().map(|core| format!("{:.0}", core)).collect();
+                lines.push(Line::from(vec![
+                    Span::styled("CPU busy: ", Style::default().fg(Color::Cyan)),
+                    Span::raw(format!("{:.0}%", usage)),
+                    Span::styled(
+                        if cores.is_empty() { String::new() } else { format!("  (per core: {})", cores.join(" ")) },
+                        Style::default().fg(Color::Gray),
+                    ),
+                ]));
+            }
+
             lines.extend(vec![
-                Line::from(vec![
-                    Span::styled("CPU: ", Style::default().fg(Color::Cyan)),
-                    Span::raw(&info.cpu_info),
-                ]),
                 Line::from(vec![
                     Span::styled("Memory: ", Style::default().fg(Color::Cyan)),
                     Span::raw(&info.memory),
+                    Span::raw(match (&info.mem_available, &info.mem_cached) {
+                        (Some(available), Some(cached)) => format!("  ({} available, {} cached)", available, cached),
+                        (Some(available), None) => format!("  ({} available)", available),
+                        _ => String::new(),
+                    }),
+                ]),
+                Line::from(vec![
+                    Span::styled("Swap: ", Style::default().fg(Color::Cyan)),
+                    Span::raw(&info.swap),
                 ]),
                 Line::from(vec![
                     Span::styled("Uptime: ", Style::default().fg(Color::Cyan)),
                     Span::raw(&info.uptime),
+                    Span::raw(match info.load_average {
+                        Some((one, five, fifteen)) => format!("  (load {:.2} {:.2} {:.2})", one, five, fifteen),
+                        None => String::new(),
+                    }),
                 ]),
                 Line::from(vec![
                     Span::styled("OS: ", Style::default().fg(Color::Cyan)),
                     Span::raw(&info.os_info),
                 ]),
             ]);
+            if let Some(init_system) = &info.init_system {
+                lines.push(Line::from(vec![
+                    Span::styled("Init: ", Style::default().fg(Color::Cyan)),
+                    Span::raw(init_system.clone()),
+                ]));
+            }
+            if let Some(userland) = &info.userland {
+                lines.push(Line::from(vec![
+                    Span::styled("Userland: ", Style::default().fg(Color::Cyan)),
+                    Span::raw(userland.clone()),
+                ]));
+            }
+            if let Some(updates) = &info.pending_updates {
+                let color = if updates.count.unwrap_or(0) > 0 { Color::Yellow } else { Color::White };
+                lines.push(Line::from(vec![
+                    Span::styled("Packages: ", Style::default().fg(Color::Cyan)),
+                    Span::styled(updates.label(), Style::default().fg(color)),
+                ]));
+            }
+
+            let interfaces: Vec<&NetIface> = info.network.iter().filter(|iface| iface.name != "lo").collect();
+            if !interfaces.is_empty() {
+                lines.push(Line::from(""));
+                lines.push(Line::from(vec![
+                    Span::styled("Network:", Style::default().fg(Color::Cyan)),
+                ]));
+                for iface in interfaces {
+                    let state_color = if iface.state == "UP" { Color::Green } else { Color::Red };
+                    let mut addresses = iface.ipv4.clone();
+                    addresses.extend(iface.ipv6.iter().filter(|addr| !addr.starts_with("fe80")).cloned());
+                    lines.push(Line::from(vec![
+                        Span::raw(format!("  {} ", iface.name)),
+                        Span::styled(iface.state.clone(), Style::default().fg(state_color)),
+                        Span::raw(format!(" {}", addresses.join(" "))),
+                        Span::styled(
+                            iface.mac.as_ref().map(|mac| format!(" [{}]", mac)).unwrap_or_default(),
+                            Style::default().fg(Color::Gray),
+                        ),
+                    ]));
+
+                    if let Some(wifi) = &iface.wifi {
+                        let mut spans = vec![Span::raw("    ")];
+                        if let Some(bars) = wifi.signal_bars() {
+                            let bar_color = match bars {
+                                3.. => Color::Green,
+                                2 => Color::Yellow,
+                                _ => Color::Red,
+                            };
+                            let glyphs: Vec<char> = "▂▄▆█".chars().collect();
+                            spans.push(Span::styled(glyphs[..bars].iter().collect::<String>(), Style::default().fg(bar_color)));
+                            spans.push(Span::styled(glyphs[bars..].iter().collect::<String>(), Style::default().fg(Color::DarkGray)));
+                        }
+                        if let Some(dbm) = wifi.signal_dbm {
+                            spans.push(Span::raw(format!(" {} dBm", dbm)));
+                        }
+                        if let Some(ssid) = &wifi.ssid {
+                            spans.push(Span::styled(format!(" \"{}\"", ssid), Style::default().fg(Color::Cyan)));
+                        }
+                        if let Some(bitrate) = &wifi.tx_bitrate {
+                            spans.push(Span::styled(format!(" {}", bitrate), Style::default().fg(Color::Gray)));
+                        }
+                        lines.push(Line::from(spans));
+                    }
+                }
+            }
+
+            if !info.storage_devices.is_empty() {
+                lines.push(Line::from(""));
+                lines.push(Line::from(vec![
+                    Span::styled("Storage:", Style::default().fg(Color::Cyan)),
+                ]));
+                for device in &info.storage_devices {
+                    let mut description = format!("  {} {}", device.name, device.size_label());
+                    for detail in [&device.media, &device.model].into_iter().flatten() {
+                        description.push_str(&format!(" {}", detail));
+                    }
+                    let mut spans = vec![Span::raw(description)];
+                    if let Some(wear) = device.wear_label() {
+                        let wear_color = match device.wear_level.unwrap_or(0) {
+                            9.. => Color::Red,
+                            7..=8 => Color::Yellow,
+                            _ => Color::Green,
+                        };
+                        spans.push(Span::styled(format!("  wear {}", wear), Style::default().fg(wear_color)));
+                    }
+                    lines.push(Line::from(spans));
+                }
+            }
+
+            if !info.mounts.is_empty() {
+                lines.push(Line::from(""));
+                lines.push(Line::from(vec![
+                    Span::styled("Mounts:", Style::default().fg(Color::Cyan)),
+                ]));
+                let width = info.mounts.iter().map(|mount| mount.mountpoint.len()).max().unwrap_or(0);
+                for mount in &info.mounts {
+                    let used = mount.used_percent.map(|used| format!("{:>3.0}%", used)).unwrap_or_else(|| "   -".to_string());
+                    let flags = if mount.read_only { " ro" } else { "" };
+                    let style = if mount.read_only || mount.is_near_full() {
+                        Style::default().fg(Color::Red)
+                    } else {
+                        Style::default()
+                    };
+                    lines.push(Line::from(vec![
+                        Span::styled(
+                            format!("  {:<width$} {:<8} {:>8} {}{}", mount.mountpoint, mount.fstype, mount.size_label(), used, flags, width = width),
+                            style,
+                        ),
+                        Span::styled(format!("  {}", mount.device), Style::default().fg(Color::Gray)),
+                    ]));
+                }
+            }
+
+            if !info.pci_devices.is_empty() {
+                lines.push(Line::from(""));
+                lines.push(Line::from(vec![
+                    Span::styled("PCI:", Style::default().fg(Color::Cyan)),
+                ]));
+                for device in &info.pci_devices {
+                    let (address, description) = device.split_once(' ').unwrap_or((device.as_str(), ""));
+                    lines.push(Line::from(vec![
+                        Span::styled(format!("  {} ", address), Style::default().fg(Color::Gray)),
+                        Span::raw(description.to_string()),
+                    ]));
+                }
+            }
+
+            if !info.modules.is_empty() {
+                // Follows the logs search, so '/' narrows a long module list down to one driver
+                let query = self.search.to_lowercase();
+                let matching: Vec<&KernelModule> = info
+                    .modules
+                    .iter()
+                    .filter(|module| module.name.to_lowercase().contains(&query))
+                    .collect();
+                let title = if query.is_empty() {
+                    format!("Modules ({}):", info.modules.len())
+                } else {
+                    format!("Modules matching \"{}\" ({}/{}):", self.search, matching.len(), info.modules.len())
+                };
+                lines.push(Line::from(""));
+                lines.push(Line::from(vec![
+                    Span::styled(title, Style::default().fg(Color::Cyan)),
+                ]));
+                let mut spans = vec![Span::raw("  ")];
+                for module in matching {
+                    spans.push(Span::raw(module.name.clone()));
+                    spans.push(Span::styled(format!("({}) ", module.use_count), Style::default().fg(Color::Gray)));
+                }
+                lines.push(Line::from(spans));
+            }
+
+            if !info.gpio.is_empty() {
+                lines.push(Line::from(""));
+                lines.push(Line::from(vec![
+                    Span::styled("GPIO:", Style::default().fg(Color::Cyan)),
+                ]));
+                for gpio in &info.gpio {
+                    let value = gpio.value.map(|v| format!("={}", v)).unwrap_or_default();
+                    lines.push(Line::from(vec![
+                        Span::raw(format!("  {}:{:<4}", gpio.chip, gpio.line)),
+                        Span::raw(format!(" {:<12}", gpio.name.as_deref().unwrap_or("-"))),
+                        Span::styled(format!(" {}{}", gpio.direction, value), Style::default().fg(Color::Yellow)),
+                        Span::styled(
+                            gpio.consumer.as_ref().map(|c| format!(" [{}]", c)).unwrap_or_default(),
+                            Style::default().fg(Color::Gray),
+                        ),
+                    ]));
+                }
+            }
+
+            if !info.extra.is_empty() {
+                lines.push(Line::from(""));
+                lines.push(Line::from(vec![
+                    Span::styled("Extra:", Style::default().fg(Color::Cyan)),
+                ]));
+                for extra in &info.extra {
+                    lines.push(Line::from(vec![
+                        Span::styled(format!("  {}: ", extra.label), Style::default().fg(Color::Cyan)),
+                        Span::raw(extra.value.clone().unwrap_or_else(|| "n/a".to_string())),
+                    ]));
+                }
+            }
+
+            let temp_color = |temp: f64| {
+                if temp >= 80.0 {
+                    Color::Red
+                } else if temp >= 65.0 {
+                    Color::Yellow
+                } else {
+                    Color::Green
+                }
+            };
+            if THERMAL_ZONE.get().is_none() && info.thermal_zones.len() > 1 {
+                // No zone picked: every zone under its type, so a hot GPU or DDR isn't hidden
+                for zone in &info.thermal_zones {
+                    lines.push(Line::from(vec![
+                        Span::styled(format!("Temp {}: ", zone.label), Style::default().fg(Color::Cyan)),
+                        Span::styled(format!("{:.1}°C", zone.celsius()), Style::default().fg(temp_color(zone.celsius()))),
+                    ]));
+                }
+            } else if let Some(temp) = info.temperature {
+                let label = match selected_thermal_zone(&info.thermal_zones) {
+                    Some(zone) if info.thermal_zones.len() > 1 => format!("Temp {}: ", zone.label),
+                    _ => "Temp: ".to_string(),
+                };
+                lines.push(Line::from(vec![
+                    Span::styled(label, Style::default().fg(Color::Cyan)),
+                    Span::styled(format!("{:.1}°C", temp), Style::default().fg(temp_color(temp))),
+                ]));
+            }
+
+            for fan in &info.fans {
+                // A stalled fan with a tachometer is worth noticing
+                let fan_color = if fan.rpm == Some(0) { Color::Yellow } else { Color::Green };
+                lines.push(Line::from(vec![
+                    Span::styled(format!("Fan {}: ", fan.label), Style::default().fg(Color::Cyan)),
+                    Span::styled(fan.value_label(), Style::default().fg(fan_color)),
+                ]));
+            }
         } else {
             lines.push(Line::from(vec![
                 Span::styled("No system information available", Style::default().fg(Color::Red))
             ]));
         }
 
+        if let Ok(last_refresh) = self.current().last_refresh.lock() {
+            if let Some(time) = last_refresh.as_ref() {
+                lines.push(Line::from(""));
+                lines.push(Line::from(vec![
+                    Span::styled("Last refresh: ", Style::default().fg(Color::Gray)),
+                    Span::styled(time.clone(), Style::default().fg(Color::Gray)),
+                ]));
+            }
+        }
+
+        self.info_line_count.set(lines.len() as u16);
+        let paragraph = Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title("System Info")
+                    .border_style(self.panel_border_style(Panel::Info)),
+            )
+            .wrap(Wrap { trim: true })
+            .scroll((self.info_scroll, 0));
+
+        f.render_widget(paragraph, area);
+    }
+
+    fn render_trends(&self, f: &mut Frame, area: Rect) {
+        let history = self.current().history.lock().unwrap();
+
+        let block = Block::default().borders(Borders::ALL).title("Trends");
+        let inner = block.inner(area);
+        f.render_widget(block, area);
+
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(TREND_HEIGHT); 3].as_ref())
+            .split(inner);
+
+        let cpu = latest_samples(&history.cpu_usage, rows[0].width);
+        let cpu_title = match cpu.last() {
+            Some(percent) => format!("CPU busy {}%", percent),
+            None => "CPU busy n/a".to_string(),
+        };
+        let sparkline = Sparkline::default()
+            .block(Block::default().title(Span::styled(cpu_title, Style::default().fg(Color::Cyan))))
+            .data(&cpu)
+            .max(100)
+            .style(Style::default().fg(Color::Yellow));
+        f.render_widget(sparkline, rows[0]);
+
+        let memory = latest_samples(&history.memory_used, rows[1].width);
+        let memory_title = match memory.last() {
+            Some(percent) => format!("Memory used {}%", percent),
+            None => "Memory used n/a".to_string(),
+        };
+        let sparkline = Sparkline::default()
+            .block(Block::default().title(Span::styled(memory_title, Style::default().fg(Color::Cyan))))
+            .data(&memory)
+            .max(100)
+            .style(Style::default().fg(Color::Magenta));
+        f.render_widget(sparkline, rows[1]);
+
+        // Unscaled so the bars use the full height; the title carries the actual value
+        let load = latest_samples(&history.load, rows[2].width);
+        let load_title = match load.last() {
+            Some(load) => format!("Load (1m) {:.2}", *load as f64 / 100.0),
+            None => "Load (1m) n/a".to_string(),
+        };
+        let sparkline = Sparkline::default()
+            .block(Block::default().title(Span::styled(load_title, Style::default().fg(Color::Cyan))))
+            .data(&load)
+            .style(Style::default().fg(Color::Green));
+        f.render_widget(sparkline, rows[2]);
+    }
+
+    fn render_alerts(&self, f: &mut Frame, area: Rect) {
+        let system_info = self.current().system_info.lock().unwrap();
+        let Some(info) = system_info.as_ref() else { return };
+
+        let mut lines: Vec<Line> = info
+            .alerts
+            .iter()
+            .map(|alert| {
+                Line::from(vec![
+                    Span::styled(format!("[{}] ", alert.timestamp), Style::default().fg(Color::Gray)),
+                    Span::styled(format!("{}: ", alert.kind), Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)),
+                    Span::raw(alert.message.clone()),
+                ])
+            })
+            .collect();
+        lines.extend(info.failed_units.iter().map(|unit| {
+            Line::from(vec![
+                Span::styled("failed: ", Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)),
+                Span::styled(format!("{} ", unit.name), Style::default().fg(Color::Yellow)),
+                Span::raw(unit.summary()),
+            ])
+        }));
+
+        let paragraph = Paragraph::new(lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Alerts (OOM / panic / call trace / failed units)")
+                .border_style(Style::default().fg(Color::Red)),
+        );
+
+        f.render_widget(paragraph, area);
+    }
+
+    fn render_processes(&self, f: &mut Frame, area: Rect) {
+        let system_info = self.current().system_info.lock().unwrap();
+        let Some(info) = system_info.as_ref() else { return };
+
+        let percent = |value: Option<f64>| value.map(|v| format!("{:>5.1}", v)).unwrap_or_else(|| format!("{:>5}", "-"));
+        let mut lines = vec![Line::from(vec![
+            Span::styled(format!("{:>7} {:>5} {:>5}  COMMAND", "PID", "%CPU", "%MEM"), Style::default().fg(Color::Gray)),
+        ])];
+        for process in &info.top_processes {
+            let cpu_color = match process.cpu_percent.unwrap_or(0.0) {
+                cpu if cpu >= 80.0 => Color::Red,
+                cpu if cpu >= 30.0 => Color::Yellow,
+                _ => Color::Green,
+            };
+            lines.push(Line::from(vec![
+                Span::raw(format!("{:>7} ", process.pid)),
+                Span::styled(percent(process.cpu_percent), Style::default().fg(cpu_color)),
+                Span::raw(format!(" {}  ", percent(process.mem_percent))),
+                Span::styled(process.command.clone(), Style::default().fg(Color::Cyan)),
+            ]));
+        }
+
+        let paragraph = Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title("Top Processes"));
+
+        f.render_widget(paragraph, area);
+    }
+
+    fn render_boot_info(&self, f: &mut Frame, area: Rect) {
+        let system_info = self.current().system_info.lock().unwrap();
+        let Some(info) = system_info.as_ref() else { return };
+        let boot = &info.boot_info;
+
+        let mut lines = Vec::new();
+        for (label, value) in [("Root: ", &boot.root), ("Mounted: ", &boot.root_mount), ("Bootloader: ", &boot.bootloader)] {
+            if let Some(value) = value {
+                lines.push(Line::from(vec![
+                    Span::styled(label, Style::default().fg(Color::Cyan)),
+                    Span::styled(value.clone(), Style::default().fg(Color::Green)),
+                ]));
+            }
+        }
+        if let Some(cmdline) = &boot.cmdline {
+            lines.push(Line::from(vec![
+                Span::styled("Cmdline: ", Style::default().fg(Color::Cyan)),
+                Span::raw(cmdline.clone()),
+            ]));
+        }
+        if let Some(bootargs) = &boot.bootargs {
+            // The kernel was not booted with the device tree's arguments, often a boot-order clue
+            lines.push(Line::from(vec![
+                Span::styled("DT bootargs: ", Style::default().fg(Color::Yellow)),
+                Span::raw(bootargs.clone()),
+            ]));
+        }
+        if !info.overlays.is_empty() {
+            lines.push(Line::from(vec![
+                Span::styled("Overlays: ", Style::default().fg(Color::Cyan)),
+                Span::styled(info.overlays.join(", "), Style::default().fg(Color::Green)),
+            ]));
+        }
+
         let paragraph = Paragraph::new(lines)
-            .block(Block::default().borders(Borders::ALL).title("System Info"))
+            .block(Block::default().borders(Borders::ALL).title("Boot"))
             .wrap(Wrap { trim: true });
 
         f.render_widget(paragraph, area);
     }
 
     fn render_logs(&self, f: &mut Frame, area: Rect) {
-        let logs = self.logs.lock().unwrap();
+        let logs = self.current().logs.lock().unwrap();
+
+        // Rows inside the borders
+        let height = area.height.saturating_sub(2) as usize;
+        self.log_view_height.set(height);
         
-        let items: Vec<ListItem> = logs
+        // Newest first, filtered by the search string
+        let visible = matching_logs(&logs, &self.search);
+        let offset = self.log_scroll.min(visible.len().saturating_sub(1));
+        let items: Vec<ListItem> = visible
             .iter()
-            .rev() // Show newest first
-            .take(20) // Show last 20 entries
+            .copied()
+            .skip(offset)
+            .take(height)
             .map(|log| {
                 let level_color = match log.level.as_str() {
                     "ERROR" => Color::Red,
@@ -223,22 +1875,46 @@ impl TuiApp {
                     _ => Color::White,
                 };
 
-                ListItem::new(Line::from(vec![
-                    Span::styled(
-                        format!("[{}] ", log.timestamp),
-                        Style::default().fg(Color::Gray),
-                    ),
+                // --raw-logs entries keep their timestamp in the message
+                let timestamp = if log.timestamp.is_empty() { String::new() } else { format!("[{}] ", log.timestamp) };
+                let mut spans = vec![
+                    Span::styled(timestamp, Style::default().fg(Color::Gray)),
                     Span::styled(
                         format!("{}: ", log.level),
                         Style::default().fg(level_color).add_modifier(Modifier::BOLD),
                     ),
-                    Span::raw(&log.message),
-                ]))
+                ];
+                spans.extend(highlight_matches(&log.message, &self.search));
+                let selected = self
+                    .selected_log
+                    .as_ref()
+                    .is_some_and(|(timestamp, message)| &log.timestamp == timestamp && &log.message == message);
+                let item = ListItem::new(Line::from(spans));
+                if selected {
+                    item.style(Style::default().bg(Color::DarkGray))
+                } else {
+                    item
+                }
             })
             .collect();
 
+        let shown = items.len();
+        let mut title = if self.log_scroll > 0 {
+            format!("Logs (showing {}-{} of {}, G: live)", offset + 1, offset + shown, visible.len())
+        } else {
+            format!("Logs (showing {}-{} of {})", if shown > 0 { 1 } else { 0 }, shown, visible.len())
+        };
+        if !self.search.is_empty() {
+            title.push_str(&format!(" [filter: {}]", self.search));
+        }
+
         let list = List::new(items)
-            .block(Block::default().borders(Borders::ALL).title("Logs"))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(title)
+                    .border_style(self.panel_border_style(Panel::Logs)),
+            )
             .style(Style::default().fg(Color::White));
 
         f.render_widget(list, area);
@@ -252,6 +1928,36 @@ impl TuiApp {
             Span::styled("r: ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
             Span::styled("Refresh", Style::default().fg(Color::White)),
             Span::raw("  "),
+            Span::styled("R: ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+            Span::styled("Reconnect", Style::default().fg(Color::White)),
+            Span::raw("  "),
+            Span::styled("↑↓/PgUp/PgDn: ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+            Span::styled("Scroll logs", Style::default().fg(Color::White)),
+            Span::raw("  "),
+            Span::styled("G: ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+            Span::styled("Live", Style::default().fg(Color::White)),
+            Span::raw("  "),
+            Span::styled("/: ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+            Span::styled("Search", Style::default().fg(Color::White)),
+            Span::raw("  "),
+            Span::styled("Tab: ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+            Span::styled("Next target", Style::default().fg(Color::White)),
+            Span::raw("  "),
+            Span::styled("v: ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+            Span::styled(
+                match self.split {
+                    Direction::Horizontal => "Stack panels",
+                    Direction::Vertical => "Side by side",
+                },
+                Style::default().fg(Color::White),
+            ),
+            Span::raw("  "),
+            Span::styled("Mouse: ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+            Span::styled("Focus/select/resize", Style::default().fg(Color::White)),
+            Span::raw("  "),
+            Span::styled("y: ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+            Span::styled("Copy line", Style::default().fg(Color::White)),
+            Span::raw("  "),
             Span::styled("ESC: ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
             Span::styled("Exit", Style::default().fg(Color::White)),
         ]);
@@ -274,6 +1980,7 @@ pub fn setup_terminal() -> Result<Terminal<CrosstermBackend<io::Stdout>>> {
 }
 
 pub fn restore_terminal(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> Result<()> {
+    crate::verbose::detach_tui();
     disable_raw_mode()?;
     execute!(
         terminal.backend_mut(),
@@ -283,3 +1990,28 @@ pub fn restore_terminal(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -
     terminal.show_cursor()?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn highlighted(message: &str, query: &str) -> Vec<String> {
+        highlight_matches(message, query)
+            .into_iter()
+            .filter(|span| span.style.bg == Some(Color::Yellow))
+            .map(|span| span.content.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn highlight_matches_ignores_case() {
+        assert_eq!(highlighted("USB disconnect, usb 1-1", "usb"), ["USB", "usb"]);
+    }
+
+    #[test]
+    fn highlight_matches_keeps_char_boundaries() {
+        // Both lowercase to the same total length, but "ẞ" shrinks and "Ⱥ" grows
+        assert_eq!(highlighted("ẞȺ", "ⱥ"), ["Ⱥ"]);
+        assert_eq!(highlighted("ẞȺ x", "x"), ["x"]);
+    }
+}
@@ -0,0 +1,149 @@
+/// The multi-call binary's banner, then where each tool the collectors use resolves to,
+/// e.g. "ps /bin/busybox"; tools that aren't installed print nothing
+pub const PROBE_COMMAND: &str = "busybox 2>/dev/null | head -n 1; echo __TOOLS__; for t in sh ps top ip ifconfig free uptime journalctl; do p=$(command -v $t) && echo \"$t $(readlink -f $p 2>/dev/null || echo $p)\"; done; true";
+
+/// Multi-call binaries whose applets have reduced flags and output
+const MULTI_CALL_BINARIES: [&str; 2] = ["busybox", "toybox"];
+
+/// Which of the tools the collectors use are installed, and which are BusyBox / toybox applets
+#[derive(Debug, Clone, Default)]
+pub struct Userland {
+    /// False when the probe itself failed; nothing is known then
+    probed: bool,
+    /// e.g. "BusyBox v1.36.1"
+    banner: Option<String>,
+    /// Tool name and the binary it resolves to
+    tools: Vec<(String, String)>,
+}
+
+impl Userland {
+    pub fn parse(output: &str) -> Self {
+        let Some((banner, tools)) = output.split_once("__TOOLS__") else {
+            return Self::default();
+        };
+
+        // "BusyBox v1.36.1 (2023-11-07 18:53:09 UTC) multi-call binary."
+        let banner = banner
+            .lines()
+            .find(|line| line.starts_with("BusyBox"))
+            .map(|line| line.split_whitespace().take(2).collect::<Vec<_>>().join(" "));
+        let tools = tools
+            .lines()
+            .filter_map(|line| line.trim().split_once(' '))
+            .map(|(tool, path)| (tool.to_string(), path.trim().to_string()))
+            .collect();
+        Self { probed: true, banner, tools }
+    }
+
+    /// True only when the probe ran and didn't find the tool
+    pub fn lacks(&self, tool: &str) -> bool {
+        self.probed && !self.tools.iter().any(|(name, _)| name == tool)
+    }
+
+    /// "busybox" or "toybox" when the tool is an applet of that binary
+    pub fn applet_of(&self, tool: &str) -> Option<&'static str> {
+        let (_, path) = self.tools.iter().find(|(name, _)| name == tool)?;
+        let binary = path.rsplit('/').next().unwrap_or(path);
+        MULTI_CALL_BINARIES.into_iter().find(|multi_call| binary == *multi_call)
+    }
+
+    /// e.g. "BusyBox v1.36.1 (sh ps top free uptime)", None when every tool is a full version
+    pub fn describe(&self) -> Option<String> {
+        let applets: Vec<&str> = self
+            .tools
+            .iter()
+            .filter(|(name, _)| self.applet_of(name).is_some())
+            .map(|(name, _)| name.as_str())
+            .collect();
+        let binary = applets.first().and_then(|tool| self.applet_of(tool))?;
+        let name = match (binary, &self.banner) {
+            ("busybox", Some(banner)) => banner.clone(),
+            ("busybox", None) => "BusyBox".to_string(),
+            _ => "toybox".to_string(),
+        };
+        Some(format!("{} ({})", name, applets.join(" ")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Alpine-style image where every tool is a BusyBox applet and there is no journalctl
+    const BUSYBOX_PROBE: &str = "\
+BusyBox v1.36.1 (2023-11-07 18:53:09 UTC) multi-call binary.
+__TOOLS__
+sh /bin/busybox
+ps /bin/busybox
+top /bin/busybox
+ip /bin/busybox
+ifconfig /bin/busybox
+free /bin/busybox
+uptime /bin/busybox
+";
+
+    /// Android: mksh as sh, the real `ip`, toybox for the rest
+    const TOYBOX_PROBE: &str = "\
+__TOOLS__
+sh /system/bin/sh
+ps /system/bin/toybox
+top /system/bin/toybox
+ip /system/bin/ip
+ifconfig /system/bin/toybox
+free /system/bin/toybox
+uptime /system/bin/toybox
+";
+
+    /// Debian desktop with procps and iproute2, no ifconfig
+    const PROCPS_PROBE: &str = "\
+__TOOLS__
+sh /usr/bin/dash
+ps /usr/bin/ps
+top /usr/bin/top
+ip /usr/bin/ip
+free /usr/bin/free
+uptime /usr/bin/uptime
+journalctl /usr/bin/journalctl
+";
+
+    #[test]
+    fn busybox_image() {
+        let userland = Userland::parse(BUSYBOX_PROBE);
+        assert_eq!(userland.applet_of("ps"), Some("busybox"));
+        assert_eq!(userland.applet_of("free"), Some("busybox"));
+        assert!(userland.lacks("journalctl"));
+        assert!(!userland.lacks("ip"));
+        assert_eq!(userland.describe().as_deref(), Some("BusyBox v1.36.1 (sh ps top ip ifconfig free uptime)"));
+    }
+
+    #[test]
+    fn toybox_android() {
+        let userland = Userland::parse(TOYBOX_PROBE);
+        assert_eq!(userland.applet_of("sh"), None);
+        assert_eq!(userland.applet_of("ip"), None);
+        assert_eq!(userland.applet_of("top"), Some("toybox"));
+        assert_eq!(userland.applet_of("free"), Some("toybox"));
+        assert!(userland.lacks("journalctl"));
+        assert_eq!(userland.describe().as_deref(), Some("toybox (ps top ifconfig free uptime)"));
+    }
+
+    #[test]
+    fn procps_desktop() {
+        let userland = Userland::parse(PROCPS_PROBE);
+        assert_eq!(userland.applet_of("ps"), None);
+        assert!(userland.lacks("ifconfig"));
+        assert!(!userland.lacks("journalctl"));
+        assert_eq!(userland.describe(), None);
+    }
+
+    #[test]
+    fn failed_probe() {
+        // Nothing known: no tool counts as missing, so collectors keep their defaults
+        for output in ["", "sh: 1: Syntax error"] {
+            let userland = Userland::parse(output);
+            assert!(!userland.lacks("ip"));
+            assert_eq!(userland.applet_of("ps"), None);
+            assert_eq!(userland.describe(), None);
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use crate::tui::{self, LogEntry};
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Number of `-v` flags given on the command line
+static LEVEL: AtomicU8 = AtomicU8::new(0);
+
+/// `--quiet`, or a machine-readable `info --format`
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// Logs panels of the running TUI by target name; while set, messages go there instead of
+/// stdout / stderr, which would draw over the alternate screen
+static TUI_LOGS: Mutex<Vec<(String, Arc<Mutex<Vec<LogEntry>>>)>> = Mutex::new(Vec::new());
+
+pub fn set_level(level: u8) {
+    LEVEL.store(level, Ordering::Relaxed);
+}
+
+pub fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+pub fn quiet() -> bool {
+    QUIET.load(Ordering::Relaxed)
+}
+
+/// Routes messages to the TUI's logs panels until `detach_tui`
+pub fn attach_tui(targets: Vec<(String, Arc<Mutex<Vec<LogEntry>>>)>) {
+    if let Ok(mut logs) = TUI_LOGS.lock() {
+        *logs = targets;
+    }
+}
+
+pub fn detach_tui() {
+    if let Ok(mut logs) = TUI_LOGS.lock() {
+        logs.clear();
+    }
+}
+
+/// Adds `message` to the logs panel of `target`, or of the first target when none matches;
+/// false when no TUI is running
+fn to_tui(target: Option<&str>, level: &str, message: String) -> bool {
+    let Ok(targets) = TUI_LOGS.lock() else { return false };
+    let Some((_, logs)) = targets.iter().find(|(name, _)| Some(name.as_str()) == target).or(targets.first()) else {
+        return false;
+    };
+    if let Ok(mut logs) = logs.lock() {
+        tui::push_log(&mut logs, LogEntry {
+            timestamp: chrono::Local::now().format("%H:%M:%S").to_string(),
+            level: level.to_string(),
+            message,
+        });
+    }
+    true
+}
+
+/// Prints a progress message ("Connecting to ...") to stdout, unless quiet so stdout only carries results
+pub fn status(message: &str) {
+    if !quiet() && !to_tui(None, "INFO", message.to_string()) {
+        println!("{}", message);
+    }
+}
+
+/// `-v`: prints a remote command to stderr before it runs
+pub fn command(connection: &str, target: &str, command: &str) {
+    if LEVEL.load(Ordering::Relaxed) >= 1 && !to_tui(Some(target), "DEBUG", format!("[{}] $ {}", connection, command)) {
+        eprintln!("[{} {}] $ {}", connection, target, command);
+    }
+}
+
+/// `-vv`: prints the raw output of a remote command before it is parsed
+pub fn output(connection: &str, target: &str, output: &str) {
+    if LEVEL.load(Ordering::Relaxed) >= 2 {
+        for line in output.lines() {
+            if !to_tui(Some(target), "DEBUG", format!("[{}] > {}", connection, line)) {
+                eprintln!("[{} {}] > {}", connection, target, line);
+            }
+        }
+    }
+}